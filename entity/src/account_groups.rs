@@ -9,6 +9,10 @@ pub struct Model {
     pub id: i32,
     pub user_id: i32,
     pub name: String,
+    pub deleted_at: Option<DateTime>,
+    pub organization_id: Option<i32>,
+    pub revision: i32,
+    pub rotation_days: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -21,8 +25,18 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Users,
+    #[sea_orm(
+        belongs_to = "super::organizations::Entity",
+        from = "Column::OrganizationId",
+        to = "super::organizations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Organizations,
     #[sea_orm(has_many = "super::accounts::Entity")]
     Accounts,
+    #[sea_orm(has_many = "super::collection_members::Entity")]
+    CollectionMembers,
 }
 
 impl Related<super::users::Entity> for Entity {
@@ -37,4 +51,16 @@ impl Related<super::accounts::Entity> for Entity {
     }
 }
 
+impl Related<super::organizations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Organizations.def()
+    }
+}
+
+impl Related<super::collection_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CollectionMembers.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -10,6 +10,17 @@ pub struct Model {
     pub user_id: i32,
     pub account_groups_id: i32,
     pub name: String,
+    pub level: i16,
+    pub favorite: bool,
+    pub url: Option<String>,
+    pub deleted_at: Option<DateTime>,
+    pub item_key: Option<Vec<u8>>,
+    pub revision: i32,
+    pub two_factor: bool,
+    pub rotation_days: Option<i32>,
+    pub rotation_reminded_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub generator_profile: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -32,6 +43,10 @@ pub enum Relation {
     Users,
     #[sea_orm(has_many = "super::account_passwords::Entity")]
     AccountPasswords,
+    #[sea_orm(has_many = "super::account_tags::Entity")]
+    AccountTags,
+    #[sea_orm(has_many = "super::account_shares::Entity")]
+    AccountShares,
 }
 
 impl Related<super::account_groups::Entity> for Entity {
@@ -52,4 +67,26 @@ impl Related<super::account_passwords::Entity> for Entity {
     }
 }
 
+impl Related<super::account_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountTags.def()
+    }
+}
+
+impl Related<super::account_shares::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountShares.def()
+    }
+}
+
+impl Related<super::tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::account_tags::Relation::Tags.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::account_tags::Relation::Accounts.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
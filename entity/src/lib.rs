@@ -4,7 +4,19 @@ pub mod prelude;
 
 pub mod account_groups;
 pub mod account_passwords;
+pub mod account_shares;
+pub mod account_tags;
 pub mod accounts;
+pub mod audit_events;
+pub mod collection_members;
 pub mod devices;
+pub mod emergency_access;
+pub mod organization_invitations;
+pub mod organization_members;
+pub mod organizations;
+pub mod tags;
 pub mod user_password_recovery;
 pub mod users;
+pub mod vault_changes;
+pub mod webhook_deliveries;
+pub mod webhooks;
@@ -2,7 +2,19 @@
 
 pub use super::account_groups::Entity as AccountGroups;
 pub use super::account_passwords::Entity as AccountPasswords;
+pub use super::account_shares::Entity as AccountShares;
+pub use super::account_tags::Entity as AccountTags;
 pub use super::accounts::Entity as Accounts;
+pub use super::audit_events::Entity as AuditEvents;
+pub use super::collection_members::Entity as CollectionMembers;
 pub use super::devices::Entity as Devices;
+pub use super::emergency_access::Entity as EmergencyAccess;
+pub use super::organization_invitations::Entity as OrganizationInvitations;
+pub use super::organization_members::Entity as OrganizationMembers;
+pub use super::organizations::Entity as Organizations;
+pub use super::tags::Entity as Tags;
 pub use super::user_password_recovery::Entity as UserPasswordRecovery;
 pub use super::users::Entity as Users;
+pub use super::vault_changes::Entity as VaultChanges;
+pub use super::webhook_deliveries::Entity as WebhookDeliveries;
+pub use super::webhooks::Entity as Webhooks;
@@ -15,6 +15,10 @@ pub struct Model {
     pub last_login: Option<DateTime>,
     pub fail_attempts: i16,
     pub last_attempt: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub public_key: Option<String>,
+    pub private_key: Option<Vec<u8>>,
+    pub revision: i64,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -27,6 +31,20 @@ pub enum Relation {
     UserPasswordRecovery,
     #[sea_orm(has_many = "super::accounts::Entity")]
     Accounts,
+    #[sea_orm(has_many = "super::tags::Entity")]
+    Tags,
+    #[sea_orm(has_many = "super::account_shares::Entity")]
+    AccountShares,
+    #[sea_orm(has_many = "super::organization_members::Entity")]
+    OrganizationMembers,
+    #[sea_orm(has_many = "super::collection_members::Entity")]
+    CollectionMembers,
+    #[sea_orm(has_many = "super::audit_events::Entity")]
+    AuditEvents,
+    #[sea_orm(has_many = "super::webhooks::Entity")]
+    Webhooks,
+    #[sea_orm(has_many = "super::vault_changes::Entity")]
+    VaultChanges,
 }
 
 impl Related<super::devices::Entity> for Entity {
@@ -53,4 +71,46 @@ impl Related<super::accounts::Entity> for Entity {
     }
 }
 
+impl Related<super::tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tags.def()
+    }
+}
+
+impl Related<super::account_shares::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountShares.def()
+    }
+}
+
+impl Related<super::organization_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OrganizationMembers.def()
+    }
+}
+
+impl Related<super::collection_members::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CollectionMembers.def()
+    }
+}
+
+impl Related<super::audit_events::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AuditEvents.def()
+    }
+}
+
+impl Related<super::webhooks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhooks.def()
+    }
+}
+
+impl Related<super::vault_changes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VaultChanges.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
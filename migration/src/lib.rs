@@ -1,12 +1,44 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20220701_000002_create_tags;
+mod m20220708_000003_accounts_search;
+mod m20220712_000004_accounts_level;
+mod m20220715_000005_soft_delete;
+mod m20220720_000006_account_shares;
+mod m20220725_000007_organizations;
+mod m20220801_000008_collection_permissions;
+mod m20220808_000009_emergency_access;
+mod m20220815_000010_audit_events;
+mod m20220822_000011_webhooks;
+mod m20220829_000012_vault_changes;
+mod m20220905_000013_item_revisions;
+mod m20220912_000014_account_two_factor;
+mod m20220919_000015_rotation_intervals;
+mod m20220926_000016_generator_profiles;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20220701_000002_create_tags::Migration),
+            Box::new(m20220708_000003_accounts_search::Migration),
+            Box::new(m20220712_000004_accounts_level::Migration),
+            Box::new(m20220715_000005_soft_delete::Migration),
+            Box::new(m20220720_000006_account_shares::Migration),
+            Box::new(m20220725_000007_organizations::Migration),
+            Box::new(m20220801_000008_collection_permissions::Migration),
+            Box::new(m20220808_000009_emergency_access::Migration),
+            Box::new(m20220815_000010_audit_events::Migration),
+            Box::new(m20220822_000011_webhooks::Migration),
+            Box::new(m20220829_000012_vault_changes::Migration),
+            Box::new(m20220905_000013_item_revisions::Migration),
+            Box::new(m20220912_000014_account_two_factor::Migration),
+            Box::new(m20220919_000015_rotation_intervals::Migration),
+            Box::new(m20220926_000016_generator_profiles::Migration),
+        ]
     }
 }
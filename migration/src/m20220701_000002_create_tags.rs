@@ -0,0 +1,132 @@
+use sea_orm_migration::{prelude::*, sea_orm::EntityTrait};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220701_000002_create_tags"
+    }
+}
+
+fn stmt_tags() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::tags::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::tags::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(entity::tags::Column::UserId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::tags::Column::Name)
+                .string_len(50)
+                .not_null(),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(entity::tags::Entity, entity::tags::Column::UserId)
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::NoAction)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .index(
+            Index::create()
+                .col(entity::tags::Column::UserId)
+                .col(entity::tags::Column::Name)
+                .unique(),
+        )
+        .to_owned()
+}
+
+fn stmt_account_tags() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::account_tags::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::account_tags::Column::AccountId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::account_tags::Column::TagId)
+                .integer()
+                .not_null(),
+        )
+        .primary_key(
+            Index::create()
+                .col(entity::account_tags::Column::AccountId)
+                .col(entity::account_tags::Column::TagId),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::account_tags::Entity,
+                    entity::account_tags::Column::AccountId,
+                )
+                .to(entity::accounts::Entity, entity::accounts::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::account_tags::Entity,
+                    entity::account_tags::Column::TagId,
+                )
+                .to(entity::tags::Entity, entity::tags::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn stmt_accounts_add_favorite() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::accounts::Entity)
+        .add_column(
+            ColumnDef::new(entity::accounts::Column::Favorite)
+                .boolean()
+                .not_null()
+                .default(false),
+        )
+        .to_owned()
+}
+
+fn drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(stmt_tags()).await?;
+        manager.create_table(stmt_account_tags()).await?;
+        manager.alter_table(stmt_accounts_add_favorite()).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::accounts::Entity)
+                    .drop_column(entity::accounts::Column::Favorite)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(drop_stmt(entity::account_tags::Entity))
+            .await?;
+        manager.drop_table(drop_stmt(entity::tags::Entity)).await?;
+
+        Ok(())
+    }
+}
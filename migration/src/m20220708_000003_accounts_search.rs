@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220708_000003_accounts_search"
+    }
+}
+
+const ACCOUNTS_USER_ID_NAME_INDEX: &str = "idx-accounts-user_id-name";
+
+fn stmt_accounts_add_url() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::accounts::Entity)
+        .add_column(ColumnDef::new(entity::accounts::Column::Url).string_len(255))
+        .to_owned()
+}
+
+fn stmt_accounts_user_id_name_index() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .name(ACCOUNTS_USER_ID_NAME_INDEX)
+        .table(entity::accounts::Entity)
+        .col(entity::accounts::Column::UserId)
+        .col(entity::accounts::Column::Name)
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.alter_table(stmt_accounts_add_url()).await?;
+        manager
+            .create_index(stmt_accounts_user_id_name_index())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(ACCOUNTS_USER_ID_NAME_INDEX)
+                    .table(entity::accounts::Entity)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::accounts::Entity)
+                    .drop_column(entity::accounts::Column::Url)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220712_000004_accounts_level"
+    }
+}
+
+fn stmt_accounts_add_level() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::accounts::Entity)
+        .add_column(
+            ColumnDef::new(entity::accounts::Column::Level)
+                .small_integer()
+                .not_null()
+                .default(1),
+        )
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.alter_table(stmt_accounts_add_level()).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::accounts::Entity)
+                    .drop_column(entity::accounts::Column::Level)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220715_000005_soft_delete"
+    }
+}
+
+fn stmt_accounts_add_deleted_at() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::accounts::Entity)
+        .add_column(ColumnDef::new(entity::accounts::Column::DeletedAt).date_time())
+        .to_owned()
+}
+
+fn stmt_account_groups_add_deleted_at() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::account_groups::Entity)
+        .add_column(ColumnDef::new(entity::account_groups::Column::DeletedAt).date_time())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.alter_table(stmt_accounts_add_deleted_at()).await?;
+        manager
+            .alter_table(stmt_account_groups_add_deleted_at())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::account_groups::Entity)
+                    .drop_column(entity::account_groups::Column::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::accounts::Entity)
+                    .drop_column(entity::accounts::Column::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use sea_orm_migration::{prelude::*, sea_orm::EntityTrait};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220720_000006_account_shares"
+    }
+}
+
+fn stmt_users_add_key_pair() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::users::Entity)
+        .add_column(ColumnDef::new(entity::users::Column::PublicKey).text())
+        .add_column(ColumnDef::new(entity::users::Column::PrivateKey).binary())
+        .to_owned()
+}
+
+fn stmt_accounts_add_item_key() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::accounts::Entity)
+        .add_column(ColumnDef::new(entity::accounts::Column::ItemKey).binary())
+        .to_owned()
+}
+
+fn stmt_account_shares() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::account_shares::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::account_shares::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(entity::account_shares::Column::AccountId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::account_shares::Column::UserId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::account_shares::Column::ItemKey)
+                .binary()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::account_shares::Column::Permission)
+                .string_len(10)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::account_shares::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::account_shares::Entity,
+                    entity::account_shares::Column::AccountId,
+                )
+                .to(entity::accounts::Entity, entity::accounts::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::account_shares::Entity,
+                    entity::account_shares::Column::UserId,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .index(
+            Index::create()
+                .col(entity::account_shares::Column::AccountId)
+                .col(entity::account_shares::Column::UserId)
+                .unique(),
+        )
+        .to_owned()
+}
+
+fn drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.alter_table(stmt_users_add_key_pair()).await?;
+        manager.alter_table(stmt_accounts_add_item_key()).await?;
+        manager.create_table(stmt_account_shares()).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_stmt(entity::account_shares::Entity))
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::accounts::Entity)
+                    .drop_column(entity::accounts::Column::ItemKey)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::users::Entity)
+                    .drop_column(entity::users::Column::PublicKey)
+                    .drop_column(entity::users::Column::PrivateKey)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
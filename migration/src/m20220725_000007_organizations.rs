@@ -0,0 +1,291 @@
+use sea_orm_migration::{prelude::*, sea_orm::EntityTrait};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220725_000007_organizations"
+    }
+}
+
+fn stmt_organizations() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::organizations::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::organizations::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(entity::organizations::Column::Name)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organizations::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+fn stmt_organization_members() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::organization_members::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::organization_members::Column::OrganizationId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_members::Column::UserId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_members::Column::Role)
+                .string_len(10)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_members::Column::OrgKey)
+                .binary()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_members::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .primary_key(
+            Index::create()
+                .col(entity::organization_members::Column::OrganizationId)
+                .col(entity::organization_members::Column::UserId),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::organization_members::Entity,
+                    entity::organization_members::Column::OrganizationId,
+                )
+                .to(
+                    entity::organizations::Entity,
+                    entity::organizations::Column::Id,
+                )
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::organization_members::Entity,
+                    entity::organization_members::Column::UserId,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn stmt_organization_invitations() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::organization_invitations::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::OrganizationId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::Email)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::Role)
+                .string_len(10)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::Token)
+                .string_len(64)
+                .not_null()
+                .unique_key(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::OrgKey)
+                .binary()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::InvitedBy)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::organization_invitations::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::organization_invitations::Entity,
+                    entity::organization_invitations::Column::OrganizationId,
+                )
+                .to(
+                    entity::organizations::Entity,
+                    entity::organizations::Column::Id,
+                )
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::organization_invitations::Entity,
+                    entity::organization_invitations::Column::InvitedBy,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn stmt_account_groups_add_organization_id() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::account_groups::Entity)
+        .add_column(ColumnDef::new(entity::account_groups::Column::OrganizationId).integer())
+        .to_owned()
+}
+
+fn stmt_account_groups_organization_fk() -> ForeignKeyCreateStatement {
+    ForeignKey::create()
+        .name("fk-account_groups-organization_id")
+        .from(
+            entity::account_groups::Entity,
+            entity::account_groups::Column::OrganizationId,
+        )
+        .to(
+            entity::organizations::Entity,
+            entity::organizations::Column::Id,
+        )
+        .on_delete(ForeignKeyAction::NoAction)
+        .on_update(ForeignKeyAction::NoAction)
+        .to_owned()
+}
+
+fn stmt_collection_members() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::collection_members::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::collection_members::Column::AccountGroupsId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::collection_members::Column::UserId)
+                .integer()
+                .not_null(),
+        )
+        .primary_key(
+            Index::create()
+                .col(entity::collection_members::Column::AccountGroupsId)
+                .col(entity::collection_members::Column::UserId),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::collection_members::Entity,
+                    entity::collection_members::Column::AccountGroupsId,
+                )
+                .to(
+                    entity::account_groups::Entity,
+                    entity::account_groups::Column::Id,
+                )
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::collection_members::Entity,
+                    entity::collection_members::Column::UserId,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(stmt_organizations()).await?;
+        manager.create_table(stmt_organization_members()).await?;
+        manager
+            .create_table(stmt_organization_invitations())
+            .await?;
+        manager
+            .alter_table(stmt_account_groups_add_organization_id())
+            .await?;
+        manager
+            .create_foreign_key(stmt_account_groups_organization_fk())
+            .await?;
+        manager.create_table(stmt_collection_members()).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_stmt(entity::collection_members::Entity))
+            .await?;
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk-account_groups-organization_id")
+                    .table(entity::account_groups::Entity)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::account_groups::Entity)
+                    .drop_column(entity::account_groups::Column::OrganizationId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(drop_stmt(entity::organization_invitations::Entity))
+            .await?;
+        manager
+            .drop_table(drop_stmt(entity::organization_members::Entity))
+            .await?;
+        manager
+            .drop_table(drop_stmt(entity::organizations::Entity))
+            .await?;
+
+        Ok(())
+    }
+}
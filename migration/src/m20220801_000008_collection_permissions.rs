@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220801_000008_collection_permissions"
+    }
+}
+
+fn stmt_collection_members_add_permission() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::collection_members::Entity)
+        .add_column(
+            ColumnDef::new(entity::collection_members::Column::Permission)
+                .string_len(25)
+                .not_null()
+                .default("view"),
+        )
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(stmt_collection_members_add_permission())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::collection_members::Entity)
+                    .drop_column(entity::collection_members::Column::Permission)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::{prelude::*, sea_orm::EntityTrait};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220808_000009_emergency_access"
+    }
+}
+
+fn stmt_emergency_access() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::emergency_access::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::emergency_access::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(entity::emergency_access::Column::GrantorId)
+                .integer()
+                .not_null(),
+        )
+        .col(ColumnDef::new(entity::emergency_access::Column::GranteeId).integer())
+        .col(
+            ColumnDef::new(entity::emergency_access::Column::Email)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::emergency_access::Column::AccessType)
+                .string_len(10)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::emergency_access::Column::Status)
+                .string_len(20)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::emergency_access::Column::WaitDays)
+                .small_integer()
+                .not_null(),
+        )
+        .col(ColumnDef::new(entity::emergency_access::Column::MasterKey).binary())
+        .col(ColumnDef::new(entity::emergency_access::Column::RecoveryRequestedAt).date_time())
+        .col(
+            ColumnDef::new(entity::emergency_access::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .name("fk-emergency_access-grantor_id")
+                .from(
+                    entity::emergency_access::Entity,
+                    entity::emergency_access::Column::GrantorId,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .name("fk-emergency_access-grantee_id")
+                .from(
+                    entity::emergency_access::Entity,
+                    entity::emergency_access::Column::GranteeId,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .index(
+            Index::create()
+                .col(entity::emergency_access::Column::GrantorId)
+                .col(entity::emergency_access::Column::Email)
+                .unique(),
+        )
+        .to_owned()
+}
+
+fn drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(stmt_emergency_access()).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_stmt(entity::emergency_access::Entity))
+            .await?;
+
+        Ok(())
+    }
+}
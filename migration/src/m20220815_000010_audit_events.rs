@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, sea_orm::EntityTrait};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220815_000010_audit_events"
+    }
+}
+
+const AUDIT_EVENTS_USER_ID_CREATED_DATE_INDEX: &str = "idx-audit_events-user_id-created_date";
+
+fn stmt_audit_events() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::audit_events::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::audit_events::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(entity::audit_events::Column::UserId).integer())
+        .col(
+            ColumnDef::new(entity::audit_events::Column::EventType)
+                .string_len(40)
+                .not_null(),
+        )
+        .col(ColumnDef::new(entity::audit_events::Column::Target).string())
+        .col(ColumnDef::new(entity::audit_events::Column::Device).string())
+        .col(ColumnDef::new(entity::audit_events::Column::IpAddress).string_len(45))
+        .col(
+            ColumnDef::new(entity::audit_events::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::audit_events::Entity,
+                    entity::audit_events::Column::UserId,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn stmt_audit_events_user_id_created_date_index() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .name(AUDIT_EVENTS_USER_ID_CREATED_DATE_INDEX)
+        .table(entity::audit_events::Entity)
+        .col(entity::audit_events::Column::UserId)
+        .col(entity::audit_events::Column::CreatedDate)
+        .to_owned()
+}
+
+fn drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(stmt_audit_events()).await?;
+        manager
+            .create_index(stmt_audit_events_user_id_created_date_index())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_stmt(entity::audit_events::Entity))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use sea_orm_migration::{prelude::*, sea_orm::EntityTrait};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220822_000011_webhooks"
+    }
+}
+
+const WEBHOOK_DELIVERIES_STATUS_NEXT_ATTEMPT_AT_INDEX: &str =
+    "idx-webhook_deliveries-status-next_attempt_at";
+
+fn stmt_webhooks() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::webhooks::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::webhooks::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(entity::webhooks::Column::UserId).integer())
+        .col(ColumnDef::new(entity::webhooks::Column::OrganizationId).integer())
+        .col(
+            ColumnDef::new(entity::webhooks::Column::Url)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::webhooks::Column::Secret)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::webhooks::Column::EventTypes)
+                .string()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::webhooks::Column::Enabled)
+                .boolean()
+                .not_null()
+                .default(true),
+        )
+        .col(
+            ColumnDef::new(entity::webhooks::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(entity::webhooks::Entity, entity::webhooks::Column::UserId)
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::webhooks::Entity,
+                    entity::webhooks::Column::OrganizationId,
+                )
+                .to(
+                    entity::organizations::Entity,
+                    entity::organizations::Column::Id,
+                )
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn stmt_webhook_deliveries() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::webhook_deliveries::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::WebhookId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::EventType)
+                .string_len(40)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::Payload)
+                .text()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::Status)
+                .string_len(20)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::Attempts)
+                .small_integer()
+                .not_null()
+                .default(0),
+        )
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::NextAttemptAt)
+                .date_time()
+                .not_null(),
+        )
+        .col(ColumnDef::new(entity::webhook_deliveries::Column::ResponseStatus).small_integer())
+        .col(ColumnDef::new(entity::webhook_deliveries::Column::Error).string())
+        .col(
+            ColumnDef::new(entity::webhook_deliveries::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .col(ColumnDef::new(entity::webhook_deliveries::Column::DeliveredAt).date_time())
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::webhook_deliveries::Entity,
+                    entity::webhook_deliveries::Column::WebhookId,
+                )
+                .to(entity::webhooks::Entity, entity::webhooks::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn stmt_webhook_deliveries_status_next_attempt_at_index() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .name(WEBHOOK_DELIVERIES_STATUS_NEXT_ATTEMPT_AT_INDEX)
+        .table(entity::webhook_deliveries::Entity)
+        .col(entity::webhook_deliveries::Column::Status)
+        .col(entity::webhook_deliveries::Column::NextAttemptAt)
+        .to_owned()
+}
+
+fn drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(stmt_webhooks()).await?;
+        manager.create_table(stmt_webhook_deliveries()).await?;
+        manager
+            .create_index(stmt_webhook_deliveries_status_next_attempt_at_index())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_stmt(entity::webhook_deliveries::Entity))
+            .await?;
+        manager
+            .drop_table(drop_stmt(entity::webhooks::Entity))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use sea_orm_migration::{prelude::*, sea_orm::EntityTrait};
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220829_000012_vault_changes"
+    }
+}
+
+const VAULT_CHANGES_USER_ID_REVISION_INDEX: &str = "idx-vault_changes-user_id-revision";
+
+fn stmt_users_add_revision() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::users::Entity)
+        .add_column(
+            ColumnDef::new(entity::users::Column::Revision)
+                .big_integer()
+                .not_null()
+                .default(0),
+        )
+        .to_owned()
+}
+
+fn stmt_vault_changes() -> TableCreateStatement {
+    sea_query::Table::create()
+        .table(entity::vault_changes::Entity)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(entity::vault_changes::Column::Id)
+                .integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(entity::vault_changes::Column::UserId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::vault_changes::Column::Revision)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::vault_changes::Column::ItemType)
+                .string_len(10)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::vault_changes::Column::ItemId)
+                .integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::vault_changes::Column::Action)
+                .string_len(10)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(entity::vault_changes::Column::CreatedDate)
+                .date_time()
+                .not_null(),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(
+                    entity::vault_changes::Entity,
+                    entity::vault_changes::Column::UserId,
+                )
+                .to(entity::users::Entity, entity::users::Column::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+        )
+        .to_owned()
+}
+
+fn stmt_vault_changes_user_id_revision_index() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .name(VAULT_CHANGES_USER_ID_REVISION_INDEX)
+        .table(entity::vault_changes::Entity)
+        .col(entity::vault_changes::Column::UserId)
+        .col(entity::vault_changes::Column::Revision)
+        .to_owned()
+}
+
+fn drop_stmt<E: EntityTrait>(e: E) -> TableDropStatement {
+    Table::drop().table(e).if_exists().to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.alter_table(stmt_users_add_revision()).await?;
+        manager.create_table(stmt_vault_changes()).await?;
+        manager
+            .create_index(stmt_vault_changes_user_id_revision_index())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(drop_stmt(entity::vault_changes::Entity))
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::users::Entity)
+                    .drop_column(entity::users::Column::Revision)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20220919_000015_rotation_intervals"
+    }
+}
+
+fn stmt_accounts_add_rotation_days() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::accounts::Entity)
+        .add_column(ColumnDef::new(entity::accounts::Column::RotationDays).integer())
+        .to_owned()
+}
+
+fn stmt_accounts_add_rotation_reminded_at() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::accounts::Entity)
+        .add_column(ColumnDef::new(entity::accounts::Column::RotationRemindedAt).date_time())
+        .to_owned()
+}
+
+fn stmt_account_groups_add_rotation_days() -> TableAlterStatement {
+    sea_query::Table::alter()
+        .table(entity::account_groups::Entity)
+        .add_column(ColumnDef::new(entity::account_groups::Column::RotationDays).integer())
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(stmt_accounts_add_rotation_days())
+            .await?;
+        manager
+            .alter_table(stmt_accounts_add_rotation_reminded_at())
+            .await?;
+        manager
+            .alter_table(stmt_account_groups_add_rotation_days())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::account_groups::Entity)
+                    .drop_column(entity::account_groups::Column::RotationDays)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::accounts::Entity)
+                    .drop_column(entity::accounts::Column::RotationRemindedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(entity::accounts::Entity)
+                    .drop_column(entity::accounts::Column::RotationDays)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,16 +1,39 @@
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[derive(Serialize, Deserialize, Validate)]
 pub struct AccountGroupRegister {
     #[validate(length(min = 1))]
     pub name: String,
+    /// Days after which the passwords of the group are due for rotation,
+    /// unless their account sets its own interval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 3650))]
+    pub rotation_days: Option<i32>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Validate)]
+pub struct AccountGroupUpdate {
+    #[validate(length(min = 1))]
+    pub name: String,
+    /// Left out keeps the current interval, 0 removes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0, max = 3650))]
+    pub rotation_days: Option<i32>,
+    /// Revision the change is based on, see [`AccountUpdate::revision`].
+    pub revision: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct AccountGroupView {
     pub id: i32,
     pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<i32>,
+    #[serde(default)]
+    pub revision: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation_days: Option<i32>,
 }
 
 #[derive(Serialize, Deserialize, Validate)]
@@ -19,18 +42,145 @@ pub struct AccountRegister {
     pub name: String,
     #[validate(range(min = 1))]
     pub group_id: i32,
+    #[validate(range(min = 1, max = 5))]
     pub level: Option<i16>,
+    #[validate(url, length(max = 255))]
+    pub url: Option<String>,
     #[validate(length(min = 1))]
     pub username: String,
     #[validate(length(min = 1))]
     pub password: String,
+    #[serde(default)]
+    pub favorite: bool,
+    /// Whether the account is protected by a second factor on the website.
+    #[serde(default)]
+    pub two_factor: bool,
+    /// Days after which the password is due for rotation, overrides the
+    /// group's interval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 3650))]
+    pub rotation_days: Option<i32>,
+    /// How new passwords of the account are generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub generator_profile: Option<GeneratorProfile>,
+    #[serde(default)]
+    #[validate(custom = "validate_tags")]
+    pub tags: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// Rules for generating the passwords of an account, kept so every rotation
+/// meets the same site requirements. Stored as is, the clients do the
+/// generation.
+#[derive(Serialize, Deserialize, Validate, Clone, Debug)]
+#[serde(default)]
+pub struct GeneratorProfile {
+    #[validate(range(min = 1, max = 1024))]
+    pub size: usize,
+    pub upper: bool,
+    pub lower: bool,
+    pub digits: bool,
+    pub symbols: bool,
+    /// Symbols to pick from instead of the client's default set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_set: Option<String>,
+    /// Characters never to use.
+    pub exclude: String,
+    pub exclude_ambiguous: bool,
+    pub min_upper: usize,
+    pub min_lower: usize,
+    pub min_digits: usize,
+    pub min_symbols: usize,
+    /// Template used instead of `size`, see the CLI's `--pattern`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    pub passphrase: bool,
+    #[validate(range(min = 1, max = 64))]
+    pub words: usize,
+    pub separator: String,
+    pub capitalize: bool,
+    #[validate(range(max = 64))]
+    pub passphrase_digits: usize,
+}
+
+impl Default for GeneratorProfile {
+    fn default() -> Self {
+        GeneratorProfile {
+            size: 16,
+            upper: true,
+            lower: true,
+            digits: true,
+            symbols: false,
+            symbol_set: None,
+            exclude: String::new(),
+            exclude_ambiguous: false,
+            min_upper: 0,
+            min_lower: 0,
+            min_digits: 0,
+            min_symbols: 0,
+            pattern: None,
+            passphrase: false,
+            words: 6,
+            separator: String::from("-"),
+            capitalize: false,
+            passphrase_digits: 0,
+        }
+    }
+}
+
+fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    if tags
+        .iter()
+        .any(|tag| tag.trim().is_empty() || tag.chars().count() > 50)
+    {
+        let mut error = ValidationError::new("tags");
+        error.message = Some("Tags must have between 1 and 50 characters".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct AccountView {
     pub id: i32,
     pub name: String,
     pub group_id: i32,
+    pub level: i16,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub two_factor: bool,
+    /// Interval set on the account itself, the group's one applies otherwise.
+    #[serde(default)]
+    pub rotation_days: Option<i32>,
+    #[serde(default)]
+    pub generator_profile: Option<GeneratorProfile>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub revision: i32,
+}
+
+/// Account whose password is due for rotation, `GET /api/accounts/due`.
+#[derive(Serialize, Deserialize)]
+pub struct DueAccountView {
+    pub id: i32,
+    pub name: String,
+    pub group_id: i32,
+    /// Interval in effect, the account's or its group's.
+    pub rotation_days: i32,
+    /// RFC 3339 date of the current password.
+    pub changed_date: String,
+    pub due_date: String,
+    /// Negative while the due date is still ahead.
+    pub overdue_days: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TagView {
+    pub id: i32,
+    pub name: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -39,4 +189,106 @@ pub struct AccountWithPasswordView {
     pub name: String,
     pub username: Option<String>,
     pub password: Option<String>,
+    #[serde(default)]
+    pub revision: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TrashedAccountView {
+    pub id: i32,
+    pub name: String,
+    pub group_id: i32,
+    pub deleted_at: String,
+    pub purge_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TrashedGroupView {
+    pub id: i32,
+    pub name: String,
+    pub deleted_at: String,
+    pub purge_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TrashView {
+    pub accounts: Vec<TrashedAccountView>,
+    pub groups: Vec<TrashedGroupView>,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct AccountUpdate {
+    #[validate(length(min = 1))]
+    pub name: Option<String>,
+    #[validate(url, length(max = 255))]
+    pub url: Option<String>,
+    #[validate(length(min = 1))]
+    pub username: Option<String>,
+    #[validate(length(min = 1))]
+    pub password: Option<String>,
+    pub two_factor: Option<bool>,
+    /// Left out keeps the current interval, 0 removes it.
+    #[validate(range(min = 0, max = 3650))]
+    pub rotation_days: Option<i32>,
+    /// Left out keeps the current profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub generator_profile: Option<GeneratorProfile>,
+    /// Revision the change is based on. When set, or sent as `If-Match`,
+    /// the update is refused with a conflict if the account changed since.
+    pub revision: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SharePermission {
+    ReadOnly,
+    Edit,
+}
+
+impl SharePermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SharePermission::ReadOnly => "read_only",
+            SharePermission::Edit => "edit",
+        }
+    }
+}
+
+impl std::str::FromStr for SharePermission {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read_only" => Ok(SharePermission::ReadOnly),
+            "edit" => Ok(SharePermission::Edit),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct AccountShareRegister {
+    #[validate(email)]
+    pub email: String,
+    pub permission: SharePermission,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AccountShareView {
+    pub id: i32,
+    pub account_id: i32,
+    pub email: String,
+    pub name: String,
+    pub permission: SharePermission,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SharedAccountView {
+    pub id: i32,
+    pub name: String,
+    pub level: i16,
+    pub url: Option<String>,
+    pub owner: String,
+    pub permission: SharePermission,
 }
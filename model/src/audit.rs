@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventType {
+    Registered,
+    Login,
+    LoginFailed,
+    LoginNewDevice,
+    TokenRefreshed,
+    Logout,
+    Elevated,
+    ElevationFailed,
+    PasswordRecoveryRequested,
+    PasswordRecovered,
+    PasswordChanged,
+    PasswordRevealed,
+    AccountUpdated,
+    AccountDeleted,
+    AccountShared,
+    ShareRevoked,
+    VaultImported,
+    VaultExported,
+}
+
+impl AuditEventType {
+    /// Every event type, for the clients listing them.
+    pub const ALL: [AuditEventType; 18] = [
+        AuditEventType::Registered,
+        AuditEventType::Login,
+        AuditEventType::LoginFailed,
+        AuditEventType::LoginNewDevice,
+        AuditEventType::TokenRefreshed,
+        AuditEventType::Logout,
+        AuditEventType::Elevated,
+        AuditEventType::ElevationFailed,
+        AuditEventType::PasswordRecoveryRequested,
+        AuditEventType::PasswordRecovered,
+        AuditEventType::PasswordChanged,
+        AuditEventType::PasswordRevealed,
+        AuditEventType::AccountUpdated,
+        AuditEventType::AccountDeleted,
+        AuditEventType::AccountShared,
+        AuditEventType::ShareRevoked,
+        AuditEventType::VaultImported,
+        AuditEventType::VaultExported,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEventType::Registered => "registered",
+            AuditEventType::Login => "login",
+            AuditEventType::LoginFailed => "login_failed",
+            AuditEventType::LoginNewDevice => "login_new_device",
+            AuditEventType::TokenRefreshed => "token_refreshed",
+            AuditEventType::Logout => "logout",
+            AuditEventType::Elevated => "elevated",
+            AuditEventType::ElevationFailed => "elevation_failed",
+            AuditEventType::PasswordRecoveryRequested => "password_recovery_requested",
+            AuditEventType::PasswordRecovered => "password_recovered",
+            AuditEventType::PasswordChanged => "password_changed",
+            AuditEventType::PasswordRevealed => "password_revealed",
+            AuditEventType::AccountUpdated => "account_updated",
+            AuditEventType::AccountDeleted => "account_deleted",
+            AuditEventType::AccountShared => "account_shared",
+            AuditEventType::ShareRevoked => "share_revoked",
+            AuditEventType::VaultImported => "vault_imported",
+            AuditEventType::VaultExported => "vault_exported",
+        }
+    }
+}
+
+impl std::str::FromStr for AuditEventType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AuditEventType::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AuditEventView {
+    pub id: i32,
+    pub event_type: AuditEventType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    pub created_date: String,
+}
@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+pub const ELEVATED_TOKEN_HEADER: &str = "x-elevated-token";
+/// Set on a response when the password just saved appears in a data breach
+/// and the server only warns about it, holds the number of occurrences.
+pub const PASSWORD_BREACHED_HEADER: &str = "x-password-breached";
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 // #[serde(rename_all = "snake_case")]
 pub enum RefreshTokenType {
@@ -28,7 +33,8 @@ pub struct UserRegister {
     #[validate(length(min = 1, message = "Email is invalid"))]
     #[validate(email(message = "Email is invalid"))]
     pub email: String,
-    #[validate(length(min = 8, message = "Password is invalid"))]
+    /// Checked against the server's password policy.
+    #[validate(length(min = 1, message = "Password is invalid"))]
     pub password: String,
 }
 
@@ -40,7 +46,7 @@ pub struct AccessToken {
     pub refresh_token: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct UserView {
     pub name: String,
     pub email: String,
@@ -59,6 +65,16 @@ pub struct PasswordRecoveryFinish {
     #[validate(length(min = 1, message = "Token is invalid"))]
     pub token: String,
 
+    /// Checked against the server's password policy.
+    #[validate(length(min = 1, message = "Password is invalid"))]
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct PasswordChange {
+    #[validate(length(min = 1, message = "Current password is invalid"))]
+    pub current_password: String,
+    /// Checked against the server's password policy.
     #[validate(length(min = 1, message = "Password is invalid"))]
     pub password: String,
 }
@@ -67,3 +83,15 @@ pub struct PasswordRecoveryFinish {
 pub struct RefreshToken {
     pub refresh_token: String,
 }
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct ElevationRequest {
+    #[validate(length(min = 1, message = "Password is invalid"))]
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ElevatedToken {
+    pub elevated_token: String,
+    pub expires_in: i64,
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmergencyAccessType {
+    View,
+    Takeover,
+}
+
+impl EmergencyAccessType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmergencyAccessType::View => "view",
+            EmergencyAccessType::Takeover => "takeover",
+        }
+    }
+}
+
+impl std::str::FromStr for EmergencyAccessType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "view" => Ok(EmergencyAccessType::View),
+            "takeover" => Ok(EmergencyAccessType::Takeover),
+            _ => Err(()),
+        }
+    }
+}
+
+/// invited → accepted → recovery_requested → approved, a rejected request
+/// goes back to accepted.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmergencyAccessStatus {
+    Invited,
+    Accepted,
+    RecoveryRequested,
+    Approved,
+}
+
+impl EmergencyAccessStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmergencyAccessStatus::Invited => "invited",
+            EmergencyAccessStatus::Accepted => "accepted",
+            EmergencyAccessStatus::RecoveryRequested => "recovery_requested",
+            EmergencyAccessStatus::Approved => "approved",
+        }
+    }
+}
+
+impl std::str::FromStr for EmergencyAccessStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "invited" => Ok(EmergencyAccessStatus::Invited),
+            "accepted" => Ok(EmergencyAccessStatus::Accepted),
+            "recovery_requested" => Ok(EmergencyAccessStatus::RecoveryRequested),
+            "approved" => Ok(EmergencyAccessStatus::Approved),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct EmergencyAccessRegister {
+    #[validate(email)]
+    pub email: String,
+    pub access_type: EmergencyAccessType,
+    #[validate(range(min = 1, max = 90))]
+    pub wait_days: i16,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EmergencyAccessView {
+    pub id: i32,
+    /// The trusted contact for the grantor, the grantor for the contact.
+    pub email: String,
+    pub access_type: EmergencyAccessType,
+    pub status: EmergencyAccessStatus,
+    pub wait_days: i16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_requested_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct EmergencyAccessTakeover {
+    /// Checked against the server's password policy.
+    #[validate(length(min = 1, message = "Password is invalid"))]
+    pub password: String,
+}
@@ -6,3 +6,11 @@ use serde::Serialize;
 pub struct ErrorResponse<T> {
     pub error: HashMap<String, T>,
 }
+
+/// Body of a `409 Conflict` answering a stale update, `current` is the
+/// version held by the server.
+#[derive(Serialize)]
+pub struct ConflictResponse<T> {
+    pub error: HashMap<String, String>,
+    pub current: T,
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+pub const EXPORT_VERSION: u32 = 1;
+pub const EXPORT_KDF: &str = "argon2id";
+pub const EXPORT_CIPHER: &str = "aes-256-gcm";
+
+/// Personal vault in clear, answer of `GET /api/export` and content of the
+/// `openpasswd` import format.
+#[derive(Serialize, Deserialize)]
+pub struct VaultExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub groups: Vec<ExportedGroup>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedGroup {
+    pub name: String,
+    pub accounts: Vec<ExportedAccount>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedAccount {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub level: i16,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub two_factor: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Every version of the credentials, oldest first, the last one being
+    /// the current.
+    pub passwords: Vec<ExportedPassword>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportedPassword {
+    pub username: String,
+    pub password: String,
+    /// RFC 3339 timestamp.
+    pub created_date: String,
+}
+
+/// [`VaultExport`] encrypted with a key derived from a passphrase. Binary
+/// values are base64 encoded.
+#[derive(Serialize, Deserialize)]
+pub struct EncryptedVaultExport {
+    pub version: u32,
+    pub kdf: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub salt: String,
+    pub cipher: String,
+    pub nonce: String,
+    pub data: String,
+}
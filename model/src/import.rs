@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Unencrypted Bitwarden JSON export.
+    #[serde(rename = "bitwarden")]
+    Bitwarden,
+    /// KeePass 2 or KeePassXC CSV export.
+    #[serde(rename = "keepass_csv")]
+    KeepassCsv,
+    /// KeePass 2 XML export.
+    #[serde(rename = "keepass_xml")]
+    KeepassXml,
+    /// 1Password CSV export.
+    #[serde(rename = "1password")]
+    OnePassword,
+    /// LastPass CSV export.
+    #[serde(rename = "lastpass")]
+    Lastpass,
+    /// OpenPasswd export, see `model::export::VaultExport`.
+    #[serde(rename = "openpasswd")]
+    OpenPasswd,
+}
+
+impl ImportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::Bitwarden => "bitwarden",
+            ImportFormat::KeepassCsv => "keepass_csv",
+            ImportFormat::KeepassXml => "keepass_xml",
+            ImportFormat::OnePassword => "1password",
+            ImportFormat::Lastpass => "lastpass",
+            ImportFormat::OpenPasswd => "openpasswd",
+        }
+    }
+}
+
+impl std::str::FromStr for ImportFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bitwarden" => Ok(ImportFormat::Bitwarden),
+            "keepass_csv" => Ok(ImportFormat::KeepassCsv),
+            "keepass_xml" => Ok(ImportFormat::KeepassXml),
+            "1password" => Ok(ImportFormat::OnePassword),
+            "lastpass" => Ok(ImportFormat::Lastpass),
+            "openpasswd" => Ok(ImportFormat::OpenPasswd),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct ImportRequest {
+    pub format: ImportFormat,
+    /// Content of the export file.
+    #[validate(length(min = 1))]
+    pub data: String,
+    /// Report what would be imported without changing the vault.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ImportIssue {
+    pub item: String,
+    pub detail: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub imported: u32,
+    /// Groups created for the folders of the export, existing groups with the
+    /// same name are reused.
+    pub groups_created: Vec<String>,
+    /// Items already in the vault, same group, name and username, which are
+    /// left out.
+    pub duplicates: Vec<ImportIssue>,
+    /// Data of imported items that has no place in an account, like notes or
+    /// TOTP secrets, and is dropped.
+    pub unsupported: Vec<ImportIssue>,
+    /// Items that are not imported at all, like credit cards.
+    pub skipped: Vec<ImportIssue>,
+}
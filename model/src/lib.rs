@@ -1,11 +1,29 @@
 use serde::{Deserialize, Serialize};
 
 pub mod accounts;
+pub mod audit;
 pub mod auth;
+pub mod emergency_access;
 pub mod error;
+pub mod export;
+pub mod import;
+pub mod notifications;
+pub mod organizations;
+pub mod reports;
+pub mod sync;
+pub mod webhooks;
 
 #[derive(Serialize, Deserialize)]
 pub struct List<T> {
     pub items: Vec<T>,
     pub total: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Pagination {
+    pub page: u32,
+    pub limit: u32,
+    pub pages: u32,
 }
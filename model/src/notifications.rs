@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    ItemCreated,
+    ItemUpdated,
+    ItemDeleted,
+    GroupCreated,
+    GroupUpdated,
+    GroupDeleted,
+    SessionRevoked,
+}
+
+impl NotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::ItemCreated => "item_created",
+            NotificationEvent::ItemUpdated => "item_updated",
+            NotificationEvent::ItemDeleted => "item_deleted",
+            NotificationEvent::GroupCreated => "group_created",
+            NotificationEvent::GroupUpdated => "group_updated",
+            NotificationEvent::GroupDeleted => "group_deleted",
+            NotificationEvent::SessionRevoked => "session_revoked",
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationEvent {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "item_created" => Ok(NotificationEvent::ItemCreated),
+            "item_updated" => Ok(NotificationEvent::ItemUpdated),
+            "item_deleted" => Ok(NotificationEvent::ItemDeleted),
+            "group_created" => Ok(NotificationEvent::GroupCreated),
+            "group_updated" => Ok(NotificationEvent::GroupUpdated),
+            "group_deleted" => Ok(NotificationEvent::GroupDeleted),
+            "session_revoked" => Ok(NotificationEvent::SessionRevoked),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Sent on `/api/notifications` as a server-sent event named after `event`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    /// Account id for item events, group id for group events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    /// Token id of the revoked session, the stream opened with it is closed
+    /// right after this event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+}
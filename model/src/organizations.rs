@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizationRole {
+    Owner,
+    Admin,
+    Manager,
+    Member,
+    ReadOnly,
+}
+
+impl OrganizationRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrganizationRole::Owner => "owner",
+            OrganizationRole::Admin => "admin",
+            OrganizationRole::Manager => "manager",
+            OrganizationRole::Member => "member",
+            OrganizationRole::ReadOnly => "read_only",
+        }
+    }
+}
+
+impl std::str::FromStr for OrganizationRole {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "owner" => Ok(OrganizationRole::Owner),
+            "admin" => Ok(OrganizationRole::Admin),
+            "manager" => Ok(OrganizationRole::Manager),
+            "member" => Ok(OrganizationRole::Member),
+            "read_only" => Ok(OrganizationRole::ReadOnly),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Permission over a collection, each level includes the ones before it.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionPermission {
+    ViewWithoutPassword,
+    #[default]
+    View,
+    Edit,
+    Manage,
+}
+
+impl CollectionPermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CollectionPermission::ViewWithoutPassword => "view_without_password",
+            CollectionPermission::View => "view",
+            CollectionPermission::Edit => "edit",
+            CollectionPermission::Manage => "manage",
+        }
+    }
+}
+
+impl std::str::FromStr for CollectionPermission {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "view_without_password" => Ok(CollectionPermission::ViewWithoutPassword),
+            "view" => Ok(CollectionPermission::View),
+            "edit" => Ok(CollectionPermission::Edit),
+            "manage" => Ok(CollectionPermission::Manage),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct OrganizationRegister {
+    #[validate(length(min = 1))]
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OrganizationView {
+    pub id: i32,
+    pub name: String,
+    pub role: OrganizationRole,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OrganizationMemberView {
+    pub user_id: i32,
+    pub name: String,
+    pub email: String,
+    pub role: OrganizationRole,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct OrganizationMemberUpdate {
+    pub role: OrganizationRole,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct OrganizationInvite {
+    #[validate(email)]
+    pub email: String,
+    pub role: Option<OrganizationRole>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OrganizationInvitationView {
+    pub id: i32,
+    pub organization_id: i32,
+    pub email: String,
+    pub role: OrganizationRole,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct OrganizationInvitationAccept {
+    #[validate(length(min = 1))]
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct CollectionMemberRegister {
+    #[validate(range(min = 1))]
+    pub user_id: i32,
+    #[serde(default)]
+    pub permission: CollectionPermission,
+}
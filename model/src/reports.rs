@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// Account flagged by a report.
+#[derive(Serialize, Deserialize)]
+pub struct ReportAccountView {
+    pub id: i32,
+    pub name: String,
+    pub group_id: i32,
+}
+
+/// Accounts sharing the same current password.
+#[derive(Serialize, Deserialize)]
+pub struct ReusedPasswordView {
+    pub accounts: Vec<ReportAccountView>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WeakPasswordView {
+    pub account: ReportAccountView,
+    /// From 0 to 4, see `HealthReport::weak`.
+    pub score: u8,
+    pub entropy_bits: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OldPasswordView {
+    pub account: ReportAccountView,
+    /// RFC 3339 date of the current password.
+    pub changed_date: String,
+    pub age_days: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BreachedPasswordView {
+    pub account: ReportAccountView,
+    /// Times the password appears in the breach dataset.
+    pub count: u64,
+}
+
+/// Health of the accounts whose passwords the user can read, `GET
+/// /api/reports/health`.
+#[derive(Serialize, Deserialize)]
+pub struct HealthReport {
+    pub generated_at: String,
+    pub accounts_checked: u32,
+    pub max_age_days: i64,
+    pub reused: Vec<ReusedPasswordView>,
+    /// Passwords scoring below 3.
+    pub weak: Vec<WeakPasswordView>,
+    /// Passwords unchanged for more than `max_age_days`.
+    pub old: Vec<OldPasswordView>,
+    pub no_two_factor: Vec<ReportAccountView>,
+    /// False when the server has no breach dataset, `breached` is then empty.
+    #[serde(default)]
+    pub breaches_checked: bool,
+    #[serde(default)]
+    pub breached: Vec<BreachedPasswordView>,
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::accounts::{AccountGroupView, AccountView};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncItemType {
+    Account,
+    Group,
+}
+
+impl SyncItemType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncItemType::Account => "account",
+            SyncItemType::Group => "group",
+        }
+    }
+}
+
+impl std::str::FromStr for SyncItemType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "account" => Ok(SyncItemType::Account),
+            "group" => Ok(SyncItemType::Group),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl SyncAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncAction::Created => "created",
+            SyncAction::Updated => "updated",
+            SyncAction::Deleted => "deleted",
+        }
+    }
+}
+
+impl std::str::FromStr for SyncAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "created" => Ok(SyncAction::Created),
+            "updated" => Ok(SyncAction::Updated),
+            "deleted" => Ok(SyncAction::Deleted),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SyncChanges<T> {
+    pub created: Vec<T>,
+    pub updated: Vec<T>,
+    pub deleted: Vec<i32>,
+}
+
+impl<T> Default for SyncChanges<T> {
+    fn default() -> Self {
+        SyncChanges {
+            created: Vec::new(),
+            updated: Vec::new(),
+            deleted: Vec::new(),
+        }
+    }
+}
+
+/// Answer of `GET /api/sync?since=<revision>`, pass `revision` as `since` on
+/// the next call. Without `since` every item is returned as created.
+#[derive(Serialize, Deserialize)]
+pub struct SyncView {
+    pub revision: i64,
+    pub groups: SyncChanges<AccountGroupView>,
+    pub accounts: SyncChanges<AccountView>,
+}
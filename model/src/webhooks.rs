@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::audit::AuditEventType;
+
+pub const WEBHOOK_EVENT_HEADER: &str = "x-openpasswd-event";
+pub const WEBHOOK_DELIVERY_HEADER: &str = "x-openpasswd-delivery";
+/// `sha256=` followed by the hex encoded HMAC-SHA256 of the request body,
+/// keyed with the webhook secret.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-openpasswd-signature";
+
+/// Event sent by the test endpoint.
+pub const WEBHOOK_PING_EVENT: &str = "ping";
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct WebhookRegister {
+    #[validate(url)]
+    pub url: String,
+    /// Events to deliver, every event when empty.
+    #[serde(default)]
+    pub event_types: Vec<AuditEventType>,
+    /// Organization webhooks receive the events of the members on the
+    /// organization collections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct WebhookUpdate {
+    #[validate(url)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_types: Option<Vec<AuditEventType>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WebhookView {
+    pub id: i32,
+    pub url: String,
+    pub event_types: Vec<AuditEventType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<i32>,
+    pub enabled: bool,
+    pub created_date: String,
+    /// Only returned when the webhook is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// Body of every delivery.
+#[derive(Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub event_type: String,
+    pub user_id: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    pub created_date: String,
+}
+
+/// pending → delivered, or failed once every retry is exhausted.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookDeliveryStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(WebhookDeliveryStatus::Pending),
+            "delivered" => Ok(WebhookDeliveryStatus::Delivered),
+            "failed" => Ok(WebhookDeliveryStatus::Failed),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WebhookDeliveryView {
+    pub id: i32,
+    pub event_type: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<String>,
+}
@@ -1,17 +1,79 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crate::{
-    api::OpenPasswdApi, clipboard::copy_password_to_clipboard, generator::generate_string,
+    api::{AccountsQuery, ApiError, DueAccountsQuery, OpenPasswdApi},
+    clipboard::copy_password_to_clipboard,
+    generator::GeneratorOptions,
     profile::Profile,
 };
 use clap::{Args, Subcommand};
-use model::accounts::AccountRegister;
+use model::accounts::{
+    AccountRegister, AccountShareRegister, AccountUpdate, GeneratorProfile, SharePermission,
+};
 
 #[derive(Debug, Subcommand)]
 enum AccountsCommands {
-    Get { name: String },
-    List { id: Option<i32> },
+    Get {
+        name: String,
+    },
+    List(AccountList),
     Create(Account),
+    Delete {
+        name: String,
+    },
+    /// Replace the password with one from the account's generator profile
+    Rotate {
+        name: String,
+    },
+    /// Set the generator profile used when rotating the account's password
+    Generator(AccountGenerator),
+    Share(AccountShare),
+    Unshare {
+        name: String,
+        email: String,
+    },
+    Shares {
+        name: String,
+    },
+    Shared,
+}
+
+#[derive(Debug, Args)]
+pub struct AccountShare {
+    name: String,
+    email: String,
+    #[clap(short, long)]
+    edit: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct AccountGenerator {
+    name: String,
+    #[clap(flatten)]
+    generator: GeneratorOptions,
+}
+
+#[derive(Debug, Args)]
+pub struct AccountList {
+    id: Option<i32>,
+    #[clap(short, long)]
+    tag: Option<String>,
+    #[clap(short, long)]
+    favorite: bool,
+    #[clap(short = 'q', long)]
+    search: Option<String>,
+    #[clap(long, possible_values = ["name", "-name", "created", "-created"])]
+    sort: Option<String>,
+    #[clap(long)]
+    page: Option<u32>,
+    #[clap(long)]
+    limit: Option<u32>,
+    /// List the passwords due for rotation instead
+    #[clap(long, conflicts_with_all = &["id", "tag", "favorite", "search", "sort", "page", "limit"])]
+    due: bool,
+    /// With --due, also list the passwords becoming due within this many days
+    #[clap(long, requires = "due")]
+    within_days: Option<u32>,
 }
 
 #[derive(Debug, Args)]
@@ -22,12 +84,25 @@ pub struct Account {
     group: Option<String>,
     #[clap(short, long, default_value_t = 1)]
     level: i16,
+    #[clap(long)]
+    url: Option<String>,
     #[clap(short, long)]
     username: String,
+    /// Generate the password, the options are kept as the account's generator profile
     #[clap(long)]
     generated: bool,
-    #[clap(short, long, default_value_t = 16)]
-    size: usize,
+    #[clap(flatten)]
+    generator: GeneratorOptions,
+    #[clap(short, long = "tag")]
+    tags: Vec<String>,
+    #[clap(short, long)]
+    favorite: bool,
+    /// The account is protected by a second factor
+    #[clap(long)]
+    two_factor: bool,
+    /// Days after which the password is due for rotation, the group's interval otherwise
+    #[clap(long)]
+    rotation_days: Option<i32>,
 }
 
 #[derive(Debug, Args)]
@@ -43,47 +118,118 @@ impl Accounts {
 
         match &self.command {
             AccountsCommands::Get { name } => self.get(api, name).await,
-            AccountsCommands::List { id } => self.list(api, id).await,
+            AccountsCommands::List(list) => self.list(api, list).await,
             AccountsCommands::Create(account) => self.create(api, account).await,
+            AccountsCommands::Delete { name } => self.delete(api, name).await,
+            AccountsCommands::Rotate { name } => self.rotate(api, name).await,
+            AccountsCommands::Generator(generator) => self.generator(api, generator).await,
+            AccountsCommands::Share(share) => self.share(api, share).await,
+            AccountsCommands::Unshare { name, email } => self.unshare(api, name, email).await,
+            AccountsCommands::Shares { name } => self.shares(api, name).await,
+            AccountsCommands::Shared => self.shared(api).await,
         }
     }
 
     async fn get(&self, api: OpenPasswdApi, name: &str) {
-        let list = api.list_accounts(&None).await.unwrap();
-        if let Some(account) = list.items.iter().filter(|a| a.name.as_str() == name).next() {
-            let account_with_password = api.get_account(account.id).await.unwrap();
+        let query = AccountsQuery {
+            q: Some(name.to_owned()),
+            ..Default::default()
+        };
+        let list = api.list_accounts(&query).await.unwrap();
+        let account_id = match list.items.iter().find(|a| a.name.as_str() == name) {
+            Some(account) => account.id,
+            None => {
+                let shared = api.list_shared_accounts().await.unwrap();
+                match shared.items.iter().find(|a| a.name.as_str() == name) {
+                    Some(account) => account.id,
+                    None => {
+                        println!("Account {name} not found");
+                        return;
+                    }
+                }
+            }
+        };
+
+        let account_with_password = match api.get_account(account_id, None).await {
+            Err(ApiError::ElevationRequired) => {
+                println!("{name} requires password confirmation");
+                let password = rpassword::prompt_password("Password: ").unwrap();
+                let elevated_token = api.auth_elevate(password).await.unwrap();
+                api.get_account(account_id, Some(&elevated_token.elevated_token))
+                    .await
+                    .unwrap()
+            }
+            result => result.unwrap(),
+        };
+
+        if let Some(password) = account_with_password.password {
+            copy_password_to_clipboard(password, 5);
+        }
+    }
+
+    async fn list(&self, api: OpenPasswdApi, list: &AccountList) {
+        if list.due {
+            return self.list_due(api, list.within_days).await;
+        }
+
+        let query = AccountsQuery {
+            group_id: list.id,
+            tag: list.tag.to_owned(),
+            favorite: if list.favorite { Some(true) } else { None },
+            q: list.search.to_owned(),
+            sort: list.sort.to_owned(),
+            page: list.page,
+            limit: list.limit,
+        };
+        let list = api.list_accounts(&query).await.unwrap();
+
+        for item in list.items {
+            let favorite = if item.favorite { "* " } else { "" };
+            if item.tags.is_empty() {
+                println!("- {favorite}{}", item.name);
+            } else {
+                println!("- {favorite}{} [{}]", item.name, item.tags.join(", "));
+            }
+        }
 
-            if let Some(password) = account_with_password.password {
-                copy_password_to_clipboard(password, 5);
+        if let Some(pagination) = list.pagination {
+            if pagination.pages > 1 {
+                println!(
+                    "Page {} of {} ({} accounts)",
+                    pagination.page, pagination.pages, list.total
+                );
             }
         }
     }
 
-    async fn list(&self, api: OpenPasswdApi, id: &Option<i32>) {
-        let list = api.list_accounts(id).await.unwrap();
+    async fn list_due(&self, api: OpenPasswdApi, within_days: Option<u32>) {
+        let list = api
+            .list_due_accounts(&DueAccountsQuery { days: within_days })
+            .await
+            .unwrap();
 
+        if list.items.is_empty() {
+            println!("No password due for rotation");
+        }
         for item in list.items {
-            println!("- {}", item.name);
+            let due_date = item.due_date.split('T').next().unwrap_or_default();
+            match item.overdue_days {
+                days if days > 0 => {
+                    println!("- {}: due {due_date}, {days} days overdue", item.name)
+                }
+                0 => println!("- {}: due today", item.name),
+                _ => println!("- {}: due {due_date}", item.name),
+            }
         }
     }
 
     async fn create(&self, api: OpenPasswdApi, account: &Account) {
         let list = api.list_groups().await.unwrap();
         let group_id = if let Some(group_name) = &account.group {
-            if let Some(group) = list
-                .items
-                .iter()
-                .filter(|g| g.name.as_str() == group_name)
-                .next()
-            {
+            if let Some(group) = list.items.iter().find(|g| g.name.as_str() == group_name) {
                 group.id
             } else {
-                if let Some(group) = list
-                    .items
-                    .iter()
-                    .filter(|g| g.name.contains(group_name))
-                    .next()
-                {
+                if let Some(group) = list.items.iter().find(|g| g.name.contains(group_name)) {
                     group.id
                 } else {
                     panic!("Group specified not found");
@@ -99,19 +245,218 @@ impl Accounts {
 
         println!("Creating Account {}", account.name);
         let password = if account.generated {
-            generate_string(account.size)
+            let generated = match account.generator.generate() {
+                Ok(generated) => generated,
+                Err(e) => return println!("{e}"),
+            };
+            println!(
+                "Generated a password with ~{:.0} bits of entropy",
+                generated.entropy_bits
+            );
+            generated.password
         } else {
             rpassword::prompt_password("Password: ").unwrap()
         };
 
-        api.register_account(AccountRegister {
-            name: account.name.to_owned(),
-            group_id,
-            level: Some(account.level),
-            username: account.username.to_owned(),
-            password,
-        })
+        let (_, breached) = api
+            .register_account(AccountRegister {
+                name: account.name.to_owned(),
+                group_id,
+                level: Some(account.level),
+                url: account.url.to_owned(),
+                username: account.username.to_owned(),
+                password,
+                favorite: account.favorite,
+                two_factor: account.two_factor,
+                rotation_days: account.rotation_days,
+                generator_profile: account.generated.then(|| account.generator.profile()),
+                tags: account.tags.to_owned(),
+            })
+            .await
+            .unwrap();
+        if let Some(count) = breached {
+            println!("Warning: this password appeared {count} times in data breaches");
+        }
+    }
+
+    async fn delete(&self, api: OpenPasswdApi, name: &str) {
+        let query = AccountsQuery {
+            q: Some(name.to_owned()),
+            ..Default::default()
+        };
+        let list = api.list_accounts(&query).await.unwrap();
+        if let Some(account) = list.items.iter().find(|a| a.name.as_str() == name) {
+            api.delete_account(account.id).await.unwrap();
+            println!("Account {} moved to the trash", account.name);
+        } else {
+            println!("Account {name} not found");
+        }
+    }
+
+    async fn rotate(&self, api: OpenPasswdApi, name: &str) {
+        let query = AccountsQuery {
+            q: Some(name.to_owned()),
+            ..Default::default()
+        };
+        let list = api.list_accounts(&query).await.unwrap();
+        let account = match list.items.into_iter().find(|a| a.name.as_str() == name) {
+            Some(account) => account,
+            None => return println!("Account {name} not found"),
+        };
+
+        let profile = account.generator_profile.unwrap_or_else(|| {
+            println!("Account {name} has no generator profile, using the defaults");
+            GeneratorProfile::default()
+        });
+        let generated = match GeneratorOptions::from(&profile).generate() {
+            Ok(generated) => generated,
+            Err(e) => return println!("{e}"),
+        };
+
+        // The server keeps the previous password as an older version.
+        let (_, breached) = api
+            .update_account(
+                account.id,
+                AccountUpdate {
+                    name: None,
+                    url: None,
+                    username: None,
+                    password: Some(generated.password.to_owned()),
+                    two_factor: None,
+                    rotation_days: None,
+                    generator_profile: None,
+                    revision: Some(account.revision),
+                },
+            )
+            .await
+            .unwrap();
+        println!(
+            "Password of {name} rotated, ~{:.0} bits of entropy",
+            generated.entropy_bits
+        );
+        if let Some(count) = breached {
+            println!("Warning: this password appeared {count} times in data breaches");
+        }
+        copy_password_to_clipboard(generated.password, 5);
+    }
+
+    async fn generator(&self, api: OpenPasswdApi, generator: &AccountGenerator) {
+        let account_id = match Self::find_account_id(&api, &generator.name).await {
+            Some(account_id) => account_id,
+            None => return println!("Account {} not found", generator.name),
+        };
+        // Options that can't produce a password would only fail at rotation.
+        if let Err(e) = generator.generator.generate() {
+            return println!("{e}");
+        }
+
+        api.update_account(
+            account_id,
+            AccountUpdate {
+                name: None,
+                url: None,
+                username: None,
+                password: None,
+                two_factor: None,
+                rotation_days: None,
+                generator_profile: Some(generator.generator.profile()),
+                revision: None,
+            },
+        )
         .await
         .unwrap();
+        println!("Generator profile of {} saved", generator.name);
+    }
+
+    async fn share(&self, api: OpenPasswdApi, share: &AccountShare) {
+        let account_id = match Self::find_account_id(&api, &share.name).await {
+            Some(account_id) => account_id,
+            None => {
+                println!("Account {} not found", share.name);
+                return;
+            }
+        };
+
+        let permission = if share.edit {
+            SharePermission::Edit
+        } else {
+            SharePermission::ReadOnly
+        };
+        let result = api
+            .share_account(
+                account_id,
+                AccountShareRegister {
+                    email: share.email.to_owned(),
+                    permission,
+                },
+            )
+            .await
+            .unwrap();
+        println!(
+            "Account {} shared with {} ({})",
+            share.name,
+            result.email,
+            result.permission.as_str()
+        );
+    }
+
+    async fn unshare(&self, api: OpenPasswdApi, name: &str, email: &str) {
+        let account_id = match Self::find_account_id(&api, name).await {
+            Some(account_id) => account_id,
+            None => {
+                println!("Account {name} not found");
+                return;
+            }
+        };
+
+        let shares = api.list_account_shares(account_id).await.unwrap();
+        if let Some(share) = shares.items.iter().find(|s| s.email.as_str() == email) {
+            api.revoke_account_share(account_id, share.id)
+                .await
+                .unwrap();
+            println!("Account {name} no longer shared with {email}");
+        } else {
+            println!("Account {name} is not shared with {email}");
+        }
+    }
+
+    async fn shares(&self, api: OpenPasswdApi, name: &str) {
+        let account_id = match Self::find_account_id(&api, name).await {
+            Some(account_id) => account_id,
+            None => {
+                println!("Account {name} not found");
+                return;
+            }
+        };
+
+        let shares = api.list_account_shares(account_id).await.unwrap();
+        for share in shares.items {
+            println!("- {} ({})", share.email, share.permission.as_str());
+        }
+    }
+
+    async fn shared(&self, api: OpenPasswdApi) {
+        let list = api.list_shared_accounts().await.unwrap();
+
+        for item in list.items {
+            println!(
+                "- {} from {} ({})",
+                item.name,
+                item.owner,
+                item.permission.as_str()
+            );
+        }
+    }
+
+    async fn find_account_id(api: &OpenPasswdApi, name: &str) -> Option<i32> {
+        let query = AccountsQuery {
+            q: Some(name.to_owned()),
+            ..Default::default()
+        };
+        let list = api.list_accounts(&query).await.unwrap();
+        list.items
+            .iter()
+            .find(|a| a.name.as_str() == name)
+            .map(|a| a.id)
     }
 }
@@ -4,15 +4,28 @@ use std::{cell::RefCell, rc::Rc};
 
 use model::{
     accounts::{
-        AccountGroupRegister, AccountGroupView, AccountRegister, AccountView,
-        AccountWithPasswordView,
+        AccountGroupRegister, AccountGroupView, AccountRegister, AccountShareRegister,
+        AccountShareView, AccountUpdate, AccountView, AccountWithPasswordView, DueAccountView,
+        SharedAccountView, TrashView,
     },
-    auth::{AccessToken, LoginRequest, RefreshToken, RefreshTokenType, UserRegister},
+    audit::AuditEventView,
+    auth::{
+        AccessToken, ElevatedToken, ElevationRequest, LoginRequest, RefreshToken, RefreshTokenType,
+        UserRegister, UserView, ELEVATED_TOKEN_HEADER, PASSWORD_BREACHED_HEADER,
+    },
+    export::VaultExport,
+    import::{ImportReport, ImportRequest},
+    organizations::{
+        CollectionMemberRegister, OrganizationInvitationAccept, OrganizationInvitationView,
+        OrganizationInvite, OrganizationMemberUpdate, OrganizationMemberView, OrganizationRegister,
+        OrganizationView,
+    },
+    reports::HealthReport,
+    webhooks::{WebhookDeliveryView, WebhookRegister, WebhookUpdate, WebhookView},
     List,
 };
 use reqwest::StatusCode;
-
-const BASE_URL: &str = "https://api.openpasswd.com";
+use serde::Serialize;
 
 pub struct OpenPasswdApi {
     profile: Rc<RefCell<Profile>>,
@@ -21,25 +34,79 @@ pub struct OpenPasswdApi {
 #[derive(Debug)]
 pub enum ApiError {
     Reqwest(reqwest::Error),
+    ElevationRequired,
 }
 
 type ApiResult<T = ()> = Result<T, ApiError>;
 
+#[derive(Default, Serialize)]
+pub struct AccountsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favorite: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Default, Serialize)]
+pub struct DueAccountsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
+}
+
+#[derive(Default, Serialize)]
+pub struct AuditQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Default, Serialize)]
+pub struct HealthQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<i64>,
+}
+
 impl OpenPasswdApi {
     pub fn new(profile: Rc<RefCell<Profile>>) -> OpenPasswdApi {
         OpenPasswdApi { profile }
     }
 
-    pub async fn auth_register(&self, user: UserRegister) -> ApiResult {
+    /// `path` on the server of the profile.
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.profile.borrow().server())
+    }
+
+    /// Returns the breach count the server warned about, if any.
+    pub async fn auth_register(&self, user: UserRegister) -> ApiResult<Option<u64>> {
         let response = reqwest::Client::new()
-            .post(format!("{BASE_URL}/api/auth/user"))
+            .post(self.url("/api/auth/user"))
             .json(&user)
             .send()
             .await
             .map_err(ApiError::Reqwest)?;
 
         if response.status() == StatusCode::CREATED {
-            Ok(())
+            let breached = response
+                .headers()
+                .get(PASSWORD_BREACHED_HEADER)
+                .and_then(|count| count.to_str().ok())
+                .and_then(|count| count.parse().ok());
+            Ok(breached)
         } else {
             let text = response.text().await.map_err(ApiError::Reqwest)?;
             panic!("{text}");
@@ -70,7 +137,7 @@ impl OpenPasswdApi {
 
     pub async fn auth_token(&self, login: LoginRequest) -> ApiResult {
         let response = reqwest::Client::new()
-            .post(format!("{BASE_URL}/api/auth/token"))
+            .post(self.url("/api/auth/token"))
             .json(&login)
             .send()
             .await
@@ -88,15 +155,38 @@ impl OpenPasswdApi {
         }
     }
 
-    pub async fn auth_refresh_token(&self) -> ApiResult<AccessToken> {
+    /// `None` when the refresh token is no longer valid. The one sent is
+    /// spent either way.
+    pub async fn auth_refresh_token(&self) -> ApiResult<Option<AccessToken>> {
         let refresh_token = self.profile.borrow().refresh_token().unwrap().to_owned();
         let response = reqwest::Client::new()
-            .post(format!("{BASE_URL}/api/auth/refresh_token"))
+            .post(self.url("/api/auth/refresh_token"))
             .json(&RefreshToken { refresh_token })
             .send()
             .await
             .map_err(ApiError::Reqwest)?;
 
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(Some(result))
+        } else if response.status().is_client_error() {
+            Ok(None)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn auth_elevate(&self, password: String) -> ApiResult<ElevatedToken> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url("/api/auth/elevate"))
+            .bearer_auth(access_token)
+            .json(&ElevationRequest { password })
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
         if response.status() == StatusCode::OK {
             let result = response.json().await.map_err(ApiError::Reqwest)?;
             Ok(result)
@@ -106,31 +196,51 @@ impl OpenPasswdApi {
         }
     }
 
-    pub async fn auth_logout(&self) -> ApiResult {
+    /// Whether the server revoked the session.
+    pub async fn auth_logout(&self) -> ApiResult<bool> {
         let (access_token, refresh_token) = {
             let profile = self.profile.borrow();
             (
                 profile.access_token().unwrap().to_owned(),
-                profile.refresh_token().unwrap().to_owned(),
+                profile.refresh_token().map(str::to_owned),
             )
         };
+        let mut request = reqwest::Client::new()
+            .post(self.url("/api/auth/logout"))
+            .bearer_auth(access_token);
+        // Without it only the access token is revoked.
+        if let Some(refresh_token) = refresh_token {
+            request = request.json(&RefreshToken { refresh_token });
+        }
+        let response = request.send().await.map_err(ApiError::Reqwest)?;
+
+        log::debug!("auth_logout: {}", response.status());
+        Ok(response.status().is_success())
+    }
+
+    pub async fn auth_user(&self) -> ApiResult<UserView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
         let response = reqwest::Client::new()
-            .post(format!("{BASE_URL}/api/auth/logout"))
+            .get(self.url("/api/auth/user"))
             .bearer_auth(access_token)
-            .json(&RefreshToken { refresh_token })
             .send()
             .await
             .map_err(ApiError::Reqwest)?;
 
-        log::debug!("auth_logout: {}", response.status());
-        Ok(())
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
     }
 
     pub async fn list_groups(&self) -> ApiResult<List<AccountGroupView>> {
         let access_token = self.profile.borrow().access_token().unwrap().to_owned();
 
         let response = reqwest::Client::new()
-            .get(format!("{BASE_URL}/api/accounts/groups"))
+            .get(self.url("/api/accounts/groups"))
             .bearer_auth(access_token)
             .send()
             .await
@@ -150,7 +260,7 @@ impl OpenPasswdApi {
         new_account_group: AccountGroupRegister,
     ) -> ApiResult<AccountGroupView> {
         let response = reqwest::Client::new()
-            .post(format!("{BASE_URL}/api/accounts/groups"))
+            .post(self.url("/api/accounts/groups"))
             .json(&new_account_group)
             .send()
             .await
@@ -165,15 +275,33 @@ impl OpenPasswdApi {
         }
     }
 
-    pub async fn list_accounts(&self, id: &Option<i32>) -> ApiResult<List<AccountView>> {
+    pub async fn list_accounts(&self, query: &AccountsQuery) -> ApiResult<List<AccountView>> {
         let access_token = self.profile.borrow().access_token().unwrap().to_owned();
-        let url = if let Some(id) = id {
-            format!("{BASE_URL}/api/accounts?group_id={id}")
+        let response = reqwest::Client::new()
+            .get(self.url("/api/accounts"))
+            .query(query)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
         } else {
-            format!("{BASE_URL}/api/accounts")
-        };
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn list_due_accounts(
+        &self,
+        query: &DueAccountsQuery,
+    ) -> ApiResult<List<DueAccountView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
         let response = reqwest::Client::new()
-            .get(url)
+            .get(self.url("/api/accounts/due"))
+            .query(query)
             .bearer_auth(access_token)
             .send()
             .await
@@ -188,16 +316,111 @@ impl OpenPasswdApi {
         }
     }
 
-    pub async fn register_account(&self, new_account: AccountRegister) -> ApiResult<AccountView> {
+    /// Also returns the breach count the server warned about, if any.
+    pub async fn register_account(
+        &self,
+        new_account: AccountRegister,
+    ) -> ApiResult<(AccountView, Option<u64>)> {
         let access_token = self.profile.borrow().access_token().unwrap().to_owned();
         let response = reqwest::Client::new()
-            .post(format!("{BASE_URL}/api/accounts"))
+            .post(self.url("/api/accounts"))
             .json(&new_account)
             .bearer_auth(access_token)
             .send()
             .await
             .map_err(ApiError::Reqwest)?;
 
+        if response.status() == StatusCode::CREATED {
+            let breached = response
+                .headers()
+                .get(PASSWORD_BREACHED_HEADER)
+                .and_then(|count| count.to_str().ok())
+                .and_then(|count| count.parse().ok());
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok((result, breached))
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    /// Also returns the breach count the server warned about, if any.
+    pub async fn update_account(
+        &self,
+        id: i32,
+        changes: AccountUpdate,
+    ) -> ApiResult<(AccountView, Option<u64>)> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .put(self.url(&format!("/api/accounts/{id}")))
+            .json(&changes)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let breached = response
+                .headers()
+                .get(PASSWORD_BREACHED_HEADER)
+                .and_then(|count| count.to_str().ok())
+                .and_then(|count| count.parse().ok());
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok((result, breached))
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn get_account(
+        &self,
+        id: i32,
+        elevated_token: Option<&str>,
+    ) -> ApiResult<AccountWithPasswordView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let mut request = reqwest::Client::new()
+            .get(self.url(&format!("/api/accounts/{id}")))
+            .bearer_auth(access_token);
+        if let Some(elevated_token) = elevated_token {
+            request = request.header(ELEVATED_TOKEN_HEADER, elevated_token);
+        }
+        let response = request.send().await.map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else if response.status() == StatusCode::FORBIDDEN {
+            Err(ApiError::ElevationRequired)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn delete_group(&self, id: i32) -> ApiResult {
+        self.delete(self.url(&format!("/api/accounts/groups/{id}")))
+            .await
+    }
+
+    pub async fn delete_account(&self, id: i32) -> ApiResult {
+        self.delete(self.url(&format!("/api/accounts/{id}"))).await
+    }
+
+    pub async fn share_account(
+        &self,
+        id: i32,
+        share: AccountShareRegister,
+    ) -> ApiResult<AccountShareView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url(&format!("/api/accounts/{id}/shares")))
+            .json(&share)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
         if response.status() == StatusCode::CREATED {
             let result = response.json().await.map_err(ApiError::Reqwest)?;
             Ok(result)
@@ -207,10 +430,10 @@ impl OpenPasswdApi {
         }
     }
 
-    pub async fn get_account(&self, id: i32) -> ApiResult<AccountWithPasswordView> {
+    pub async fn list_account_shares(&self, id: i32) -> ApiResult<List<AccountShareView>> {
         let access_token = self.profile.borrow().access_token().unwrap().to_owned();
         let response = reqwest::Client::new()
-            .get(format!("{BASE_URL}/api/accounts/{id}"))
+            .get(self.url(&format!("/api/accounts/{id}/shares")))
             .bearer_auth(access_token)
             .send()
             .await
@@ -224,4 +447,471 @@ impl OpenPasswdApi {
             panic!("{text}");
         }
     }
+
+    pub async fn revoke_account_share(&self, id: i32, share_id: i32) -> ApiResult {
+        self.delete(self.url(&format!("/api/accounts/{id}/shares/{share_id}")))
+            .await
+    }
+
+    pub async fn list_shared_accounts(&self) -> ApiResult<List<SharedAccountView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url("/api/accounts/shared"))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn list_trash(&self) -> ApiResult<TrashView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url("/api/trash"))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn restore_from_trash(&self, kind: &str, id: i32) -> ApiResult {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url(&format!("/api/trash/{kind}/{id}/restore")))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::NO_CONTENT {
+            Ok(())
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn purge_from_trash(&self, kind: &str, id: i32) -> ApiResult {
+        self.delete(self.url(&format!("/api/trash/{kind}/{id}")))
+            .await
+    }
+
+    pub async fn list_organizations(&self) -> ApiResult<List<OrganizationView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url("/api/organizations"))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn register_organization(
+        &self,
+        organization: OrganizationRegister,
+    ) -> ApiResult<OrganizationView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url("/api/organizations"))
+            .json(&organization)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::CREATED {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn list_organization_members(
+        &self,
+        id: i32,
+    ) -> ApiResult<List<OrganizationMemberView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url(&format!("/api/organizations/{id}/members")))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn remove_organization_member(&self, id: i32, user_id: i32) -> ApiResult {
+        self.delete(self.url(&format!("/api/organizations/{id}/members/{user_id}")))
+            .await
+    }
+
+    pub async fn update_organization_member(
+        &self,
+        id: i32,
+        user_id: i32,
+        update: OrganizationMemberUpdate,
+    ) -> ApiResult<OrganizationMemberView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .put(self.url(&format!("/api/organizations/{id}/members/{user_id}")))
+            .json(&update)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn invite_organization_member(
+        &self,
+        id: i32,
+        invite: OrganizationInvite,
+    ) -> ApiResult<OrganizationInvitationView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url(&format!("/api/organizations/{id}/invitations")))
+            .json(&invite)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::CREATED {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn accept_organization_invitation(
+        &self,
+        accept: OrganizationInvitationAccept,
+    ) -> ApiResult<OrganizationView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url("/api/organizations/invitations/accept"))
+            .json(&accept)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn list_collections(&self, id: i32) -> ApiResult<List<AccountGroupView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url(&format!("/api/organizations/{id}/collections")))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn register_collection(
+        &self,
+        id: i32,
+        collection: AccountGroupRegister,
+    ) -> ApiResult<AccountGroupView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url(&format!("/api/organizations/{id}/collections")))
+            .json(&collection)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::CREATED {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn assign_collection_member(
+        &self,
+        id: i32,
+        group_id: i32,
+        member: CollectionMemberRegister,
+    ) -> ApiResult {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url(&format!(
+                "/api/organizations/{id}/collections/{group_id}/members"
+            )))
+            .json(&member)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::NO_CONTENT {
+            Ok(())
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn unassign_collection_member(
+        &self,
+        id: i32,
+        group_id: i32,
+        user_id: i32,
+    ) -> ApiResult {
+        self.delete(self.url(&format!(
+            "/api/organizations/{id}/collections/{group_id}/members/{user_id}"
+        )))
+        .await
+    }
+
+    pub async fn list_audit_events(&self, query: &AuditQuery) -> ApiResult<List<AuditEventView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url("/api/audit"))
+            .query(query)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn health_report(&self, query: &HealthQuery) -> ApiResult<HealthReport> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url("/api/reports/health"))
+            .query(query)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn register_webhook(&self, webhook: WebhookRegister) -> ApiResult<WebhookView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url("/api/webhooks"))
+            .json(&webhook)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::CREATED {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn list_webhooks(&self) -> ApiResult<List<WebhookView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url("/api/webhooks"))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn update_webhook(&self, id: i32, update: WebhookUpdate) -> ApiResult<WebhookView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .put(self.url(&format!("/api/webhooks/{id}")))
+            .json(&update)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn delete_webhook(&self, id: i32) -> ApiResult {
+        self.delete(self.url(&format!("/api/webhooks/{id}"))).await
+    }
+
+    pub async fn list_webhook_deliveries(&self, id: i32) -> ApiResult<List<WebhookDeliveryView>> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url(&format!("/api/webhooks/{id}/deliveries")))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn test_webhook(&self, id: i32) -> ApiResult<WebhookDeliveryView> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url(&format!("/api/webhooks/{id}/test")))
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn import(&self, request: &ImportRequest) -> ApiResult<ImportReport> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .post(self.url("/api/import"))
+            .json(request)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status().is_success() {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    pub async fn export_vault(&self, elevated_token: &str) -> ApiResult<VaultExport> {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .get(self.url("/api/export"))
+            .bearer_auth(access_token)
+            .header(ELEVATED_TOKEN_HEADER, elevated_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::OK {
+            let result = response.json().await.map_err(ApiError::Reqwest)?;
+            Ok(result)
+        } else if response.status() == StatusCode::FORBIDDEN {
+            Err(ApiError::ElevationRequired)
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
+
+    async fn delete(&self, url: String) -> ApiResult {
+        let access_token = self.profile.borrow().access_token().unwrap().to_owned();
+        let response = reqwest::Client::new()
+            .delete(url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .map_err(ApiError::Reqwest)?;
+
+        if response.status() == StatusCode::NO_CONTENT {
+            Ok(())
+        } else {
+            let text = response.text().await.map_err(ApiError::Reqwest)?;
+            panic!("{text}");
+        }
+    }
 }
@@ -0,0 +1,54 @@
+use std::{cell::RefCell, rc::Rc};
+
+use clap::Args;
+use model::audit::AuditEventType;
+
+use crate::{
+    api::{AuditQuery, OpenPasswdApi},
+    profile::Profile,
+};
+
+/// Names of the event types, as accepted by the server.
+pub fn event_types() -> [&'static str; AuditEventType::ALL.len()] {
+    AuditEventType::ALL.map(|event_type| event_type.as_str())
+}
+
+/// Show your own recent security events, newest first
+#[derive(Debug, Args)]
+pub struct Audit {
+    #[clap(long = "type", possible_values = event_types())]
+    event_type: Option<String>,
+    /// Only events at or after this RFC 3339 timestamp
+    #[clap(long)]
+    since: Option<String>,
+    #[clap(long, default_value_t = 20)]
+    limit: u32,
+}
+
+impl Audit {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let api = OpenPasswdApi::new(profile);
+
+        let query = AuditQuery {
+            event_type: self.event_type.clone(),
+            since: self.since.clone(),
+            limit: Some(self.limit),
+            ..Default::default()
+        };
+        let events = api.list_audit_events(&query).await.unwrap();
+
+        for event in events.items {
+            let mut line = format!("{} {}", event.created_date, event.event_type.as_str());
+            if let Some(target) = event.target {
+                line.push_str(&format!(" {target}"));
+            }
+            if let Some(device) = event.device {
+                line.push_str(&format!(" from {device}"));
+            }
+            if let Some(ip_address) = event.ip_address {
+                line.push_str(&format!(" ({ip_address})"));
+            }
+            println!("{line}");
+        }
+    }
+}
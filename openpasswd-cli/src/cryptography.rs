@@ -0,0 +1,196 @@
+use aes_gcm::aead::{Aead, NewAead};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use model::export::{EncryptedVaultExport, EXPORT_CIPHER, EXPORT_KDF, EXPORT_VERSION};
+use rand::RngCore;
+
+const KEY_LENGTH: u32 = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+const MEMORY_KIB: u32 = 65536;
+const ITERATIONS: u32 = 3;
+const PARALLELISM: u32 = 4;
+/// Bounds of the costs read from a file, a damaged or hostile one could
+/// otherwise make Argon2 panic or allocate terabytes.
+const MIN_SALT_LENGTH: usize = 16;
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 64;
+
+#[derive(Debug)]
+pub enum ExportCryptoError {
+    UnsupportedFormat,
+    InvalidEncoding,
+    /// Wrong passphrase or altered file, AES-GCM can't tell them apart.
+    DecryptionFailed,
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<Vec<u8>, ExportCryptoError> {
+    // Argon2 needs 8 KiB of memory per lane.
+    if salt.len() < MIN_SALT_LENGTH
+        || !(1..=MAX_PARALLELISM).contains(&parallelism)
+        || !(8 * parallelism..=MAX_MEMORY_KIB).contains(&memory_kib)
+        || !(1..=MAX_ITERATIONS).contains(&iterations)
+    {
+        return Err(ExportCryptoError::UnsupportedFormat);
+    }
+
+    let config = argon2::Config {
+        variant: argon2::Variant::Argon2id,
+        version: argon2::Version::Version13,
+        mem_cost: memory_kib,
+        time_cost: iterations,
+        lanes: parallelism,
+        thread_mode: argon2::ThreadMode::Parallel,
+        secret: &[],
+        ad: &[],
+        hash_length: KEY_LENGTH,
+    };
+    argon2::hash_raw(passphrase.as_bytes(), salt, &config)
+        .map_err(|_| ExportCryptoError::UnsupportedFormat)
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; length];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+pub fn encrypt_export(plaintext: &str, passphrase: &str) -> EncryptedVaultExport {
+    encrypt_export_with_costs(plaintext, passphrase, MEMORY_KIB, ITERATIONS, PARALLELISM)
+}
+
+fn encrypt_export_with_costs(
+    plaintext: &str,
+    passphrase: &str,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> EncryptedVaultExport {
+    let salt = random_bytes(SALT_LENGTH);
+    let nonce = random_bytes(NONCE_LENGTH);
+    let key = derive_key(passphrase, &salt, memory_kib, iterations, parallelism)
+        .expect("invalid export costs");
+
+    let cipher = Aes256Gcm::new(Key::from_slice(&key));
+    let data = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+        .expect("encryption failure!");
+
+    EncryptedVaultExport {
+        version: EXPORT_VERSION,
+        kdf: String::from(EXPORT_KDF),
+        memory_kib,
+        iterations,
+        parallelism,
+        salt: base64::encode(salt),
+        cipher: String::from(EXPORT_CIPHER),
+        nonce: base64::encode(nonce),
+        data: base64::encode(data),
+    }
+}
+
+/// The KDF parameters are read from the file so exports made with other
+/// costs stay readable.
+pub fn decrypt_export(
+    export: &EncryptedVaultExport,
+    passphrase: &str,
+) -> Result<String, ExportCryptoError> {
+    if export.kdf != EXPORT_KDF || export.cipher != EXPORT_CIPHER {
+        return Err(ExportCryptoError::UnsupportedFormat);
+    }
+    let salt = base64::decode(&export.salt).map_err(|_| ExportCryptoError::InvalidEncoding)?;
+    let nonce = base64::decode(&export.nonce).map_err(|_| ExportCryptoError::InvalidEncoding)?;
+    let data = base64::decode(&export.data).map_err(|_| ExportCryptoError::InvalidEncoding)?;
+    if nonce.len() != NONCE_LENGTH {
+        return Err(ExportCryptoError::InvalidEncoding);
+    }
+
+    let key = derive_key(
+        passphrase,
+        &salt,
+        export.memory_kib,
+        export.iterations,
+        export.parallelism,
+    )?;
+    let cipher = Aes256Gcm::new(Key::from_slice(&key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), data.as_ref())
+        .map_err(|_| ExportCryptoError::DecryptionFailed)?;
+
+    String::from_utf8(plaintext).map_err(|_| ExportCryptoError::InvalidEncoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use model::export::EncryptedVaultExport;
+
+    use super::{decrypt_export, encrypt_export_with_costs, ExportCryptoError};
+
+    const PLAINTEXT: &str = r#"{"version":1,"groups":[{"name":"Personal","accounts":[]}]}"#;
+
+    /// Small costs, the default ones take seconds in debug builds.
+    fn encrypt(passphrase: &str) -> EncryptedVaultExport {
+        encrypt_export_with_costs(PLAINTEXT, passphrase, 64, 1, 1)
+    }
+
+    #[test]
+    fn decrypts_what_it_encrypted() {
+        let export = encrypt("correct horse");
+        assert_eq!(PLAINTEXT, decrypt_export(&export, "correct horse").unwrap());
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let export = encrypt("correct horse");
+        assert!(matches!(
+            decrypt_export(&export, "battery staple"),
+            Err(ExportCryptoError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn tampered_data_fails() {
+        let mut export = encrypt("correct horse");
+        let mut data = base64::decode(&export.data).unwrap();
+        data[0] ^= 1;
+        export.data = base64::encode(data);
+        assert!(matches!(
+            decrypt_export(&export, "correct horse"),
+            Err(ExportCryptoError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn unknown_kdf_is_unsupported() {
+        let mut export = encrypt("correct horse");
+        export.kdf = String::from("pbkdf2");
+        assert!(matches!(
+            decrypt_export(&export, "correct horse"),
+            Err(ExportCryptoError::UnsupportedFormat)
+        ));
+    }
+
+    #[test]
+    fn unreasonable_costs_are_unsupported() {
+        let unsupported = |change: fn(&mut EncryptedVaultExport)| {
+            let mut export = encrypt("correct horse");
+            change(&mut export);
+            matches!(
+                decrypt_export(&export, "correct horse"),
+                Err(ExportCryptoError::UnsupportedFormat)
+            )
+        };
+
+        assert!(unsupported(|export| export.parallelism = 0));
+        assert!(unsupported(|export| export.iterations = 0));
+        assert!(unsupported(|export| export.memory_kib = 4));
+        assert!(unsupported(|export| export.memory_kib = u32::MAX));
+        assert!(unsupported(|export| export.salt = base64::encode([0; 4])));
+    }
+}
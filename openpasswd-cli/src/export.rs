@@ -0,0 +1,120 @@
+use std::{cell::RefCell, fs::OpenOptions, io::Write, path::PathBuf, rc::Rc};
+
+use clap::Args;
+
+use crate::{
+    api::OpenPasswdApi,
+    cryptography::encrypt_export,
+    keepass::{write_kdbx, CIPHERS},
+    login::read_prompt_input,
+    profile::Profile,
+};
+
+pub const FORMATS: [&str; 2] = ["openpasswd", "kdbx"];
+
+/// Export the personal vault, encrypted with a passphrase unless told otherwise
+#[derive(Debug, Args)]
+pub struct Export {
+    file: PathBuf,
+    #[clap(long, possible_values = FORMATS, default_value = "openpasswd")]
+    format: String,
+    /// Payload cipher of a KeePass database
+    #[clap(long, possible_values = CIPHERS, default_value = "aes")]
+    cipher: String,
+    /// Write the passwords in clear
+    #[clap(long)]
+    plaintext: bool,
+    /// Don't ask for confirmation of a plaintext export
+    #[clap(short, long)]
+    yes: bool,
+}
+
+impl Export {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let kdbx = self.format == "kdbx";
+        if self.plaintext && kdbx {
+            println!("KeePass databases are always encrypted");
+            return;
+        }
+        if self.plaintext && !self.yes {
+            println!("The export will contain every password in clear.");
+            let answer = read_prompt_input("Continue? [y/N] ").unwrap();
+            if !answer.eq_ignore_ascii_case("y") {
+                return;
+            }
+        }
+
+        let api = OpenPasswdApi::new(profile);
+        let password = rpassword::prompt_password("Password: ").unwrap();
+        let elevated_token = api.auth_elevate(password).await.unwrap();
+        let export = api
+            .export_vault(&elevated_token.elevated_token)
+            .await
+            .unwrap();
+
+        let contents = if self.plaintext {
+            serde_json::to_vec_pretty(&export).unwrap()
+        } else {
+            let prompt = if kdbx {
+                "Database password: "
+            } else {
+                "Export passphrase: "
+            };
+            let passphrase = match prompt_new_passphrase(prompt) {
+                Some(passphrase) => passphrase,
+                None => return,
+            };
+            if kdbx {
+                match write_kdbx(&export, &self.cipher, &passphrase) {
+                    Ok(contents) => contents,
+                    Err(e) => {
+                        println!("Can't write the KeePass database: {e}");
+                        return;
+                    }
+                }
+            } else {
+                let plaintext = serde_json::to_string(&export).unwrap();
+                serde_json::to_vec_pretty(&encrypt_export(&plaintext, &passphrase)).unwrap()
+            }
+        };
+
+        if let Err(e) = write_private(&self.file, &contents) {
+            println!("Can't write {}: {e}", self.file.display());
+            return;
+        }
+
+        let accounts: usize = export.groups.iter().map(|group| group.accounts.len()).sum();
+        println!(
+            "{} groups and {accounts} accounts exported to {}",
+            export.groups.len(),
+            self.file.display()
+        );
+    }
+}
+
+fn prompt_new_passphrase(prompt: &str) -> Option<String> {
+    let passphrase = rpassword::prompt_password(prompt).unwrap();
+    if passphrase.is_empty() {
+        println!("The passphrase can't be empty");
+        return None;
+    }
+    let confirmation = rpassword::prompt_password("Confirm passphrase: ").unwrap();
+    if passphrase != confirmation {
+        println!("The passphrases don't match");
+        return None;
+    }
+    Some(passphrase)
+}
+
+/// Creates the file readable by its owner only.
+fn write_private(path: &PathBuf, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(contents)
+}
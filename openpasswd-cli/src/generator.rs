@@ -1,24 +1,393 @@
 use crate::clipboard::copy_password_to_clipboard;
 use clap::Args;
-use rand::Rng;
+use model::accounts::GeneratorProfile;
+use rand::{seq::SliceRandom, Rng};
 
-fn generate_string_vec_u8(size: usize) -> Vec<u8> {
-    let mut rng = rand::thread_rng();
-    (&mut rng)
-        .sample_iter(rand::distributions::Alphanumeric)
-        .take(size)
+/// EFF large wordlist, `DICE\tWORD` per line, 7776 words.
+const EFF_LARGE_WORDLIST: &str = include_str!("../wordlists/eff_large_wordlist.txt");
+
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const DIGITS: &str = "0123456789";
+const DEFAULT_SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>/?~";
+/// Characters easily mistaken for one another.
+const AMBIGUOUS: &str = "0Oo1lI|`'\"";
+
+fn wordlist() -> Vec<&'static str> {
+    EFF_LARGE_WORDLIST
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(_, word)| word)
         .collect()
 }
 
-pub fn generate_string(size: usize) -> String {
-    String::from_utf8(generate_string_vec_u8(size)).unwrap()
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
 
+/// Same bounds as `GeneratorProfile`, checked before anything is generated.
+fn in_range(value: &str, range: std::ops::RangeInclusive<usize>) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(value) if range.contains(&value) => Ok(()),
+        _ => Err(format!(
+            "must be between {} and {}",
+            range.start(),
+            range.end()
+        )),
+    }
+}
+
+fn valid_size(value: &str) -> Result<(), String> {
+    in_range(value, 1..=1024)
+}
+
+fn valid_words(value: &str) -> Result<(), String> {
+    in_range(value, 1..=64)
+}
+
+/// Uniform pick, every character of `chars` is equally likely.
+fn pick(rng: &mut impl Rng, chars: &[char]) -> char {
+    chars[rng.gen_range(0..chars.len())]
+}
+
+pub struct GeneratedPassword {
+    pub password: String,
+    pub entropy_bits: f64,
+}
+
+struct CharacterClass {
+    name: &'static str,
+    chars: Vec<char>,
+    included: bool,
+    min: usize,
+}
+
+// How passwords are generated, shared by `generator` and
+// `account create --generated`. Not a doc comment, clap would show it as the
+// description of both commands.
+#[derive(Debug, Args)]
+pub struct GeneratorOptions {
+    /// Length of a random password
+    #[clap(short, long, default_value_t = 16, validator = valid_size)]
+    size: usize,
+
+    /// Leave out upper case letters
+    #[clap(long)]
+    no_upper: bool,
+
+    /// Leave out lower case letters
+    #[clap(long)]
+    no_lower: bool,
+
+    /// Leave out digits
+    #[clap(long)]
+    no_digits: bool,
+
+    /// Include symbols
+    #[clap(long)]
+    symbols: bool,
+
+    /// Symbols to pick from, implies --symbols
+    #[clap(long)]
+    symbol_set: Option<String>,
+
+    /// Characters never to use
+    #[clap(long, default_value = "")]
+    exclude: String,
+
+    /// Leave out characters easily mistaken for one another, like 0 and O
+    #[clap(long)]
+    no_ambiguous: bool,
+
+    /// Minimum number of upper case letters
+    #[clap(long, default_value_t = 0)]
+    min_upper: usize,
+
+    /// Minimum number of lower case letters
+    #[clap(long, default_value_t = 0)]
+    min_lower: usize,
+
+    /// Minimum number of digits
+    #[clap(long, default_value_t = 0)]
+    min_digits: usize,
+
+    /// Minimum number of symbols
+    #[clap(long, default_value_t = 0)]
+    min_symbols: usize,
+
+    /// Template of the password instead of --size: A upper case letter,
+    /// a lower case letter, 9 digit, # symbol, * any included character,
+    /// \ escapes the next character, anything else is kept as is
+    #[clap(long, conflicts_with = "passphrase")]
+    pattern: Option<String>,
+
+    /// Generate a passphrase of words from the EFF large wordlist instead
+    #[clap(long)]
+    passphrase: bool,
+
+    /// Words in the passphrase
+    #[clap(long, default_value_t = 6, validator = valid_words)]
+    words: usize,
+
+    /// Between the words of the passphrase
+    #[clap(long, default_value = "-")]
+    separator: String,
+
+    /// Capitalize the words of the passphrase
+    #[clap(long)]
+    capitalize: bool,
+
+    /// Random digits appended to the passphrase
+    #[clap(long, default_value_t = 0)]
+    digits: usize,
+}
+
+impl From<&GeneratorProfile> for GeneratorOptions {
+    fn from(profile: &GeneratorProfile) -> Self {
+        GeneratorOptions {
+            size: profile.size,
+            no_upper: !profile.upper,
+            no_lower: !profile.lower,
+            no_digits: !profile.digits,
+            symbols: profile.symbols,
+            symbol_set: profile.symbol_set.to_owned(),
+            exclude: profile.exclude.to_owned(),
+            no_ambiguous: profile.exclude_ambiguous,
+            min_upper: profile.min_upper,
+            min_lower: profile.min_lower,
+            min_digits: profile.min_digits,
+            min_symbols: profile.min_symbols,
+            pattern: profile.pattern.to_owned(),
+            passphrase: profile.passphrase,
+            words: profile.words,
+            separator: profile.separator.to_owned(),
+            capitalize: profile.capitalize,
+            digits: profile.passphrase_digits,
+        }
+    }
+}
+
+impl GeneratorOptions {
+    /// The options as stored on an account.
+    pub fn profile(&self) -> GeneratorProfile {
+        GeneratorProfile {
+            size: self.size,
+            upper: !self.no_upper,
+            lower: !self.no_lower,
+            digits: !self.no_digits,
+            symbols: self.symbols,
+            symbol_set: self.symbol_set.to_owned(),
+            exclude: self.exclude.to_owned(),
+            exclude_ambiguous: self.no_ambiguous,
+            min_upper: self.min_upper,
+            min_lower: self.min_lower,
+            min_digits: self.min_digits,
+            min_symbols: self.min_symbols,
+            pattern: self.pattern.to_owned(),
+            passphrase: self.passphrase,
+            words: self.words,
+            separator: self.separator.to_owned(),
+            capitalize: self.capitalize,
+            passphrase_digits: self.digits,
+        }
+    }
+
+    pub fn generate(&self) -> Result<GeneratedPassword, String> {
+        if self.passphrase {
+            Ok(self.generate_passphrase())
+        } else if let Some(pattern) = &self.pattern {
+            self.generate_from_pattern(pattern)
+        } else {
+            self.generate_random()
+        }
+    }
+
+    /// `chars` without the excluded and, if asked, the ambiguous ones, each
+    /// character once so none is more likely than the others.
+    fn allowed(&self, chars: &str) -> Vec<char> {
+        let mut allowed: Vec<char> = Vec::new();
+        for c in chars.chars() {
+            let excluded = self.exclude.contains(c) || (self.no_ambiguous && AMBIGUOUS.contains(c));
+            if !excluded && !allowed.contains(&c) {
+                allowed.push(c);
+            }
+        }
+        allowed
+    }
+
+    fn classes(&self) -> [CharacterClass; 4] {
+        [
+            CharacterClass {
+                name: "upper case letters",
+                chars: self.allowed(UPPER),
+                included: !self.no_upper,
+                min: self.min_upper,
+            },
+            CharacterClass {
+                name: "lower case letters",
+                chars: self.allowed(LOWER),
+                included: !self.no_lower,
+                min: self.min_lower,
+            },
+            CharacterClass {
+                name: "digits",
+                chars: self.allowed(DIGITS),
+                included: !self.no_digits,
+                min: self.min_digits,
+            },
+            CharacterClass {
+                name: "symbols",
+                // Letters and digits in a custom set would be picked more
+                // often than the others.
+                chars: self
+                    .allowed(self.symbol_set.as_deref().unwrap_or(DEFAULT_SYMBOLS))
+                    .into_iter()
+                    .filter(|c| !c.is_alphanumeric())
+                    .collect(),
+                included: self.symbols || self.symbol_set.is_some(),
+                min: self.min_symbols,
+            },
+        ]
+    }
+
+    /// The minimum of each class is drawn from that class, the rest from all
+    /// included characters, then the positions are shuffled. Nothing is ever
+    /// drawn again to meet a constraint.
+    fn generate_random(&self) -> Result<GeneratedPassword, String> {
+        let classes = self.classes();
+        for class in classes.iter().filter(|class| class.min > 0) {
+            if !class.included || class.chars.is_empty() {
+                return Err(format!(
+                    "A minimum of {} is set but they are excluded",
+                    class.name
+                ));
+            }
+        }
+        let required: usize = classes.iter().map(|class| class.min).sum();
+        if required > self.size {
+            return Err(format!(
+                "The minimums add up to {required} characters, more than the size of {}",
+                self.size
+            ));
+        }
+        let alphabet: Vec<char> = classes
+            .iter()
+            .filter(|class| class.included)
+            .flat_map(|class| class.chars.iter().copied())
+            .collect();
+        if alphabet.is_empty() {
+            return Err(String::from("Every character is excluded"));
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut password: Vec<char> = Vec::with_capacity(self.size);
+        let mut entropy_bits = 0.0;
+        for class in classes.iter() {
+            for _ in 0..class.min {
+                password.push(pick(&mut rng, &class.chars));
+            }
+            entropy_bits += class.min as f64 * (class.chars.len() as f64).log2();
+        }
+        for _ in required..self.size {
+            password.push(pick(&mut rng, &alphabet));
+        }
+        entropy_bits += (self.size - required) as f64 * (alphabet.len() as f64).log2();
+        password.shuffle(&mut rng);
+
+        Ok(GeneratedPassword {
+            password: password.into_iter().collect(),
+            // Lower bound, where the minimums landed is left out.
+            entropy_bits,
+        })
+    }
+
+    fn generate_from_pattern(&self, pattern: &str) -> Result<GeneratedPassword, String> {
+        let [upper, lower, digits, symbols] = self.classes();
+        let any = CharacterClass {
+            name: "characters",
+            chars: [&upper, &lower, &digits, &symbols]
+                .iter()
+                .filter(|class| class.included)
+                .flat_map(|class| class.chars.iter().copied())
+                .collect(),
+            included: true,
+            min: 0,
+        };
+
+        let mut rng = rand::thread_rng();
+        let mut password = String::new();
+        let mut entropy_bits = 0.0;
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            let class = match c {
+                'A' => &upper,
+                'a' => &lower,
+                '9' => &digits,
+                '#' => &symbols,
+                '*' => &any,
+                '\\' => {
+                    password.extend(chars.next());
+                    continue;
+                }
+                c => {
+                    password.push(c);
+                    continue;
+                }
+            };
+            if !class.included || class.chars.is_empty() {
+                return Err(format!(
+                    "The pattern asks for {} but they are excluded",
+                    class.name
+                ));
+            }
+            password.push(pick(&mut rng, &class.chars));
+            entropy_bits += (class.chars.len() as f64).log2();
+        }
+
+        Ok(GeneratedPassword {
+            password,
+            entropy_bits,
+        })
+    }
+
+    /// Capitalization adds no entropy, it is applied to every word.
+    fn generate_passphrase(&self) -> GeneratedPassword {
+        let wordlist = wordlist();
+        let mut rng = rand::thread_rng();
+
+        let mut parts: Vec<String> = (0..self.words)
+            .map(|_| {
+                let word = wordlist.choose(&mut rng).unwrap();
+                if self.capitalize {
+                    capitalize(word)
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect();
+        if self.digits > 0 {
+            let digits: Vec<char> = DIGITS.chars().collect();
+            parts.push((0..self.digits).map(|_| pick(&mut rng, &digits)).collect());
+        }
+
+        GeneratedPassword {
+            password: parts.join(&self.separator),
+            entropy_bits: self.words as f64 * (wordlist.len() as f64).log2()
+                + self.digits as f64 * 10f64.log2(),
+        }
+    }
+}
+
+/// Generate a password or a passphrase and copy it to the clipboard
 #[derive(Debug, Args)]
 #[clap(args_conflicts_with_subcommands = true)]
 pub struct Generator {
-    #[clap(short, long, default_value_t = 16)]
-    size: usize,
+    #[clap(flatten)]
+    options: GeneratorOptions,
 
     #[clap(short, long)]
     print: bool,
@@ -26,11 +395,148 @@ pub struct Generator {
 
 impl Generator {
     pub fn execute(&self) {
-        let password = generate_string(self.size);
+        let generated = match self.options.generate() {
+            Ok(generated) => generated,
+            Err(e) => return println!("{e}"),
+        };
 
         if self.print {
-            println!("{}", &password);
+            println!("{}", &generated.password);
+        }
+        println!("~{:.0} bits of entropy", generated.entropy_bits);
+        copy_password_to_clipboard(generated.password, 5);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use model::accounts::GeneratorProfile;
+
+    use super::{wordlist, GeneratorOptions, AMBIGUOUS, DEFAULT_SYMBOLS};
+
+    fn options(profile: GeneratorProfile) -> GeneratorOptions {
+        GeneratorOptions::from(&profile)
+    }
+
+    fn count(password: &str, chars: impl Fn(char) -> bool) -> usize {
+        password.chars().filter(|c| chars(*c)).count()
+    }
+
+    #[test]
+    fn minimums_are_met() {
+        let options = options(GeneratorProfile {
+            size: 8,
+            symbols: true,
+            min_upper: 2,
+            min_digits: 3,
+            min_symbols: 2,
+            ..Default::default()
+        });
+        for _ in 0..100 {
+            let password = options.generate().unwrap().password;
+            assert_eq!(8, password.chars().count());
+            assert!(
+                count(&password, |c| c.is_ascii_uppercase()) >= 2,
+                "{password}"
+            );
+            assert!(count(&password, |c| c.is_ascii_digit()) >= 3, "{password}");
+            assert!(
+                count(&password, |c| DEFAULT_SYMBOLS.contains(c)) >= 2,
+                "{password}"
+            );
         }
-        copy_password_to_clipboard(password, 5);
+    }
+
+    #[test]
+    fn excluded_characters_are_left_out() {
+        let options = options(GeneratorProfile {
+            size: 500,
+            symbols: true,
+            exclude: String::from("abcXYZ7%"),
+            exclude_ambiguous: true,
+            ..Default::default()
+        });
+        let password = options.generate().unwrap().password;
+        assert_eq!(
+            0,
+            count(&password, |c| "abcXYZ7%".contains(c)
+                || AMBIGUOUS.contains(c))
+        );
+    }
+
+    #[test]
+    fn patterns_keep_literals_and_escapes() {
+        let options = options(GeneratorProfile {
+            pattern: Some(String::from(r"\A\9-Aa9-x")),
+            ..Default::default()
+        });
+        let generated = options.generate().unwrap();
+        let chars: Vec<char> = generated.password.chars().collect();
+        assert_eq!(8, chars.len());
+        assert_eq!(['A', '9', '-'], chars[..3]);
+        assert!(chars[3].is_ascii_uppercase());
+        assert!(chars[4].is_ascii_lowercase());
+        assert!(chars[5].is_ascii_digit());
+        assert_eq!(['-', 'x'], chars[6..]);
+        let expected = 2.0 * 26f64.log2() + 10f64.log2();
+        assert!((generated.entropy_bits - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn impossible_options_are_errors() {
+        let minimums_too_large = options(GeneratorProfile {
+            size: 4,
+            min_upper: 3,
+            min_digits: 2,
+            ..Default::default()
+        });
+        assert!(minimums_too_large.generate().is_err());
+
+        let everything_excluded = options(GeneratorProfile {
+            upper: false,
+            lower: false,
+            digits: false,
+            ..Default::default()
+        });
+        assert!(everything_excluded.generate().is_err());
+
+        let minimum_of_excluded = options(GeneratorProfile {
+            upper: false,
+            min_upper: 1,
+            ..Default::default()
+        });
+        assert!(minimum_of_excluded.generate().is_err());
+
+        let pattern_of_excluded = options(GeneratorProfile {
+            upper: false,
+            pattern: Some(String::from("AAaa")),
+            ..Default::default()
+        });
+        assert!(pattern_of_excluded.generate().is_err());
+
+        let pattern_of_emptied = options(GeneratorProfile {
+            exclude: String::from("0123456789"),
+            pattern: Some(String::from("a9")),
+            ..Default::default()
+        });
+        assert!(pattern_of_emptied.generate().is_err());
+    }
+
+    #[test]
+    fn passphrases_have_the_words_asked_for() {
+        let options = options(GeneratorProfile {
+            passphrase: true,
+            words: 5,
+            separator: String::from("_"),
+            ..Default::default()
+        });
+        let wordlist = wordlist();
+        assert_eq!(7776, wordlist.len());
+
+        let generated = options.generate().unwrap();
+        let words: Vec<&str> = generated.password.split('_').collect();
+        assert_eq!(5, words.len());
+        assert!(words.iter().all(|word| wordlist.contains(word)));
+        assert!((generated.entropy_bits - 5.0 * 7776f64.log2()).abs() < 1e-9);
     }
 }
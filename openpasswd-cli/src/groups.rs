@@ -8,7 +8,15 @@ use crate::{api::OpenPasswdApi, profile::Profile};
 #[derive(Debug, Subcommand)]
 enum GroupsCommands {
     List,
-    Create { name: String },
+    Create {
+        name: String,
+        /// Days after which the passwords of the group are due for rotation
+        #[clap(long)]
+        rotation_days: Option<i32>,
+    },
+    Delete {
+        name: String,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -24,7 +32,11 @@ impl Groups {
 
         match &self.command {
             GroupsCommands::List => self.list(api).await,
-            GroupsCommands::Create { name } => self.create(api, name).await,
+            GroupsCommands::Create {
+                name,
+                rotation_days,
+            } => self.create(api, name, *rotation_days).await,
+            GroupsCommands::Delete { name } => self.delete(api, name).await,
         }
     }
 
@@ -32,15 +44,29 @@ impl Groups {
         let list = api.list_groups().await.unwrap();
 
         for item in list.items {
-            println!("- {}", item.name);
+            match item.rotation_days {
+                Some(days) => println!("- {} (rotation every {days} days)", item.name),
+                None => println!("- {}", item.name),
+            }
         }
     }
 
-    async fn create(&self, api: OpenPasswdApi, name: &str) {
+    async fn create(&self, api: OpenPasswdApi, name: &str, rotation_days: Option<i32>) {
         api.register_group(AccountGroupRegister {
             name: name.to_owned(),
+            rotation_days,
         })
         .await
         .unwrap();
     }
+
+    async fn delete(&self, api: OpenPasswdApi, name: &str) {
+        let list = api.list_groups().await.unwrap();
+        if let Some(group) = list.items.iter().find(|g| g.name.as_str() == name) {
+            api.delete_group(group.id).await.unwrap();
+            println!("Group {} and its accounts moved to the trash", group.name);
+        } else {
+            println!("Group {name} not found");
+        }
+    }
 }
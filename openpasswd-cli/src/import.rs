@@ -0,0 +1,139 @@
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use clap::Args;
+use model::{
+    export::EncryptedVaultExport,
+    import::{ImportFormat, ImportIssue, ImportRequest},
+};
+
+use crate::{
+    api::OpenPasswdApi,
+    cryptography::{decrypt_export, ExportCryptoError},
+    keepass::read_kdbx,
+    profile::Profile,
+};
+
+/// KeePass databases are opened by the CLI and sent as an `openpasswd`
+/// export, the server never sees the database password.
+const KDBX_FORMAT: &str = "kdbx";
+
+pub const FORMATS: [&str; 7] = [
+    "bitwarden",
+    "keepass_csv",
+    "keepass_xml",
+    "1password",
+    "lastpass",
+    "openpasswd",
+    KDBX_FORMAT,
+];
+
+/// Import the accounts of another password manager's export
+#[derive(Debug, Args)]
+pub struct Import {
+    #[clap(possible_values = FORMATS)]
+    format: String,
+    file: PathBuf,
+    /// Only report what would be imported
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl Import {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let read = if self.format == KDBX_FORMAT {
+            self.read_kdbx()
+        } else {
+            self.read_export()
+        };
+        let (format, data, mut unsupported) = match read {
+            Some(read) => read,
+            None => return,
+        };
+
+        let api = OpenPasswdApi::new(profile);
+        let request = ImportRequest {
+            format,
+            data,
+            dry_run: self.dry_run,
+        };
+        let report = api.import(&request).await.unwrap();
+
+        if report.dry_run {
+            println!("{} accounts would be imported", report.imported);
+        } else {
+            println!("{} accounts imported", report.imported);
+        }
+        for group in report.groups_created.iter() {
+            println!("- new group {group}");
+        }
+        print_issues("Duplicates, left out:", &report.duplicates);
+        print_issues("Skipped:", &report.skipped);
+        unsupported.extend(report.unsupported);
+        print_issues("Dropped unsupported data:", &unsupported);
+    }
+
+    fn read_export(&self) -> Option<(ImportFormat, String, Vec<ImportIssue>)> {
+        let data = match std::fs::read_to_string(&self.file) {
+            Ok(data) => data,
+            Err(e) => {
+                println!("Can't read {}: {e}", self.file.display());
+                return None;
+            }
+        };
+
+        let format: ImportFormat = self.format.parse().unwrap();
+        // Encrypted exports are opened here, the server only sees the vault
+        // in clear over TLS, like any other format.
+        let data = match serde_json::from_str::<EncryptedVaultExport>(&data) {
+            Ok(encrypted) if format == ImportFormat::OpenPasswd => {
+                let passphrase = rpassword::prompt_password("Export passphrase: ").unwrap();
+                match decrypt_export(&encrypted, &passphrase) {
+                    Ok(data) => data,
+                    Err(ExportCryptoError::DecryptionFailed) => {
+                        println!("Wrong passphrase or damaged file");
+                        return None;
+                    }
+                    Err(e) => {
+                        println!("Can't read {}: {e:?}", self.file.display());
+                        return None;
+                    }
+                }
+            }
+            _ => data,
+        };
+        Some((format, data, Vec::new()))
+    }
+
+    fn read_kdbx(&self) -> Option<(ImportFormat, String, Vec<ImportIssue>)> {
+        let data = match std::fs::read(&self.file) {
+            Ok(data) => data,
+            Err(e) => {
+                println!("Can't read {}: {e}", self.file.display());
+                return None;
+            }
+        };
+
+        let password = rpassword::prompt_password("Database password: ").unwrap();
+        match read_kdbx(&data, &password) {
+            Ok((export, unsupported)) => Some((
+                ImportFormat::OpenPasswd,
+                serde_json::to_string(&export).unwrap(),
+                unsupported,
+            )),
+            Err(e) => {
+                println!("Can't open {}: {e}", self.file.display());
+                None
+            }
+        }
+    }
+}
+
+fn print_issues(title: &str, issues: &[ImportIssue]) {
+    if issues.is_empty() {
+        return;
+    }
+    println!("{title}");
+    for issue in issues {
+        println!("- {}: {}", issue.item, issue.detail);
+    }
+}
@@ -0,0 +1,335 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use keepass::{
+    config::{InnerCipherConfig, KdfConfig, OuterCipherConfig},
+    db::{
+        fields, DatabaseOpenError, DatabaseSaveError, Entry, EntryRef, GroupId, GroupRef, History,
+    },
+    Database, DatabaseKey,
+};
+use model::{
+    export::{ExportedAccount, ExportedGroup, ExportedPassword, VaultExport, EXPORT_VERSION},
+    import::ImportIssue,
+};
+
+pub const CIPHERS: [&str; 2] = ["aes", "chacha20"];
+
+/// KeePass has no notion of levels, favorites and second factors, they are
+/// kept in custom fields so a database written here imports back unchanged.
+const LEVEL_FIELD: &str = "OpenPasswd Level";
+const FAVORITE_FIELD: &str = "OpenPasswd Favorite";
+const TWO_FACTOR_FIELD: &str = "OpenPasswd 2FA";
+
+/// Entries at the root of a database have no group in OpenPasswd.
+const DEFAULT_GROUP: &str = "Imported";
+const DEFAULT_LEVEL: i16 = 1;
+
+const KDF_MEMORY: u64 = 64 * 1024 * 1024;
+const KDF_ITERATIONS: u64 = 10;
+const KDF_PARALLELISM: u32 = 4;
+
+/// KDBX 4 database with one KeePass group per OpenPasswd group, keyed with
+/// Argon2id.
+pub fn write_kdbx(
+    export: &VaultExport,
+    cipher: &str,
+    password: &str,
+) -> Result<Vec<u8>, DatabaseSaveError> {
+    let kdf_config = KdfConfig::Argon2id {
+        iterations: KDF_ITERATIONS,
+        memory: KDF_MEMORY,
+        parallelism: KDF_PARALLELISM,
+        version: keepass_argon2::Version::Version13,
+    };
+    write_kdbx_with_kdf(export, cipher, password, kdf_config)
+}
+
+fn write_kdbx_with_kdf(
+    export: &VaultExport,
+    cipher: &str,
+    password: &str,
+    kdf_config: KdfConfig,
+) -> Result<Vec<u8>, DatabaseSaveError> {
+    let mut database = Database::new();
+    database.config.outer_cipher_config = match cipher {
+        "chacha20" => OuterCipherConfig::ChaCha20,
+        _ => OuterCipherConfig::AES256,
+    };
+    database.config.inner_cipher_config = InnerCipherConfig::ChaCha20;
+    database.config.kdf_config = kdf_config;
+    database.meta.database_name = Some(String::from("OpenPasswd"));
+
+    let mut root = database.root_mut();
+    for group in export.groups.iter() {
+        let mut keepass_group = root.add_group();
+        keepass_group.name = group.name.to_owned();
+
+        for account in group.accounts.iter() {
+            let (current, past) = match account.passwords.split_last() {
+                Some(passwords) => passwords,
+                None => continue,
+            };
+
+            let mut entry = keepass_group.add_entry();
+            entry.set_unprotected(fields::TITLE, &account.name);
+            if let Some(url) = &account.url {
+                entry.set_unprotected(fields::URL, url);
+            }
+            entry.set_unprotected(LEVEL_FIELD, account.level.to_string());
+            if account.favorite {
+                entry.set_unprotected(FAVORITE_FIELD, "true");
+            }
+            if account.two_factor {
+                entry.set_unprotected(TWO_FACTOR_FIELD, "true");
+            }
+            entry.tags = account.tags.clone();
+            entry.times.creation = account
+                .passwords
+                .first()
+                .and_then(|password| parse_date(&password.created_date));
+
+            // History::add_entry inserts at the front, newest past version
+            // first leaves them oldest first like KeePass does.
+            let mut history = History::default();
+            for password in past.iter().rev() {
+                set_credentials(&mut entry, password);
+                history.add_entry((*entry).clone());
+            }
+            set_credentials(&mut entry, current);
+            entry.history = Some(history);
+        }
+    }
+
+    let mut data = Vec::new();
+    database.save(&mut data, DatabaseKey::new().with_password(password))?;
+    Ok(data)
+}
+
+fn set_credentials(entry: &mut Entry, password: &ExportedPassword) {
+    entry.set_unprotected(fields::USERNAME, &password.username);
+    entry.set_protected(fields::PASSWORD, &password.password);
+    entry.times.last_modification = parse_date(&password.created_date);
+}
+
+/// Reads a KDBX database as an OpenPasswd export. Nested groups are
+/// flattened into `Parent/Child` names, the recycle bin is left out and
+/// what an account can't hold is reported.
+pub fn read_kdbx(
+    data: &[u8],
+    password: &str,
+) -> Result<(VaultExport, Vec<ImportIssue>), DatabaseOpenError> {
+    let database = Database::parse(data, DatabaseKey::new().with_password(password))?;
+    let mut export = VaultExport {
+        version: EXPORT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        groups: Vec::new(),
+    };
+    let mut unsupported = Vec::new();
+
+    let root = database.root();
+    let accounts: Vec<ExportedAccount> = root
+        .entries()
+        .map(|entry| read_entry(&entry, &mut unsupported))
+        .collect();
+    if !accounts.is_empty() {
+        export.groups.push(ExportedGroup {
+            name: String::from(DEFAULT_GROUP),
+            accounts,
+        });
+    }
+    walk(
+        &root,
+        None,
+        database.meta.recyclebin_uuid.map(GroupId::from),
+        &mut export.groups,
+        &mut unsupported,
+    );
+
+    Ok((export, unsupported))
+}
+
+fn walk(
+    parent: &GroupRef,
+    path: Option<&str>,
+    recycle_bin: Option<GroupId>,
+    groups: &mut Vec<ExportedGroup>,
+    unsupported: &mut Vec<ImportIssue>,
+) {
+    for group in parent.groups() {
+        if Some(group.id()) == recycle_bin {
+            continue;
+        }
+        let name = match path {
+            Some(path) => format!("{path}/{}", group.name),
+            None => group.name.to_owned(),
+        };
+        groups.push(ExportedGroup {
+            name: name.to_owned(),
+            accounts: group
+                .entries()
+                .map(|entry| read_entry(&entry, unsupported))
+                .collect(),
+        });
+        walk(&group, Some(&name), recycle_bin, groups, unsupported);
+    }
+}
+
+fn read_entry(entry: &EntryRef, unsupported: &mut Vec<ImportIssue>) -> ExportedAccount {
+    let name = entry.get_title().unwrap_or_default().to_owned();
+
+    let mut history: Vec<&Entry> = entry
+        .history
+        .as_ref()
+        .map(|history| history.get_entries().iter().collect())
+        .unwrap_or_default();
+    history.sort_by_key(|past| past.times.last_modification);
+    let passwords = history
+        .into_iter()
+        .chain(std::iter::once(&**entry))
+        .map(|version| ExportedPassword {
+            username: version.get_username().unwrap_or_default().to_owned(),
+            password: version.get_password().unwrap_or_default().to_owned(),
+            created_date: to_rfc3339(version.times.last_modification.or(version.times.creation)),
+        })
+        .collect();
+
+    let mut dropped: Vec<String> = entry
+        .fields
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .filter_map(|(key, _)| match key.as_str() {
+            fields::TITLE | fields::USERNAME | fields::PASSWORD | fields::URL => None,
+            LEVEL_FIELD | FAVORITE_FIELD | TWO_FACTOR_FIELD => None,
+            fields::NOTES => Some(String::from("notes")),
+            key => Some(format!("custom field {key}")),
+        })
+        .collect();
+    dropped.sort();
+    if entry.attachments().next().is_some() {
+        dropped.push(String::from("attachments"));
+    }
+    unsupported.extend(dropped.into_iter().map(|detail| ImportIssue {
+        item: name.to_owned(),
+        detail,
+    }));
+
+    ExportedAccount {
+        url: entry.get_url().map(str::to_owned),
+        level: entry
+            .get(LEVEL_FIELD)
+            .and_then(|level| level.parse().ok())
+            .unwrap_or(DEFAULT_LEVEL),
+        favorite: entry.get(FAVORITE_FIELD) == Some("true"),
+        two_factor: entry.get(TWO_FACTOR_FIELD) == Some("true"),
+        tags: entry.tags.clone(),
+        passwords,
+        name,
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|datetime| datetime.naive_utc())
+}
+
+fn to_rfc3339(datetime: Option<NaiveDateTime>) -> String {
+    datetime
+        .map(|datetime| datetime.and_utc())
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use keepass::config::KdfConfig;
+    use model::export::{
+        ExportedAccount, ExportedGroup, ExportedPassword, VaultExport, EXPORT_VERSION,
+    };
+
+    use super::{read_kdbx, write_kdbx_with_kdf, CIPHERS};
+
+    fn password(username: &str, password: &str, created_date: &str) -> ExportedPassword {
+        ExportedPassword {
+            username: username.to_owned(),
+            password: password.to_owned(),
+            created_date: created_date.to_owned(),
+        }
+    }
+
+    fn export() -> VaultExport {
+        VaultExport {
+            version: EXPORT_VERSION,
+            exported_at: String::from("2022-10-01T12:00:00+00:00"),
+            groups: vec![
+                ExportedGroup {
+                    name: String::from("Personal"),
+                    accounts: vec![
+                        ExportedAccount {
+                            name: String::from("Mail"),
+                            url: Some(String::from("https://mail.example.com")),
+                            level: 3,
+                            favorite: true,
+                            two_factor: true,
+                            tags: vec![String::from("email"), String::from("important")],
+                            passwords: vec![
+                                password("jdoe", "first", "2022-01-01T08:00:00+00:00"),
+                                password("jdoe", "second", "2022-03-01T09:30:00+00:00"),
+                                password("john.doe", "third", "2022-06-15T18:45:10+00:00"),
+                            ],
+                        },
+                        ExportedAccount {
+                            name: String::from("Forum"),
+                            url: None,
+                            level: 1,
+                            favorite: false,
+                            two_factor: false,
+                            tags: Vec::new(),
+                            passwords: vec![password("jd", "only", "2022-02-02T02:02:02+00:00")],
+                        },
+                    ],
+                },
+                ExportedGroup {
+                    name: String::from("Work/Infra"),
+                    accounts: vec![ExportedAccount {
+                        name: String::from("Console"),
+                        url: Some(String::from("https://console.example.com")),
+                        level: 5,
+                        favorite: false,
+                        two_factor: true,
+                        tags: vec![String::from("ops")],
+                        passwords: vec![
+                            password("admin", "old", "2021-12-31T23:59:59+00:00"),
+                            password("admin", "new", "2022-09-01T00:00:00+00:00"),
+                        ],
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn kdbx_round_trip() {
+        // Small parameters, the default ones take seconds in debug builds.
+        let kdf_config = KdfConfig::Argon2id {
+            iterations: 1,
+            memory: 64 * 1024,
+            parallelism: 1,
+            version: keepass_argon2::Version::Version13,
+        };
+        let export = export();
+
+        for cipher in CIPHERS {
+            let data =
+                write_kdbx_with_kdf(&export, cipher, "passphrase", kdf_config.clone()).unwrap();
+            let (read, unsupported) = read_kdbx(&data, "passphrase").unwrap();
+
+            assert!(unsupported.is_empty(), "{cipher}");
+            assert_eq!(
+                serde_json::to_value(&export.groups).unwrap(),
+                serde_json::to_value(&read.groups).unwrap(),
+                "{cipher}"
+            );
+            assert!(read_kdbx(&data, "wrong").is_err(), "{cipher}");
+        }
+    }
+}
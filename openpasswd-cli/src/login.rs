@@ -1,14 +1,14 @@
 use crate::api::OpenPasswdApi;
 use crate::profile::Profile;
 use clap::Args;
-use model::auth::{LoginRequest, RefreshTokenType};
+use model::auth::{LoginRequest, RefreshTokenType, UserRegister};
 use std::{
     cell::RefCell,
     io::{BufRead, Write},
     rc::Rc,
 };
 
-fn read_prompt_input(prompt: &str) -> std::io::Result<String> {
+pub fn read_prompt_input(prompt: &str) -> std::io::Result<String> {
     print!("{}", prompt);
     std::io::stdout().flush()?;
 
@@ -18,9 +18,11 @@ fn read_prompt_input(prompt: &str) -> std::io::Result<String> {
     Ok(value.trim().to_owned())
 }
 
+/// Log in, the email and device name are asked only the first time
 #[derive(Debug, Args)]
 #[clap(args_conflicts_with_subcommands = true)]
 pub struct Login {
+    /// Ask again for the email and device name
     #[clap(short, long)]
     clean: bool,
 }
@@ -51,7 +53,7 @@ impl Login {
         api.auth_token(LoginRequest {
             email: email.clone(),
             password,
-            device_name: Some("XXX".to_owned()),
+            device_name: Some(device_name.clone()),
             refresh_token: Some(RefreshTokenType::Token),
         })
         .await
@@ -59,8 +61,131 @@ impl Login {
 
         {
             let mut profile = profile.borrow_mut();
+            profile.remember_server();
             profile.set_email(email);
             profile.set_device_name(device_name);
         }
     }
 }
+
+/// Revoke the session on the server and forget its tokens
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct Logout {}
+
+impl Logout {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        if profile.borrow().access_token().is_none() {
+            return println!("Not logged in");
+        }
+
+        let api = OpenPasswdApi::new(profile.clone());
+        // The refresh token outlives the access token, an expired session is
+        // refreshed so both can be revoked.
+        let expired = profile.borrow().is_token_expired();
+        let active = if !expired {
+            true
+        } else if profile.borrow().refresh_token().is_some() {
+            match api.auth_refresh_token().await.unwrap() {
+                Some(access_token) => {
+                    profile
+                        .borrow_mut()
+                        .set_tokens(Some(access_token.access_token), access_token.refresh_token);
+                    true
+                }
+                None => false,
+            }
+        } else {
+            false
+        };
+
+        let revoked = !active || api.auth_logout().await.unwrap();
+        profile.borrow_mut().set_tokens(None, None);
+        if revoked {
+            println!("Logged out");
+        } else {
+            println!("Logged out, but the server did not revoke the session, it stays valid until it expires");
+        }
+    }
+}
+
+/// Create a user and log in with it
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct Register {
+    #[clap(short, long)]
+    name: Option<String>,
+    #[clap(short, long)]
+    email: Option<String>,
+}
+
+impl Register {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let api = OpenPasswdApi::new(profile.clone());
+        let name = match &self.name {
+            Some(name) => name.to_owned(),
+            None => read_prompt_input("Name: ").unwrap(),
+        };
+        let email = match &self.email {
+            Some(email) => email.to_owned(),
+            None => read_prompt_input("Email: ").unwrap(),
+        };
+        let password = rpassword::prompt_password("Password: ").unwrap();
+        let confirmation = rpassword::prompt_password("Confirm Password: ").unwrap();
+        if password != confirmation {
+            return println!("Passwords don't match");
+        }
+
+        let breached = api
+            .auth_register(UserRegister {
+                name,
+                email: email.clone(),
+                password: password.clone(),
+            })
+            .await
+            .unwrap();
+        println!("Registered {email}");
+        if let Some(count) = breached {
+            println!("Warning: this password appeared {count} times in data breaches");
+        }
+
+        let device_name = read_prompt_input("Device Name: ").unwrap();
+        api.auth_token(LoginRequest {
+            email: email.clone(),
+            password,
+            device_name: Some(device_name.clone()),
+            refresh_token: Some(RefreshTokenType::Token),
+        })
+        .await
+        .unwrap();
+
+        {
+            let mut profile = profile.borrow_mut();
+            profile.remember_server();
+            profile.set_email(email);
+            profile.set_device_name(device_name);
+        }
+    }
+}
+
+/// Show the user logged in
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct Whoami {}
+
+impl Whoami {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let api = OpenPasswdApi::new(profile.clone());
+        let user = api.auth_user().await.unwrap();
+
+        let profile = profile.borrow();
+        println!("{} <{}>", user.name, user.email);
+        println!("Profile: {} on {}", profile.name(), profile.server());
+        if let Some(device_name) = profile.device_name() {
+            println!("Device: {device_name}");
+        }
+        if let Some(last_login) = user.last_login {
+            println!("Last login: {last_login}");
+        }
+    }
+}
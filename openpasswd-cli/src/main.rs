@@ -1,51 +1,112 @@
 extern crate copypasta;
 
 use accounts::Accounts;
+use audit::Audit;
 use clap::{Parser, Subcommand};
+use export::Export;
 use generator::Generator;
 use groups::Groups;
-use login::Login;
+use import::Import;
+use login::{Login, Logout, Register, Whoami};
+use organizations::Organizations;
 use profile::Profile;
+use report::Report;
 use std::{cell::RefCell, rc::Rc};
+use trash::Trash;
+use webhooks::Webhooks;
 
 mod accounts;
 mod api;
+mod audit;
 mod clipboard;
+mod cryptography;
+mod export;
 mod generator;
 mod groups;
+mod import;
+mod keepass;
 mod login;
+mod organizations;
 mod profile;
+mod report;
+mod trash;
+mod webhooks;
 
 /// A fictional versioning CLI
 #[derive(Debug, Parser)]
 #[clap(name = "openpasswd-cli")]
 #[clap(about = "The official OpenPasswd CLI", long_about = None)]
 struct Cli {
+    /// Profile to use, each has its own server, user and session
+    #[clap(long, global = true)]
+    profile: Option<String>,
+
+    /// URL of the server, OPENPASSWD_SERVER otherwise
+    #[clap(long, global = true)]
+    server: Option<String>,
+
     #[clap(subcommand)]
     command: Commands,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
-    // Login(Login),
+    Login(Login),
+    Logout(Logout),
+    Register(Register),
+    Whoami(Whoami),
     Account(Accounts),
     Group(Groups),
+    Org(Organizations),
+    Trash(Trash),
+    Audit(Audit),
+    Webhook(Webhooks),
+    Import(Import),
+    Export(Export),
+    Report(Report),
     Generator(Generator),
 }
 
+impl Commands {
+    /// Whether the command needs a session, one is opened first if the
+    /// current one expired.
+    fn requires_login(&self) -> bool {
+        !matches!(
+            self,
+            Commands::Login(_)
+                | Commands::Logout(_)
+                | Commands::Register(_)
+                | Commands::Generator(_)
+        )
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let args = Cli::parse();
-    let profile = Rc::new(RefCell::new(Profile::new()));
+    let server = args
+        .server
+        .or_else(|| std::env::var("OPENPASSWD_SERVER").ok());
+    let profile = Rc::new(RefCell::new(Profile::new(args.profile, server)));
 
-    if profile.borrow().is_token_expired() {
+    if args.command.requires_login() && profile.borrow().is_token_expired() {
         Login::new().execute(profile.clone()).await
     }
 
     match args.command {
-        // Commands::Login(login) => login.execute(profile).await,
+        Commands::Login(login) => login.execute(profile).await,
+        Commands::Logout(logout) => logout.execute(profile).await,
+        Commands::Register(register) => register.execute(profile).await,
+        Commands::Whoami(whoami) => whoami.execute(profile).await,
         Commands::Account(account) => account.execute(profile).await,
         Commands::Group(group) => group.execute(profile).await,
+        Commands::Org(organization) => organization.execute(profile).await,
+        Commands::Trash(trash) => trash.execute(profile).await,
+        Commands::Audit(audit) => audit.execute(profile).await,
+        Commands::Webhook(webhook) => webhook.execute(profile).await,
+        Commands::Import(import) => import.execute(profile).await,
+        Commands::Export(export) => export.execute(profile).await,
+        Commands::Report(report) => report.execute(profile).await,
         Commands::Generator(generator) => generator.execute(),
     }
 }
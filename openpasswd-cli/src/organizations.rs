@@ -0,0 +1,304 @@
+use std::{cell::RefCell, rc::Rc};
+
+use clap::{Args, Subcommand};
+use model::{
+    accounts::AccountGroupRegister,
+    organizations::{
+        CollectionMemberRegister, CollectionPermission, OrganizationInvitationAccept,
+        OrganizationInvite, OrganizationMemberUpdate, OrganizationRegister, OrganizationRole,
+    },
+};
+
+use crate::{api::OpenPasswdApi, profile::Profile};
+
+#[derive(Debug, Subcommand)]
+enum OrganizationsCommands {
+    List,
+    Create {
+        name: String,
+    },
+    Members {
+        name: String,
+    },
+    Invite {
+        name: String,
+        email: String,
+        #[clap(long, possible_values = ROLES)]
+        role: Option<String>,
+    },
+    Accept {
+        token: String,
+    },
+    Remove {
+        name: String,
+        email: String,
+    },
+    Role {
+        name: String,
+        email: String,
+        #[clap(possible_values = ROLES)]
+        role: String,
+    },
+    Collections {
+        name: String,
+    },
+    CreateCollection {
+        name: String,
+        collection: String,
+    },
+    Assign {
+        #[clap(flatten)]
+        member: CollectionMember,
+        #[clap(
+            long,
+            default_value = "view",
+            possible_values = ["view_without_password", "view", "edit", "manage"]
+        )]
+        permission: String,
+    },
+    Unassign(CollectionMember),
+}
+
+const ROLES: [&str; 5] = ["owner", "admin", "manager", "member", "read_only"];
+
+#[derive(Debug, Args)]
+pub struct CollectionMember {
+    name: String,
+    collection: String,
+    email: String,
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct Organizations {
+    #[clap(subcommand)]
+    command: OrganizationsCommands,
+}
+
+impl Organizations {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let api = OpenPasswdApi::new(profile);
+
+        match &self.command {
+            OrganizationsCommands::List => self.list(api).await,
+            OrganizationsCommands::Create { name } => self.create(api, name).await,
+            OrganizationsCommands::Members { name } => self.members(api, name).await,
+            OrganizationsCommands::Invite { name, email, role } => {
+                self.invite(api, name, email, role.as_deref()).await
+            }
+            OrganizationsCommands::Accept { token } => self.accept(api, token).await,
+            OrganizationsCommands::Remove { name, email } => self.remove(api, name, email).await,
+            OrganizationsCommands::Role { name, email, role } => {
+                self.role(api, name, email, role).await
+            }
+            OrganizationsCommands::Collections { name } => self.collections(api, name).await,
+            OrganizationsCommands::CreateCollection { name, collection } => {
+                self.create_collection(api, name, collection).await
+            }
+            OrganizationsCommands::Assign { member, permission } => {
+                self.assign(api, member, permission).await
+            }
+            OrganizationsCommands::Unassign(member) => self.unassign(api, member).await,
+        }
+    }
+
+    async fn list(&self, api: OpenPasswdApi) {
+        let list = api.list_organizations().await.unwrap();
+
+        for item in list.items {
+            println!("- {} ({})", item.name, item.role.as_str());
+        }
+    }
+
+    async fn create(&self, api: OpenPasswdApi, name: &str) {
+        api.register_organization(OrganizationRegister {
+            name: name.to_owned(),
+        })
+        .await
+        .unwrap();
+    }
+
+    async fn members(&self, api: OpenPasswdApi, name: &str) {
+        let id = match Self::find_organization_id(&api, name).await {
+            Some(id) => id,
+            None => return println!("Organization {name} not found"),
+        };
+
+        let list = api.list_organization_members(id).await.unwrap();
+        for item in list.items {
+            println!("- {} <{}> ({})", item.name, item.email, item.role.as_str());
+        }
+    }
+
+    async fn invite(&self, api: OpenPasswdApi, name: &str, email: &str, role: Option<&str>) {
+        let id = match Self::find_organization_id(&api, name).await {
+            Some(id) => id,
+            None => return println!("Organization {name} not found"),
+        };
+
+        api.invite_organization_member(
+            id,
+            OrganizationInvite {
+                email: email.to_owned(),
+                role: role.map(|role| role.parse::<OrganizationRole>().unwrap()),
+            },
+        )
+        .await
+        .unwrap();
+        println!("Invitation sent to {email}");
+    }
+
+    async fn accept(&self, api: OpenPasswdApi, token: &str) {
+        let organization = api
+            .accept_organization_invitation(OrganizationInvitationAccept {
+                token: token.to_owned(),
+            })
+            .await
+            .unwrap();
+        println!("Joined {}", organization.name);
+    }
+
+    async fn remove(&self, api: OpenPasswdApi, name: &str, email: &str) {
+        let id = match Self::find_organization_id(&api, name).await {
+            Some(id) => id,
+            None => return println!("Organization {name} not found"),
+        };
+
+        match Self::find_member_id(&api, id, email).await {
+            Some(user_id) => {
+                api.remove_organization_member(id, user_id).await.unwrap();
+                println!("{email} removed from {name}");
+            }
+            None => println!("{email} is not a member of {name}"),
+        }
+    }
+
+    async fn role(&self, api: OpenPasswdApi, name: &str, email: &str, role: &str) {
+        let id = match Self::find_organization_id(&api, name).await {
+            Some(id) => id,
+            None => return println!("Organization {name} not found"),
+        };
+
+        match Self::find_member_id(&api, id, email).await {
+            Some(user_id) => {
+                let member = api
+                    .update_organization_member(
+                        id,
+                        user_id,
+                        OrganizationMemberUpdate {
+                            role: role.parse().unwrap(),
+                        },
+                    )
+                    .await
+                    .unwrap();
+                println!("{email} is now {} of {name}", member.role.as_str());
+            }
+            None => println!("{email} is not a member of {name}"),
+        }
+    }
+
+    async fn collections(&self, api: OpenPasswdApi, name: &str) {
+        let id = match Self::find_organization_id(&api, name).await {
+            Some(id) => id,
+            None => return println!("Organization {name} not found"),
+        };
+
+        let list = api.list_collections(id).await.unwrap();
+        for item in list.items {
+            println!("- {}", item.name);
+        }
+    }
+
+    async fn create_collection(&self, api: OpenPasswdApi, name: &str, collection: &str) {
+        let id = match Self::find_organization_id(&api, name).await {
+            Some(id) => id,
+            None => return println!("Organization {name} not found"),
+        };
+
+        api.register_collection(
+            id,
+            AccountGroupRegister {
+                name: collection.to_owned(),
+                rotation_days: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn assign(&self, api: OpenPasswdApi, member: &CollectionMember, permission: &str) {
+        if let Some((id, group_id, user_id)) = Self::find_collection_member(&api, member).await {
+            let permission = permission.parse::<CollectionPermission>().unwrap();
+            api.assign_collection_member(
+                id,
+                group_id,
+                CollectionMemberRegister {
+                    user_id,
+                    permission,
+                },
+            )
+            .await
+            .unwrap();
+            println!("{} assigned to {}", member.email, member.collection);
+        }
+    }
+
+    async fn unassign(&self, api: OpenPasswdApi, member: &CollectionMember) {
+        if let Some((id, group_id, user_id)) = Self::find_collection_member(&api, member).await {
+            api.unassign_collection_member(id, group_id, user_id)
+                .await
+                .unwrap();
+            println!("{} unassigned from {}", member.email, member.collection);
+        }
+    }
+
+    pub async fn find_organization_id(api: &OpenPasswdApi, name: &str) -> Option<i32> {
+        let list = api.list_organizations().await.unwrap();
+        list.items
+            .iter()
+            .find(|o| o.name.as_str() == name)
+            .map(|o| o.id)
+    }
+
+    async fn find_member_id(api: &OpenPasswdApi, id: i32, email: &str) -> Option<i32> {
+        let list = api.list_organization_members(id).await.unwrap();
+        list.items
+            .iter()
+            .find(|m| m.email.as_str() == email)
+            .map(|m| m.user_id)
+    }
+
+    async fn find_collection_member(
+        api: &OpenPasswdApi,
+        member: &CollectionMember,
+    ) -> Option<(i32, i32, i32)> {
+        let id = match Self::find_organization_id(api, &member.name).await {
+            Some(id) => id,
+            None => {
+                println!("Organization {} not found", member.name);
+                return None;
+            }
+        };
+
+        let collections = api.list_collections(id).await.unwrap();
+        let group_id = match collections
+            .items
+            .iter()
+            .find(|c| c.name.as_str() == member.collection)
+        {
+            Some(collection) => collection.id,
+            None => {
+                println!("Collection {} not found", member.collection);
+                return None;
+            }
+        };
+
+        match Self::find_member_id(api, id, &member.email).await {
+            Some(user_id) => Some((id, group_id, user_id)),
+            None => {
+                println!("{} is not a member of {}", member.email, member.name);
+                None
+            }
+        }
+    }
+}
@@ -1,97 +1,188 @@
-use std::fs::{self, create_dir};
+use std::{
+    collections::BTreeMap,
+    fs::{self, create_dir_all},
+    path::PathBuf,
+};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
-pub struct Profile {
+const DEFAULT_SERVER: &str = "https://api.openpasswd.com";
+const DEFAULT_PROFILE: &str = "default";
+
+/// `config.json` next to the profiles, written by hand:
+/// `{"server": "https://openpasswd.example.com", "profile": "work"}`.
+#[derive(Serialize, Deserialize, Default)]
+struct Config {
+    /// Server of the profiles that don't have their own.
+    #[serde(default)]
+    server: Option<String>,
+    /// Profile used without `--profile`.
+    #[serde(default)]
+    profile: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+struct ProfileData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    server: Option<String>,
+    #[serde(default)]
     email: Option<String>,
+    #[serde(default)]
     device_name: Option<String>,
+    #[serde(default)]
     access_token: Option<String>,
+    #[serde(default)]
     refresh_token: Option<String>,
 }
 
+/// The `.profile` file, profiles by name.
+#[derive(Serialize, Deserialize, Default)]
+struct Profiles {
+    profiles: BTreeMap<String, ProfileData>,
+}
+
+impl Profiles {
+    fn load() -> Profiles {
+        let file = match config_file(".profile") {
+            Some(file) if file.exists() => file,
+            _ => return Profiles::default(),
+        };
+        let json = fs::read_to_string(file).unwrap();
+        match serde_json::from_str(&json) {
+            Ok(profiles) => profiles,
+            // Before named profiles the file held a single one.
+            Err(_) => Profiles {
+                profiles: BTreeMap::from([(
+                    DEFAULT_PROFILE.to_owned(),
+                    serde_json::from_str(&json).unwrap(),
+                )]),
+            },
+        }
+    }
+}
+
+fn config_file(name: &str) -> Option<PathBuf> {
+    directories::ProjectDirs::from("com", "openpasswd", "openpasswd-cli")
+        .map(|project_dirs| project_dirs.config_dir().join(name))
+}
+
+pub struct Profile {
+    name: String,
+    data: ProfileData,
+    /// `--server` or `OPENPASSWD_SERVER`, over the server of the profile.
+    server_override: Option<String>,
+    config_server: Option<String>,
+}
+
 impl Profile {
-    pub fn new() -> Profile {
-        if let Some(project_dirs) =
-            directories::ProjectDirs::from("com", "openpasswd", "openpasswd-cli")
-        {
-            let file = project_dirs.config_dir().join(".profile");
-            if file.exists() {
-                let json = fs::read_to_string(file).unwrap();
-                return serde_json::from_str(&json).unwrap();
+    /// The profile named `name`, or the one of the config file, or the
+    /// default one.
+    pub fn new(name: Option<String>, server_override: Option<String>) -> Profile {
+        let config: Config = match config_file("config.json") {
+            Some(file) if file.exists() => {
+                serde_json::from_str(&fs::read_to_string(file).unwrap()).unwrap()
             }
+            _ => Config::default(),
+        };
+        let name = name
+            .or(config.profile)
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_owned());
+        let mut data = Profiles::load().profiles.remove(&name).unwrap_or_default();
+        // The tokens of another server are never sent, a new session is
+        // opened on this one instead.
+        let saved_server = data
+            .server
+            .as_deref()
+            .or(config.server.as_deref())
+            .unwrap_or(DEFAULT_SERVER)
+            .trim_end_matches('/');
+        let other_server = matches!(
+            &server_override,
+            Some(server) if server.trim_end_matches('/') != saved_server
+        );
+        if other_server {
+            data.access_token = None;
+            data.refresh_token = None;
         }
 
         Profile {
-            email: None,
-            device_name: None,
-            access_token: None,
-            refresh_token: None,
+            name,
+            data,
+            server_override,
+            config_server: config.server,
         }
     }
 
+    /// Only this profile is written, the others are kept as they are on disk.
     fn save(&self) {
-        let json = serde_json::to_string(self).unwrap();
-        if let Some(project_dirs) =
-            directories::ProjectDirs::from("com", "openpasswd", "openpasswd-cli")
-        {
-            let dir = project_dirs.config_dir();
-            if !dir.exists() {
-                create_dir(dir).unwrap();
+        let mut profiles = Profiles::load();
+        profiles
+            .profiles
+            .insert(self.name.to_owned(), self.data.clone());
+
+        let json = serde_json::to_string(&profiles).unwrap();
+        if let Some(file) = config_file(".profile") {
+            if let Some(dir) = file.parent() {
+                create_dir_all(dir).unwrap();
             }
-
-            let file = dir.join(".profile");
             fs::write(file, json.as_bytes()).unwrap();
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// URL of the server, without a trailing slash.
+    pub fn server(&self) -> &str {
+        self.server_override
+            .as_deref()
+            .or(self.data.server.as_deref())
+            .or(self.config_server.as_deref())
+            .unwrap_or(DEFAULT_SERVER)
+            .trim_end_matches('/')
+    }
+
+    /// Keeps the server in use as the profile's own, the tokens belong to it.
+    pub fn remember_server(&mut self) {
+        self.data.server = Some(self.server().to_owned());
+        self.save();
+    }
+
     pub fn set_email(&mut self, email: String) {
-        self.email = Some(email);
+        self.data.email = Some(email);
         self.save();
     }
 
     pub fn email(&self) -> Option<&str> {
-        self.email.as_deref()
+        self.data.email.as_deref()
     }
 
     pub fn set_device_name(&mut self, device_name: String) {
-        self.device_name = Some(device_name);
+        self.data.device_name = Some(device_name);
         self.save();
     }
 
     pub fn device_name(&self) -> Option<&str> {
-        self.device_name.as_deref()
+        self.data.device_name.as_deref()
     }
 
     pub fn set_tokens(&mut self, access_token: Option<String>, refresh_token: Option<String>) {
-        self.access_token = access_token;
-        self.refresh_token = refresh_token;
-        self.save();
-    }
-
-    #[allow(dead_code)]
-    pub fn set_access_token(&mut self, access_token: String) {
-        self.access_token = Some(access_token);
+        self.data.access_token = access_token;
+        self.data.refresh_token = refresh_token;
         self.save();
     }
 
     pub fn access_token(&self) -> Option<&str> {
-        self.access_token.as_deref()
-    }
-
-    #[allow(dead_code)]
-    pub fn set_refresh_token(&mut self, refresh_token: String) {
-        self.refresh_token = Some(refresh_token);
-        self.save();
+        self.data.access_token.as_deref()
     }
 
-    #[allow(dead_code)]
     pub fn refresh_token(&self) -> Option<&str> {
-        self.refresh_token.as_deref()
+        self.data.refresh_token.as_deref()
     }
 
     pub fn is_token_expired(&self) -> bool {
-        if let Some(access_token) = self.access_token.as_ref() {
+        if let Some(access_token) = self.data.access_token.as_ref() {
             let mut validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS512);
             validation.insecure_disable_signature_validation();
             match jsonwebtoken::decode::<serde_json::Value>(
@@ -99,10 +190,7 @@ impl Profile {
                 &jsonwebtoken::DecodingKey::from_secret(&[]),
                 &validation,
             ) {
-                Err(e) => match e.kind() {
-                    jsonwebtoken::errors::ErrorKind::ExpiredSignature => true,
-                    _ => false,
-                },
+                Err(e) => matches!(e.kind(), jsonwebtoken::errors::ErrorKind::ExpiredSignature),
                 _ => false,
             }
         } else {
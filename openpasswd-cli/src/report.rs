@@ -0,0 +1,80 @@
+use std::{cell::RefCell, rc::Rc};
+
+use clap::Args;
+
+use crate::{
+    api::{HealthQuery, OpenPasswdApi},
+    profile::Profile,
+};
+
+const SCORES: [&str; 5] = ["very weak", "weak", "fair", "good", "strong"];
+
+/// Report reused, weak, old and breached passwords and accounts without 2FA
+#[derive(Debug, Args)]
+pub struct Report {
+    /// Age in days after which a password is old, the server's default otherwise
+    #[clap(long)]
+    max_age_days: Option<i64>,
+}
+
+impl Report {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let api = OpenPasswdApi::new(profile);
+        let report = api
+            .health_report(&HealthQuery {
+                max_age_days: self.max_age_days,
+            })
+            .await
+            .unwrap();
+
+        println!("{} accounts checked", report.accounts_checked);
+
+        if !report.reused.is_empty() {
+            println!("Reused passwords:");
+            for reused in report.reused.iter() {
+                let names: Vec<&str> = reused
+                    .accounts
+                    .iter()
+                    .map(|account| account.name.as_str())
+                    .collect();
+                println!("- {}", names.join(", "));
+            }
+        }
+
+        if !report.weak.is_empty() {
+            println!("Weak passwords:");
+            for weak in report.weak.iter() {
+                println!(
+                    "- {}: {} (~{} bits)",
+                    weak.account.name,
+                    SCORES[usize::from(weak.score).min(SCORES.len() - 1)],
+                    weak.entropy_bits
+                );
+            }
+        }
+
+        if !report.old.is_empty() {
+            println!("Older than {} days:", report.max_age_days);
+            for old in report.old.iter() {
+                println!("- {}: {} days", old.account.name, old.age_days);
+            }
+        }
+
+        if !report.no_two_factor.is_empty() {
+            println!("Without 2FA:");
+            for account in report.no_two_factor.iter() {
+                println!("- {}", account.name);
+            }
+        }
+
+        if !report.breached.is_empty() {
+            println!("Breached passwords:");
+            for breached in report.breached.iter() {
+                println!(
+                    "- {}: seen {} times in data breaches",
+                    breached.account.name, breached.count
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::{cell::RefCell, rc::Rc};
+
+use clap::{ArgEnum, Args, Subcommand};
+
+use crate::{api::OpenPasswdApi, profile::Profile};
+
+#[derive(Debug, Clone, ArgEnum)]
+enum TrashKind {
+    Account,
+    Group,
+}
+
+impl TrashKind {
+    fn path(&self) -> &'static str {
+        match self {
+            TrashKind::Account => "accounts",
+            TrashKind::Group => "groups",
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum TrashCommands {
+    List,
+    Restore {
+        #[clap(arg_enum)]
+        kind: TrashKind,
+        id: i32,
+    },
+    Purge {
+        #[clap(arg_enum)]
+        kind: TrashKind,
+        id: i32,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct Trash {
+    #[clap(subcommand)]
+    command: TrashCommands,
+}
+
+impl Trash {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let api = OpenPasswdApi::new(profile);
+
+        match &self.command {
+            TrashCommands::List => self.list(api).await,
+            TrashCommands::Restore { kind, id } => {
+                api.restore_from_trash(kind.path(), *id).await.unwrap()
+            }
+            TrashCommands::Purge { kind, id } => {
+                api.purge_from_trash(kind.path(), *id).await.unwrap()
+            }
+        }
+    }
+
+    async fn list(&self, api: OpenPasswdApi) {
+        let trash = api.list_trash().await.unwrap();
+
+        for group in trash.groups {
+            println!(
+                "- group {} ({}), purged at {}",
+                group.name, group.id, group.purge_at
+            );
+        }
+        for account in trash.accounts {
+            println!(
+                "- account {} ({}), purged at {}",
+                account.name, account.id, account.purge_at
+            );
+        }
+    }
+}
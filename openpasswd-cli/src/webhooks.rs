@@ -0,0 +1,155 @@
+use std::{cell::RefCell, rc::Rc};
+
+use clap::{Args, Subcommand};
+use model::webhooks::{WebhookRegister, WebhookUpdate};
+
+use crate::{
+    api::OpenPasswdApi, audit::event_types, organizations::Organizations, profile::Profile,
+};
+
+#[derive(Debug, Subcommand)]
+enum WebhooksCommands {
+    List,
+    Add {
+        url: String,
+        /// Only deliver these events, every event when omitted
+        #[clap(long = "event", possible_values = event_types())]
+        events: Vec<String>,
+        /// Deliver the events on the collections of this organization
+        #[clap(long)]
+        org: Option<String>,
+    },
+    Enable {
+        id: i32,
+    },
+    Disable {
+        id: i32,
+    },
+    Remove {
+        id: i32,
+    },
+    Deliveries {
+        id: i32,
+    },
+    Test {
+        id: i32,
+    },
+}
+
+#[derive(Debug, Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct Webhooks {
+    #[clap(subcommand)]
+    command: WebhooksCommands,
+}
+
+impl Webhooks {
+    pub async fn execute(&self, profile: Rc<RefCell<Profile>>) {
+        let api = OpenPasswdApi::new(profile);
+
+        match &self.command {
+            WebhooksCommands::List => self.list(api).await,
+            WebhooksCommands::Add { url, events, org } => {
+                self.add(api, url, events, org.as_deref()).await
+            }
+            WebhooksCommands::Enable { id } => self.set_enabled(api, *id, true).await,
+            WebhooksCommands::Disable { id } => self.set_enabled(api, *id, false).await,
+            WebhooksCommands::Remove { id } => api.delete_webhook(*id).await.unwrap(),
+            WebhooksCommands::Deliveries { id } => self.deliveries(api, *id).await,
+            WebhooksCommands::Test { id } => self.test(api, *id).await,
+        }
+    }
+
+    async fn list(&self, api: OpenPasswdApi) {
+        let list = api.list_webhooks().await.unwrap();
+
+        for item in list.items {
+            let events = if item.event_types.is_empty() {
+                String::from("all events")
+            } else {
+                item.event_types
+                    .iter()
+                    .map(|event_type| event_type.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let state = if item.enabled { "" } else { ", disabled" };
+            println!("- {} {} ({events}{state})", item.id, item.url);
+        }
+    }
+
+    async fn add(&self, api: OpenPasswdApi, url: &str, events: &[String], org: Option<&str>) {
+        let organization_id = match org {
+            Some(name) => match Organizations::find_organization_id(&api, name).await {
+                Some(id) => Some(id),
+                None => return println!("Organization {name} not found"),
+            },
+            None => None,
+        };
+
+        let webhook = api
+            .register_webhook(WebhookRegister {
+                url: url.to_owned(),
+                event_types: events
+                    .iter()
+                    .filter_map(|event| event.parse().ok())
+                    .collect(),
+                organization_id,
+            })
+            .await
+            .unwrap();
+
+        println!("Webhook {} created", webhook.id);
+        if let Some(secret) = webhook.secret {
+            println!("Signing secret (shown only once): {secret}");
+        }
+    }
+
+    async fn set_enabled(&self, api: OpenPasswdApi, id: i32, enabled: bool) {
+        api.update_webhook(
+            id,
+            WebhookUpdate {
+                url: None,
+                event_types: None,
+                enabled: Some(enabled),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn deliveries(&self, api: OpenPasswdApi, id: i32) {
+        let list = api.list_webhook_deliveries(id).await.unwrap();
+
+        for item in list.items {
+            let mut line = format!(
+                "{} {} {} {} attempt(s)",
+                item.created_date,
+                item.event_type,
+                item.status.as_str(),
+                item.attempts
+            );
+            if let Some(response_status) = item.response_status {
+                line.push_str(&format!(", HTTP {response_status}"));
+            }
+            if let Some(error) = item.error {
+                line.push_str(&format!(", {error}"));
+            }
+            if let Some(next_attempt_at) = item.next_attempt_at {
+                line.push_str(&format!(", next attempt at {next_attempt_at}"));
+            }
+            println!("{line}");
+        }
+    }
+
+    async fn test(&self, api: OpenPasswdApi, id: i32) {
+        let delivery = api.test_webhook(id).await.unwrap();
+        match (delivery.response_status, delivery.error) {
+            (_, None) => println!("Delivered"),
+            (Some(response_status), Some(error)) => {
+                println!("Failed with HTTP {response_status}: {error}")
+            }
+            (None, Some(error)) => println!("Failed: {error}"),
+        }
+    }
+}
@@ -1,14 +1,34 @@
 use std::collections::HashMap;
 
-use super::{dto::accounts_error::AccountResult, service::AccountService};
-use crate::{auth::dto::claims::Claims, core::validator::ValidatedJson, repository::Repository};
+use super::{
+    dto::accounts_error::{AccountError, AccountResult},
+    service::AccountService,
+};
+use crate::{
+    auth::dto::{claims::Claims, elevated_token::ElevatedClaims},
+    core::audit::RequestOrigin,
+    core::authorization::{AuthorizationError, Authorizer},
+    core::breached_passwords::{breach_headers, BreachChecker},
+    core::cache::Cache,
+    core::elevation::ElevationPolicy,
+    core::etag::{if_match_revision, json_with_etag, revision_etag},
+    core::validator::ValidatedJson,
+    repository::models::account::{AccountFilter, AccountSort, PageRequest},
+    repository::Repository,
+};
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     Extension, Json,
 };
-use model::accounts::{AccountGroupRegister, AccountRegister};
+use model::accounts::{
+    AccountGroupRegister, AccountGroupUpdate, AccountRegister, AccountShareRegister, AccountUpdate,
+};
+use model::organizations::CollectionPermission;
+
+const DEFAULT_PAGE_LIMIT: u64 = 50;
+const MAX_PAGE_LIMIT: u64 = 500;
 
 // use axum::{extract::Path, http::StatusCode, response::IntoResponse, Json};
 // use model::{accounts::AccountView, List};
@@ -18,61 +38,332 @@ pub async fn register_group(
     claims: Claims,
     ValidatedJson(account_groups): ValidatedJson<AccountGroupRegister>,
     Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
 ) -> AccountResult<impl IntoResponse> {
-    let account_service = AccountService::new(repository);
+    let account_service = AccountService::new(repository, cache);
     let account_group = account_service
         .register_group(account_groups, claims.sub)
         .await?;
     Ok((StatusCode::CREATED, Json(account_group)))
 }
 
+pub async fn delete_group(
+    claims: Claims,
+    Path(group_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let group = Authorizer::new(repository.clone())
+        .group(claims.sub, group_id, CollectionPermission::Manage)
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    account_service.delete_group(group).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn update_group(
+    claims: Claims,
+    headers: HeaderMap,
+    Path(group_id): Path<i32>,
+    ValidatedJson(mut update): ValidatedJson<AccountGroupUpdate>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    if let Some(revision) =
+        if_match_revision(&headers).map_err(|_| AccountError::InvalidRevision)?
+    {
+        update.revision = Some(revision);
+    }
+    let group = Authorizer::new(repository.clone())
+        .group(claims.sub, group_id, CollectionPermission::Manage)
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    let result = account_service.update_group(group, update).await?;
+    Ok((
+        StatusCode::OK,
+        [(header::ETAG, revision_etag(result.revision))],
+        Json(result),
+    ))
+}
+
 pub async fn list_groups(
     claims: Claims,
+    headers: HeaderMap,
     Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
 ) -> AccountResult<impl IntoResponse> {
-    let account_service = AccountService::new(repository);
-    let result = account_service.list_groups(claims.sub).await?;
-    Ok((StatusCode::OK, Json(result)))
+    let groups = Authorizer::new(repository.clone()).groups(claims.sub).await;
+    let account_service = AccountService::new(repository, cache);
+    let result = account_service.list_groups(groups).await?;
+    Ok(json_with_etag(&headers, &result))
 }
 
 pub async fn register_account(
     claims: Claims,
     ValidatedJson(account): ValidatedJson<AccountRegister>,
     Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+    Extension(breaches): Extension<BreachChecker>,
 ) -> AccountResult<impl IntoResponse> {
-    let account_service = AccountService::new(repository);
-    let account = account_service
-        .register_account(account, claims.sub)
+    let group = Authorizer::new(repository.clone())
+        .group(claims.sub, account.group_id, CollectionPermission::Edit)
+        .await
+        .map_err(|e| match e {
+            AuthorizationError::NotFound => AccountError::InvalidAccountGroup,
+            e => e.into(),
+        })?;
+    let account_service = AccountService::new(repository, cache);
+    let (account, breached) = account_service
+        .register_account(account, claims.sub, group, &breaches)
         .await?;
-    Ok((StatusCode::CREATED, Json(account)))
+    Ok((StatusCode::CREATED, breach_headers(breached), Json(account)))
 }
 
 pub async fn list_accounts(
     claims: Claims,
+    headers: HeaderMap,
     Query(params): Query<HashMap<String, String>>,
     Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
 ) -> AccountResult<impl IntoResponse> {
-    let account_service = AccountService::new(repository);
+    let groups = Authorizer::new(repository.clone()).groups(claims.sub).await;
+    let account_service = AccountService::new(repository, cache);
     let group_id = if let Some(group_id) = params.get("group_id") {
-        if let Ok(group_id) = group_id.parse::<i32>() {
-            Some(group_id)
-        } else {
-            None
-        }
+        group_id.parse::<i32>().ok()
     } else {
         None
     };
-    let result = account_service.list_accounts(claims.sub, group_id).await?;
-    Ok((StatusCode::OK, Json(result)))
+    let tag = params.get("tag").filter(|tag| !tag.is_empty()).cloned();
+    let favorite = if let Some(favorite) = params.get("favorite") {
+        favorite.parse::<bool>().ok()
+    } else {
+        None
+    };
+    let search = params.get("q").filter(|q| !q.is_empty()).cloned();
+    let filter = AccountFilter {
+        group_id,
+        tag,
+        favorite,
+        search,
+    };
+
+    let sort = if let Some(sort) = params.get("sort") {
+        sort.parse::<AccountSort>().unwrap_or_default()
+    } else {
+        AccountSort::default()
+    };
+
+    let page = if let Some(page) = params.get("page") {
+        page.parse::<u64>().ok().filter(|page| *page > 0)
+    } else {
+        None
+    };
+    let limit = if let Some(limit) = params.get("limit") {
+        limit.parse::<u64>().ok().filter(|limit| *limit > 0)
+    } else {
+        None
+    };
+    let page = PageRequest {
+        page: page.unwrap_or(1),
+        limit: limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT),
+    };
+
+    let result = account_service
+        .list_accounts(groups, filter, sort, page)
+        .await?;
+    Ok(json_with_etag(&headers, &result))
+}
+
+pub async fn list_tags(
+    claims: Claims,
+    headers: HeaderMap,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let account_service = AccountService::new(repository, cache);
+    let result = account_service.list_tags(claims.sub).await?;
+    Ok(json_with_etag(&headers, &result))
 }
 
 pub async fn get_account(
+    claims: Claims,
+    origin: RequestOrigin,
+    elevated_claims: Option<ElevatedClaims>,
+    Path(account_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+    Extension(elevation): Extension<ElevationPolicy>,
+) -> AccountResult<impl IntoResponse> {
+    let access = Authorizer::new(repository.clone())
+        .account(
+            claims.sub,
+            account_id,
+            CollectionPermission::ViewWithoutPassword,
+        )
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    let elevated = elevated_claims
+        .map(|elevated_claims| elevated_claims.sub == claims.sub)
+        .unwrap_or(false);
+    let result = account_service
+        .get_account(
+            claims.sub,
+            access,
+            elevated,
+            &elevation,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    Ok((
+        StatusCode::OK,
+        [(header::ETAG, revision_etag(result.revision))],
+        Json(result),
+    ))
+}
+
+pub async fn delete_account(
+    claims: Claims,
+    origin: RequestOrigin,
+    Path(account_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let access = Authorizer::new(repository.clone())
+        .account(claims.sub, account_id, CollectionPermission::Edit)
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    account_service
+        .delete_account(
+            claims.sub,
+            access,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn update_account(
+    claims: Claims,
+    origin: RequestOrigin,
+    headers: HeaderMap,
+    Path(account_id): Path<i32>,
+    ValidatedJson(mut account): ValidatedJson<AccountUpdate>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+    Extension(breaches): Extension<BreachChecker>,
+) -> AccountResult<impl IntoResponse> {
+    if let Some(revision) =
+        if_match_revision(&headers).map_err(|_| AccountError::InvalidRevision)?
+    {
+        account.revision = Some(revision);
+    }
+    let access = Authorizer::new(repository.clone())
+        .account(claims.sub, account_id, CollectionPermission::Edit)
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    let (result, breached) = account_service
+        .update_account(
+            claims.sub,
+            access,
+            account,
+            &breaches,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    Ok((
+        StatusCode::OK,
+        [(header::ETAG, revision_etag(result.revision))],
+        breach_headers(breached),
+        Json(result),
+    ))
+}
+
+pub async fn share_account(
+    claims: Claims,
+    origin: RequestOrigin,
+    Path(account_id): Path<i32>,
+    ValidatedJson(share): ValidatedJson<AccountShareRegister>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let access = Authorizer::new(repository.clone())
+        .account(claims.sub, account_id, CollectionPermission::Manage)
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    let result = account_service
+        .share_account(
+            claims.sub,
+            access,
+            share,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+pub async fn list_shares(
     claims: Claims,
     Path(account_id): Path<i32>,
     Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let access = Authorizer::new(repository.clone())
+        .account(claims.sub, account_id, CollectionPermission::Manage)
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    let result = account_service.list_shares(access).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn revoke_share(
+    claims: Claims,
+    origin: RequestOrigin,
+    Path((account_id, share_id)): Path<(i32, i32)>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let access = Authorizer::new(repository.clone())
+        .account(claims.sub, account_id, CollectionPermission::Manage)
+        .await?;
+    let account_service = AccountService::new(repository, cache);
+    account_service
+        .revoke_share(
+            claims.sub,
+            access,
+            share_id,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_shared_accounts(
+    claims: Claims,
+    headers: HeaderMap,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let account_service = AccountService::new(repository, cache);
+    let result = account_service.list_shared_accounts(claims.sub).await?;
+    Ok(json_with_etag(&headers, &result))
+}
+
+/// `days` also lists the passwords becoming due within that many days.
+pub async fn list_due_accounts(
+    claims: Claims,
+    Query(params): Query<HashMap<String, String>>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
 ) -> AccountResult<impl IntoResponse> {
-    let account_service = AccountService::new(repository);
-    let result = account_service.get_account(claims.sub, account_id).await?;
+    let within_days = params
+        .get("days")
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| *days >= 0)
+        .unwrap_or(0);
+    let groups = Authorizer::new(repository.clone()).groups(claims.sub).await;
+    let account_service = AccountService::new(repository, cache);
+    let result = account_service.list_due(groups, within_days).await?;
     Ok((StatusCode::OK, Json(result)))
 }
 
@@ -5,7 +5,11 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use model::error::ErrorResponse;
+use model::accounts::{AccountGroupView, AccountView};
+use model::error::{ConflictResponse, ErrorResponse};
+
+use crate::core::authorization::AuthorizationError;
+use crate::core::breached_passwords::breached_response;
 
 pub type AccountResult<T = ()> = Result<T, AccountError>;
 
@@ -14,16 +18,51 @@ pub type AccountResult<T = ()> = Result<T, AccountError>;
 pub enum AccountError {
     InvalidAccountGroup,
     NotFound,
+    ElevationRequired,
+    InvalidRecipient,
+    Forbidden,
+    MissingCredentials,
+    InvalidRevision,
+    Conflict(Box<AccountView>),
+    GroupConflict(AccountGroupView),
+    PasswordBreached(u64),
 }
 
 impl IntoResponse for AccountError {
     fn into_response(self) -> Response {
+        let conflict_message = String::from("Modified since the given revision");
         let (status, error_message) = match self {
             AccountError::InvalidAccountGroup => (
                 StatusCode::BAD_REQUEST,
                 String::from("Invalid Account Group"),
             ),
             AccountError::NotFound => (StatusCode::NOT_FOUND, String::from("Invalid Path")),
+            AccountError::ElevationRequired => (
+                StatusCode::FORBIDDEN,
+                String::from("Password confirmation required"),
+            ),
+            AccountError::InvalidRecipient => {
+                (StatusCode::BAD_REQUEST, String::from("Invalid Recipient"))
+            }
+            AccountError::Forbidden => (
+                StatusCode::FORBIDDEN,
+                String::from("Insufficient permissions"),
+            ),
+            AccountError::MissingCredentials => (
+                StatusCode::BAD_REQUEST,
+                String::from("Username and password are required"),
+            ),
+            AccountError::InvalidRevision => (
+                StatusCode::BAD_REQUEST,
+                String::from("If-Match must hold a revision"),
+            ),
+            AccountError::Conflict(current) => {
+                return conflict(conflict_message, current);
+            }
+            AccountError::GroupConflict(current) => {
+                return conflict(conflict_message, current);
+            }
+            AccountError::PasswordBreached(count) => return breached_response(count),
         };
         let body = Json(ErrorResponse {
             error: HashMap::from([(String::from("message"), error_message)]),
@@ -31,3 +70,20 @@ impl IntoResponse for AccountError {
         (status, body).into_response()
     }
 }
+
+fn conflict<T: serde::Serialize>(error_message: String, current: T) -> Response {
+    let body = Json(ConflictResponse {
+        error: HashMap::from([(String::from("message"), error_message)]),
+        current,
+    });
+    (StatusCode::CONFLICT, body).into_response()
+}
+
+impl From<AuthorizationError> for AccountError {
+    fn from(error: AuthorizationError) -> Self {
+        match error {
+            AuthorizationError::NotFound => AccountError::NotFound,
+            AuthorizationError::Forbidden => AccountError::Forbidden,
+        }
+    }
+}
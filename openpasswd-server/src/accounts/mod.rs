@@ -1,4 +1,11 @@
-use axum::{routing::get, Router};
+use axum::{
+    routing::{delete, get, put},
+    Router,
+};
+
+use model::accounts::GeneratorProfile;
+
+use crate::{core::cache::Cache, repository::Repository};
 
 pub mod controller;
 pub mod dto;
@@ -10,10 +17,56 @@ pub fn route() -> Router {
             "/api/accounts",
             get(self::controller::list_accounts).post(self::controller::register_account),
         )
-        .route("/api/accounts/:id", get(self::controller::get_account))
+        .route(
+            "/api/accounts/:id",
+            get(self::controller::get_account)
+                .put(self::controller::update_account)
+                .delete(self::controller::delete_account),
+        )
+        .route(
+            "/api/accounts/:id/shares",
+            get(self::controller::list_shares).post(self::controller::share_account),
+        )
+        .route(
+            "/api/accounts/:id/shares/:share_id",
+            delete(self::controller::revoke_share),
+        )
+        .route(
+            "/api/accounts/due",
+            get(self::controller::list_due_accounts),
+        )
+        .route(
+            "/api/accounts/shared",
+            get(self::controller::list_shared_accounts),
+        )
         .route(
             "/api/accounts/groups",
             get(self::controller::list_groups).post(self::controller::register_group),
         )
+        .route(
+            "/api/accounts/groups/:id",
+            put(self::controller::update_group).delete(self::controller::delete_group),
+        )
+        .route("/api/accounts/tags", get(self::controller::list_tags))
     // .route("/api/accounts/:id", get(accounts::get))
 }
+
+/// Emails reminders for passwords due for rotation.
+pub async fn reminder_task(repository: Repository, cache: Cache) {
+    service::AccountService::new(repository, cache)
+        .reminder_task()
+        .await
+}
+
+/// Generator profile stored as JSON on the account, `None` when there is none
+/// or it can't be read.
+pub fn stored_generator_profile(json: Option<&str>) -> Option<GeneratorProfile> {
+    let json = json?;
+    match serde_json::from_str(json) {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            log::warn!("generator profile: {e}");
+            None
+        }
+    }
+}
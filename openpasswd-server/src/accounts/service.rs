@@ -1,27 +1,106 @@
-use crate::core::cryptography::{AesGcmCipher, Cipher};
-use crate::repository::models::account::{NewAccount, NewAccountGroup, NewAccountPassword};
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::core::audit::{self, RequestOrigin};
+use crate::core::authorization::{group_members, AccountAccess, GroupAccess};
+use crate::core::breached_passwords::BreachChecker;
+use crate::core::cache::Cache;
+use crate::core::cryptography::{generate_key, AesGcmCipher, Cipher, RsaCipher};
+use crate::core::elevation::ElevationPolicy;
+use crate::core::keys::{organization_key, user_key_pair};
+use crate::core::mail_service::{EmailAddress, MailService, MessageBody};
+use crate::core::notifications::{group_recipients, notify_users};
+use crate::repository::models::account::{
+    AccountChanges, AccountFilter, AccountSort, NewAccount, NewAccountGroup, NewAccountPassword,
+    NewAccountShare, PageRequest,
+};
 use crate::repository::repositories::accounts_repository::AccountsRepository;
+use crate::repository::repositories::audit_repository::AuditRepository;
+use crate::repository::repositories::authorization_repository::AuthorizationRepository;
+use crate::repository::repositories::organizations_repository::OrganizationsRepository;
+use crate::repository::repositories::reports_repository::ReportsRepository;
+use crate::repository::repositories::rotation_repository::RotationRepository;
+use crate::repository::repositories::shares_repository::SharesRepository;
+use crate::repository::repositories::sync_repository::SyncRepository;
+use crate::repository::repositories::trash_repository::TrashRepository;
 use crate::repository::repositories::users_repository::UsersRepository;
+use crate::repository::repositories::webhooks_repository::WebhooksRepository;
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
+use entity::users::Model as User;
 use model::accounts::{
-    AccountGroupRegister, AccountGroupView, AccountRegister, AccountView, AccountWithPasswordView,
+    AccountGroupRegister, AccountGroupUpdate, AccountGroupView, AccountRegister,
+    AccountShareRegister, AccountShareView, AccountUpdate, AccountView, AccountWithPasswordView,
+    DueAccountView, SharePermission, SharedAccountView, TagView,
 };
-use model::List;
+use model::audit::AuditEventType;
+use model::notifications::NotificationEvent;
+use model::organizations::CollectionPermission;
+use model::sync::{SyncAction, SyncItemType};
+use model::{List, Pagination};
 
 use super::dto::accounts_error::{AccountError, AccountResult};
+use super::stored_generator_profile;
+
+const DEFAULT_ACCOUNT_LEVEL: i16 = 1;
+
+fn account_target(account_id: i32) -> String {
+    format!("account:{account_id}")
+}
+
+const ROTATION_REMINDER_INTERVAL_SECONDS: u64 = 60 * 60;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Due date of a password changed on `changed_date` and the days it is
+/// overdue by, negative while it is still ahead. `None` when the due date is
+/// out of range, such a password is never due.
+fn rotation_due(
+    changed_date: NaiveDateTime,
+    rotation_days: i32,
+    now: NaiveDateTime,
+) -> Option<(NaiveDateTime, i64)> {
+    let due_date = changed_date.checked_add_signed(Duration::days(i64::from(rotation_days)))?;
+    // Rounded down, a password due in a few hours is overdue by -1 day.
+    let overdue_days = (now - due_date).num_seconds().div_euclid(SECONDS_PER_DAY);
+    Some((due_date, overdue_days))
+}
+
+fn to_rfc3339(datetime: NaiveDateTime) -> String {
+    Utc.from_utc_datetime(&datetime).to_rfc3339()
+}
 
 pub struct AccountService<T>
 where
-    T: AccountsRepository + UsersRepository,
+    T: AccountsRepository
+        + AuditRepository
+        + AuthorizationRepository
+        + OrganizationsRepository
+        + ReportsRepository
+        + RotationRepository
+        + SharesRepository
+        + SyncRepository
+        + TrashRepository
+        + UsersRepository
+        + WebhooksRepository,
 {
     repository: T,
+    cache: Cache,
 }
 
 impl<T> AccountService<T>
 where
-    T: AccountsRepository + UsersRepository,
+    T: AccountsRepository
+        + AuditRepository
+        + AuthorizationRepository
+        + OrganizationsRepository
+        + ReportsRepository
+        + RotationRepository
+        + SharesRepository
+        + SyncRepository
+        + TrashRepository
+        + UsersRepository
+        + WebhooksRepository,
 {
-    pub fn new(repository: T) -> AccountService<T> {
-        AccountService { repository }
+    pub fn new(repository: T, cache: Cache) -> AccountService<T> {
+        AccountService { repository, cache }
     }
 
     pub async fn register_group(
@@ -29,69 +108,194 @@ where
         account_group: AccountGroupRegister,
         id: i32,
     ) -> AccountResult<AccountGroupView> {
-        let AccountGroupRegister { name } = account_group;
-        let account_group = NewAccountGroup { name, user_id: id };
+        let AccountGroupRegister {
+            name,
+            rotation_days,
+        } = account_group;
+        let account_group = NewAccountGroup {
+            name,
+            user_id: id,
+            organization_id: None,
+            rotation_days,
+        };
 
         let account_group = self
             .repository
             .accounts_groups_insert(account_group)
             .await
             .unwrap();
+        let user_ids = group_recipients(&self.repository, &account_group, None).await;
+        self.repository
+            .sync_record(
+                &user_ids,
+                SyncItemType::Group.as_str(),
+                account_group.id,
+                SyncAction::Created.as_str(),
+            )
+            .await;
+        notify_users(
+            &self.cache,
+            &user_ids,
+            NotificationEvent::GroupCreated,
+            account_group.id,
+        )
+        .await;
 
         Ok(AccountGroupView {
             id: account_group.id,
             name: account_group.name,
+            organization_id: account_group.organization_id,
+            revision: account_group.revision,
+            rotation_days: account_group.rotation_days,
         })
     }
 
-    pub async fn list_groups(self, user_id: i32) -> AccountResult<List<AccountGroupView>> {
-        let result = self.repository.accounts_groups_list(user_id).await;
+    pub async fn update_group(
+        self,
+        group: GroupAccess,
+        update: AccountGroupUpdate,
+    ) -> AccountResult<AccountGroupView> {
+        let updated = self
+            .repository
+            .accounts_groups_update(
+                group.group.id,
+                update.revision,
+                update.name,
+                update
+                    .rotation_days
+                    .map(|days| Some(days).filter(|days| *days > 0)),
+            )
+            .await;
+        let group = self
+            .repository
+            .accounts_groups_find_by_id(group.group.id)
+            .await
+            .ok_or(AccountError::NotFound)?;
+        let view = AccountGroupView {
+            id: group.id,
+            name: group.name.to_owned(),
+            organization_id: group.organization_id,
+            revision: group.revision,
+            rotation_days: group.rotation_days,
+        };
+        if !updated {
+            return Err(AccountError::GroupConflict(view));
+        }
+
+        let user_ids = group_recipients(&self.repository, &group, None).await;
+        self.repository
+            .sync_record(
+                &user_ids,
+                SyncItemType::Group.as_str(),
+                group.id,
+                SyncAction::Updated.as_str(),
+            )
+            .await;
+        notify_users(
+            &self.cache,
+            &user_ids,
+            NotificationEvent::GroupUpdated,
+            group.id,
+        )
+        .await;
+        Ok(view)
+    }
 
+    pub async fn delete_group(self, group: GroupAccess) -> AccountResult {
+        if self.repository.trash_groups_move(group.group.id).await {
+            let user_ids = group_recipients(&self.repository, &group.group, None).await;
+            self.repository
+                .sync_record(
+                    &user_ids,
+                    SyncItemType::Group.as_str(),
+                    group.group.id,
+                    SyncAction::Deleted.as_str(),
+                )
+                .await;
+            notify_users(
+                &self.cache,
+                &user_ids,
+                NotificationEvent::GroupDeleted,
+                group.group.id,
+            )
+            .await;
+            Ok(())
+        } else {
+            Err(AccountError::NotFound)
+        }
+    }
+
+    pub async fn list_groups(
+        self,
+        groups: Vec<GroupAccess>,
+    ) -> AccountResult<List<AccountGroupView>> {
         Ok(List {
-            items: result
-                .iter()
-                .map(|r| AccountGroupView {
-                    id: r.id,
-                    name: r.name.to_owned(),
+            total: groups.len() as u32,
+            items: groups
+                .into_iter()
+                .map(|access| AccountGroupView {
+                    id: access.group.id,
+                    name: access.group.name,
+                    organization_id: access.group.organization_id,
+                    revision: access.group.revision,
+                    rotation_days: access.group.rotation_days,
                 })
                 .collect(),
-            total: result.len() as u32,
+            pagination: None,
         })
     }
 
+    /// The breach count comes along when the password was accepted with a
+    /// warning.
     pub async fn register_account(
         self,
         account: AccountRegister,
         user_id: i32,
-    ) -> AccountResult<AccountView> {
-        let groups = self
-            .repository
-            .accounts_groups_find_by_id(account.group_id, user_id)
-            .await;
-
-        if groups.is_none() {
-            return Err(AccountError::InvalidAccountGroup);
-        }
-
-        let master_key = self
-            .repository
-            .users_find_by_id(user_id)
+        group: GroupAccess,
+        breaches: &BreachChecker,
+    ) -> AccountResult<(AccountView, Option<u64>)> {
+        let breached = breaches
+            .check_new_password(&account.password)
             .await
-            .unwrap()
-            .master_key
-            .unwrap();
-        let cipher = AesGcmCipher::new(&master_key);
+            .map_err(AccountError::PasswordBreached)?;
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let key_cipher = self.key_cipher(&user, group.group.organization_id).await;
+        let item_key = generate_key();
+        let cipher = AesGcmCipher::new(&item_key);
 
         let new_account = NewAccount {
             name: account.name,
-            level: account.level,
-            account_groups_id: account.group_id,
+            level: account.level.unwrap_or(DEFAULT_ACCOUNT_LEVEL),
+            account_groups_id: group.group.id,
+            url: account.url,
+            favorite: account.favorite,
+            two_factor: account.two_factor,
+            rotation_days: account.rotation_days,
+            generator_profile: account
+                .generator_profile
+                .map(|profile| serde_json::to_string(&profile).unwrap()),
+            item_key: Some(key_cipher.encrypt(&item_key)),
 
             user_id,
         };
 
         let db_account = self.repository.accounts_insert(new_account).await.unwrap();
 
+        let mut tag_names: Vec<String> = account
+            .tags
+            .iter()
+            .map(|tag| tag.trim().to_owned())
+            .collect();
+        tag_names.sort();
+        tag_names.dedup();
+        let tags = self
+            .repository
+            .tags_find_or_insert(user_id, tag_names)
+            .await;
+        self.repository
+            .account_tags_insert(db_account.id, tags.iter().map(|t| t.id).collect())
+            .await;
+
         let password = cipher.encrypt(&account.password);
         let created_date = chrono::Utc::now().naive_utc();
         let account_password = NewAccountPassword {
@@ -106,26 +310,60 @@ where
             .account_passwords_insert(account_password)
             .await
             .unwrap();
+        let user_ids = group_recipients(&self.repository, &group.group, Some(db_account.id)).await;
+        self.repository
+            .sync_record(
+                &user_ids,
+                SyncItemType::Account.as_str(),
+                db_account.id,
+                SyncAction::Created.as_str(),
+            )
+            .await;
+        notify_users(
+            &self.cache,
+            &user_ids,
+            NotificationEvent::ItemCreated,
+            db_account.id,
+        )
+        .await;
 
-        Ok(AccountView {
+        let view = AccountView {
             id: db_account.id,
             name: db_account.name,
             group_id: db_account.account_groups_id,
-        })
+            level: db_account.level,
+            url: db_account.url,
+            favorite: db_account.favorite,
+            two_factor: db_account.two_factor,
+            rotation_days: db_account.rotation_days,
+            generator_profile: stored_generator_profile(db_account.generator_profile.as_deref()),
+            tags: tags.into_iter().map(|t| t.name).collect(),
+            revision: db_account.revision,
+        };
+        Ok((view, breached))
     }
 
     pub async fn list_accounts(
         self,
-        user_id: i32,
-        group_id: Option<i32>,
+        groups: Vec<GroupAccess>,
+        filter: AccountFilter,
+        sort: AccountSort,
+        page: PageRequest,
     ) -> AccountResult<List<AccountView>> {
-        let result = if let Some(group_id) = group_id {
-            self.repository
-                .accounts_list_by_group_id(user_id, group_id)
-                .await
-        } else {
-            self.repository.accounts_list(user_id).await
-        };
+        let (result, total) = self
+            .repository
+            .accounts_list(
+                groups.iter().map(|access| access.group.id).collect(),
+                filter,
+                sort,
+                page,
+            )
+            .await;
+
+        let account_tags = self
+            .repository
+            .account_tags_list_by_account_ids(result.iter().map(|r| r.id).collect())
+            .await;
 
         Ok(List {
             items: result
@@ -134,49 +372,655 @@ where
                     id: r.id,
                     name: r.name.to_owned(),
                     group_id: r.account_groups_id,
+                    level: r.level,
+                    url: r.url.to_owned(),
+                    favorite: r.favorite,
+                    two_factor: r.two_factor,
+                    rotation_days: r.rotation_days,
+                    generator_profile: stored_generator_profile(r.generator_profile.as_deref()),
+                    tags: account_tags
+                        .iter()
+                        .filter(|(account_id, _)| *account_id == r.id)
+                        .map(|(_, name)| name.to_owned())
+                        .collect(),
+                    revision: r.revision,
+                })
+                .collect(),
+            total: total as u32,
+            pagination: Some(Pagination {
+                page: page.page as u32,
+                limit: page.limit as u32,
+                pages: total.div_ceil(page.limit) as u32,
+            }),
+        })
+    }
+
+    pub async fn list_tags(self, user_id: i32) -> AccountResult<List<TagView>> {
+        let result = self.repository.tags_list(user_id).await;
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|r| TagView {
+                    id: r.id,
+                    name: r.name.to_owned(),
                 })
                 .collect(),
             total: result.len() as u32,
+            pagination: None,
         })
     }
 
     pub async fn get_account(
         self,
         user_id: i32,
-        account_id: i32,
+        access: AccountAccess,
+        elevated: bool,
+        elevation: &ElevationPolicy,
+        origin: RequestOrigin,
     ) -> AccountResult<AccountWithPasswordView> {
-        let result = self
+        let account_passwords = self
             .repository
-            .accounts_get_with_passwords_by_account_id(account_id, user_id)
+            .accounts_passwords_list_account_id(access.account.id)
             .await;
 
-        if let Some((account, account_passwords)) = result {
-            let master_key = self
-                .repository
-                .users_find_by_id(user_id)
-                .await
-                .unwrap()
-                .master_key
-                .unwrap();
-            let cipher = AesGcmCipher::new(&master_key);
+        let latest = account_passwords.iter().max_by_key(|p| p.id);
+        let username = latest.map(|latest| latest.username.to_owned());
 
-            let (username, password) = if let Some(account_password) = account_passwords.last() {
-                (
-                    Some(account_password.username.to_owned()),
-                    Some(cipher.decrypt(&account_password.password)),
+        // Members allowed to see that an account exists but not its password
+        // still get the username.
+        let password = match latest {
+            Some(latest) if access.permission >= CollectionPermission::View => {
+                if elevation.requires_elevation(access.account.level) && !elevated {
+                    return Err(AccountError::ElevationRequired);
+                }
+                let user = self.repository.users_find_by_id(user_id).await.unwrap();
+                let cipher = self.access_cipher(&user, &access).await;
+                let password = cipher.decrypt(&latest.password);
+                audit::record(
+                    &self.repository,
+                    Some(user_id),
+                    AuditEventType::PasswordRevealed,
+                    Some(account_target(access.account.id)),
+                    &origin,
                 )
-            } else {
-                (None, None)
-            };
+                .await;
+                Some(password)
+            }
+            _ => None,
+        };
+
+        Ok(AccountWithPasswordView {
+            id: access.account.id,
+            name: access.account.name,
+            username,
+            password,
+            revision: access.account.revision,
+        })
+    }
+
+    pub async fn delete_account(
+        self,
+        user_id: i32,
+        access: AccountAccess,
+        origin: RequestOrigin,
+    ) -> AccountResult {
+        if self.repository.trash_accounts_move(access.account.id).await {
+            let user_ids =
+                group_recipients(&self.repository, &access.group, Some(access.account.id)).await;
+            self.repository
+                .sync_record(
+                    &user_ids,
+                    SyncItemType::Account.as_str(),
+                    access.account.id,
+                    SyncAction::Deleted.as_str(),
+                )
+                .await;
+            notify_users(
+                &self.cache,
+                &user_ids,
+                NotificationEvent::ItemDeleted,
+                access.account.id,
+            )
+            .await;
+            audit::record(
+                &self.repository,
+                Some(user_id),
+                AuditEventType::AccountDeleted,
+                Some(account_target(access.account.id)),
+                &origin,
+            )
+            .await;
+            Ok(())
+        } else {
+            Err(AccountError::NotFound)
+        }
+    }
+
+    pub async fn update_account(
+        self,
+        user_id: i32,
+        access: AccountAccess,
+        changes: AccountUpdate,
+        breaches: &BreachChecker,
+        origin: RequestOrigin,
+    ) -> AccountResult<(AccountView, Option<u64>)> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let cipher = self.access_cipher(&user, &access).await;
+        let account = access.account;
+        let account_passwords = self
+            .repository
+            .accounts_passwords_list_account_id(account.id)
+            .await;
 
-            Ok(AccountWithPasswordView {
-                id: account.id,
-                name: account.name,
+        let AccountUpdate {
+            name,
+            url,
+            username,
+            password,
+            two_factor,
+            rotation_days,
+            generator_profile,
+            revision,
+        } = changes;
+
+        let breached = match &password {
+            Some(password) => breaches
+                .check_new_password(password)
+                .await
+                .map_err(AccountError::PasswordBreached)?,
+            None => None,
+        };
+
+        // Credentials are versioned, a change appends a new account_passwords
+        // row carrying over whatever was not changed from the latest one.
+        let new_password = if username.is_some() || password.is_some() {
+            let latest = account_passwords.iter().max_by_key(|p| p.id);
+            let username = match (username, latest) {
+                (Some(username), _) => username,
+                (None, Some(latest)) => latest.username.to_owned(),
+                (None, None) => return Err(AccountError::MissingCredentials),
+            };
+            let password = match (password, latest) {
+                (Some(password), _) => cipher.encrypt(&password),
+                (None, Some(latest)) => latest.password.to_owned(),
+                (None, None) => return Err(AccountError::MissingCredentials),
+            };
+            Some(NewAccountPassword {
+                account_id: account.id,
                 username,
                 password,
+                created_date: chrono::Utc::now().naive_utc(),
             })
+        } else {
+            None
+        };
+
+        let updated = self
+            .repository
+            .accounts_update(
+                account.id,
+                revision,
+                AccountChanges {
+                    name,
+                    url,
+                    two_factor,
+                    rotation_days: rotation_days.map(|days| Some(days).filter(|days| *days > 0)),
+                    generator_profile: generator_profile
+                        .map(|profile| serde_json::to_string(&profile).unwrap()),
+                },
+            )
+            .await;
+        let account = self
+            .repository
+            .accounts_find_by_id(account.id)
+            .await
+            .ok_or(AccountError::NotFound)?;
+        if !updated {
+            return Err(AccountError::Conflict(Box::new(
+                self.account_view(account).await,
+            )));
+        }
+
+        if let Some(new_password) = new_password {
+            self.repository
+                .account_passwords_insert(new_password)
+                .await
+                .unwrap();
+        }
+
+        audit::record(
+            &self.repository,
+            Some(user_id),
+            AuditEventType::AccountUpdated,
+            Some(account_target(account.id)),
+            &origin,
+        )
+        .await;
+        let user_ids = group_recipients(&self.repository, &access.group, Some(account.id)).await;
+        self.repository
+            .sync_record(
+                &user_ids,
+                SyncItemType::Account.as_str(),
+                account.id,
+                SyncAction::Updated.as_str(),
+            )
+            .await;
+        notify_users(
+            &self.cache,
+            &user_ids,
+            NotificationEvent::ItemUpdated,
+            account.id,
+        )
+        .await;
+
+        Ok((self.account_view(account).await, breached))
+    }
+
+    pub async fn share_account(
+        self,
+        user_id: i32,
+        access: AccountAccess,
+        share: AccountShareRegister,
+        origin: RequestOrigin,
+    ) -> AccountResult<AccountShareView> {
+        let account = access.account;
+        let account_passwords = self
+            .repository
+            .accounts_passwords_list_account_id(account.id)
+            .await;
+
+        let recipient = match self.repository.users_find_by_email(&share.email).await {
+            Some(recipient) if recipient.id != user_id => recipient,
+            _ => return Err(AccountError::InvalidRecipient),
+        };
+
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let key_cipher = self.key_cipher(&user, access.group.organization_id).await;
+        let item_key = match &account.item_key {
+            Some(item_key) => key_cipher.decrypt(item_key),
+            None => {
+                // Accounts created before sharing existed are encrypted with
+                // the master key, move them to their own item key first.
+                let item_key = generate_key();
+                let item_cipher = AesGcmCipher::new(&item_key);
+                let passwords = account_passwords
+                    .iter()
+                    .map(|account_password| {
+                        let password = key_cipher.decrypt(&account_password.password);
+                        (account_password.id, item_cipher.encrypt(&password))
+                    })
+                    .collect();
+                self.repository
+                    .accounts_set_item_key(account.id, key_cipher.encrypt(&item_key), passwords)
+                    .await;
+                item_key
+            }
+        };
+
+        let (public_key, _) = user_key_pair(&self.repository, &recipient).await;
+        let new_share = NewAccountShare {
+            account_id: account.id,
+            user_id: recipient.id,
+            item_key: RsaCipher::from_public_key(&public_key).encrypt(&item_key),
+            permission: share.permission.as_str().to_owned(),
+            created_date: chrono::Utc::now().naive_utc(),
+        };
+        let db_share = self.repository.shares_upsert(new_share).await;
+        self.repository
+            .sync_record(
+                &[recipient.id],
+                SyncItemType::Account.as_str(),
+                account.id,
+                SyncAction::Created.as_str(),
+            )
+            .await;
+        notify_users(
+            &self.cache,
+            &[recipient.id],
+            NotificationEvent::ItemCreated,
+            account.id,
+        )
+        .await;
+        audit::record(
+            &self.repository,
+            Some(user_id),
+            AuditEventType::AccountShared,
+            Some(account_target(account.id)),
+            &origin,
+        )
+        .await;
+
+        Ok(AccountShareView {
+            id: db_share.id,
+            account_id: db_share.account_id,
+            email: recipient.email,
+            name: recipient.name,
+            permission: share.permission,
+        })
+    }
+
+    pub async fn list_shares(self, access: AccountAccess) -> AccountResult<List<AccountShareView>> {
+        let result = self
+            .repository
+            .shares_list_by_account_id(access.account.id)
+            .await;
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|(share, recipient)| AccountShareView {
+                    id: share.id,
+                    account_id: share.account_id,
+                    email: recipient.email.to_owned(),
+                    name: recipient.name.to_owned(),
+                    permission: share
+                        .permission
+                        .parse()
+                        .unwrap_or(SharePermission::ReadOnly),
+                })
+                .collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    pub async fn revoke_share(
+        self,
+        user_id: i32,
+        access: AccountAccess,
+        share_id: i32,
+        origin: RequestOrigin,
+    ) -> AccountResult {
+        let recipient_id = self
+            .repository
+            .shares_list_by_account_id(access.account.id)
+            .await
+            .into_iter()
+            .find(|(share, _)| share.id == share_id)
+            .map(|(share, _)| share.user_id);
+
+        if self
+            .repository
+            .shares_delete(share_id, access.account.id)
+            .await
+        {
+            if let Some(recipient_id) = recipient_id {
+                self.repository
+                    .sync_record(
+                        &[recipient_id],
+                        SyncItemType::Account.as_str(),
+                        access.account.id,
+                        SyncAction::Deleted.as_str(),
+                    )
+                    .await;
+                notify_users(
+                    &self.cache,
+                    &[recipient_id],
+                    NotificationEvent::ItemDeleted,
+                    access.account.id,
+                )
+                .await;
+            }
+            audit::record(
+                &self.repository,
+                Some(user_id),
+                AuditEventType::ShareRevoked,
+                Some(account_target(access.account.id)),
+                &origin,
+            )
+            .await;
+            Ok(())
         } else {
             Err(AccountError::NotFound)
         }
     }
+
+    pub async fn list_shared_accounts(
+        self,
+        user_id: i32,
+    ) -> AccountResult<List<SharedAccountView>> {
+        let result = self.repository.shares_list_by_user_id(user_id).await;
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|(share, account, owner)| SharedAccountView {
+                    id: account.id,
+                    name: account.name.to_owned(),
+                    level: account.level,
+                    url: account.url.to_owned(),
+                    owner: owner.email.to_owned(),
+                    permission: share
+                        .permission
+                        .parse()
+                        .unwrap_or(SharePermission::ReadOnly),
+                })
+                .collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    /// Accounts whose password is due for rotation within `within_days`,
+    /// the most overdue first.
+    pub async fn list_due(
+        self,
+        groups: Vec<GroupAccess>,
+        within_days: i64,
+    ) -> AccountResult<List<DueAccountView>> {
+        let now = Utc::now().naive_utc();
+        let mut result: Vec<DueAccountView> = self
+            .due_accounts(Some(groups.iter().map(|access| access.group.id).collect()))
+            .await
+            .into_iter()
+            .filter_map(|(account, _, rotation_days, changed_date)| {
+                let (due_date, overdue_days) = rotation_due(changed_date, rotation_days, now)?;
+                (overdue_days >= -within_days).then(|| DueAccountView {
+                    id: account.id,
+                    name: account.name,
+                    group_id: account.account_groups_id,
+                    rotation_days,
+                    changed_date: to_rfc3339(changed_date),
+                    due_date: to_rfc3339(due_date),
+                    overdue_days,
+                })
+            })
+            .collect();
+        result.sort_by(|a, b| b.overdue_days.cmp(&a.overdue_days).then(a.id.cmp(&b.id)));
+
+        Ok(List {
+            total: result.len() as u32,
+            items: result,
+            pagination: None,
+        })
+    }
+
+    /// Emails the users able to change each account's password once it is
+    /// due, again only after it was rotated and became due once more.
+    pub async fn reminder_task(self) {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            ROTATION_REMINDER_INTERVAL_SECONDS,
+        ));
+        loop {
+            interval.tick().await;
+
+            let now = Utc::now().naive_utc();
+            let mut recipients: HashMap<i32, Vec<i32>> = HashMap::new();
+            let mut reminders: Vec<(i32, Vec<entity::accounts::Model>)> = Vec::new();
+            for (account, group, rotation_days, changed_date) in self.due_accounts(None).await {
+                let overdue_days = match rotation_due(changed_date, rotation_days, now) {
+                    Some((_, overdue_days)) => overdue_days,
+                    None => continue,
+                };
+                let reminded = account
+                    .rotation_reminded_at
+                    .map(|reminded_at| reminded_at >= changed_date)
+                    .unwrap_or(false);
+                if overdue_days < 0 || reminded {
+                    continue;
+                }
+                if let Entry::Vacant(entry) = recipients.entry(group.id) {
+                    entry.insert(
+                        group_members(&self.repository, &group, CollectionPermission::Edit).await,
+                    );
+                }
+                for user_id in recipients[&group.id].iter() {
+                    match reminders.iter_mut().find(|(id, _)| id == user_id) {
+                        Some((_, accounts)) => accounts.push(account.clone()),
+                        None => reminders.push((*user_id, vec![account.clone()])),
+                    }
+                }
+            }
+
+            for (user_id, accounts) in reminders {
+                let user = match self.repository.users_find_by_id(user_id).await {
+                    Some(user) => user,
+                    None => continue,
+                };
+                let names: Vec<String> = accounts
+                    .iter()
+                    .map(|account| format!("- {}", account.name))
+                    .collect();
+                if let Err(e) = MailService::send_email_from_system(
+                    EmailAddress::new(Some(&user.name), &user.email),
+                    String::from("Passwords due for rotation"),
+                    MessageBody::Text(format!(
+                        "The passwords of these OpenPasswd accounts are due for rotation:\n{}",
+                        names.join("\n")
+                    )),
+                )
+                .await
+                {
+                    log::error!("{}", e);
+                    continue;
+                }
+                self.repository
+                    .rotation_set_reminded(accounts.iter().map(|account| account.id).collect(), now)
+                    .await;
+            }
+        }
+    }
+
+    /// Accounts with a rotation interval, with their group, the interval in
+    /// effect and the date of their current password.
+    async fn due_accounts(
+        &self,
+        group_ids: Option<Vec<i32>>,
+    ) -> Vec<(
+        entity::accounts::Model,
+        entity::account_groups::Model,
+        i32,
+        NaiveDateTime,
+    )> {
+        let accounts = self.repository.rotation_accounts(group_ids).await;
+        let passwords = self
+            .repository
+            .reports_current_passwords(accounts.iter().map(|(account, _)| account.id).collect())
+            .await;
+
+        accounts
+            .into_iter()
+            .filter_map(|(account, group)| {
+                let rotation_days = account.rotation_days.or(group.rotation_days)?;
+                let changed_date = passwords
+                    .iter()
+                    .find(|password| password.account_id == account.id)?
+                    .created_date;
+                Some((account, group, rotation_days, changed_date))
+            })
+            .collect()
+    }
+
+    async fn account_view(&self, account: entity::accounts::Model) -> AccountView {
+        let tags = self
+            .repository
+            .account_tags_list_by_account_ids(vec![account.id])
+            .await;
+
+        AccountView {
+            id: account.id,
+            name: account.name,
+            group_id: account.account_groups_id,
+            level: account.level,
+            url: account.url,
+            favorite: account.favorite,
+            two_factor: account.two_factor,
+            rotation_days: account.rotation_days,
+            generator_profile: stored_generator_profile(account.generator_profile.as_deref()),
+            tags: tags.into_iter().map(|(_, name)| name).collect(),
+            revision: account.revision,
+        }
+    }
+
+    /// Cipher wrapping the item keys of a group: the organization key for
+    /// collections, the master key of `user` for personal groups.
+    async fn key_cipher(&self, user: &User, organization_id: Option<i32>) -> AesGcmCipher {
+        match organization_id {
+            Some(organization_id) => {
+                let org_key = organization_key(&self.repository, user, organization_id)
+                    .await
+                    .unwrap();
+                AesGcmCipher::new(&org_key)
+            }
+            None => AesGcmCipher::new(user.master_key.as_ref().unwrap()),
+        }
+    }
+
+    /// Cipher for the passwords of an account, either through its group or
+    /// through a direct share. Accounts without an item key predate sharing
+    /// and use the key of the group directly.
+    async fn access_cipher(&self, user: &User, access: &AccountAccess) -> AesGcmCipher {
+        if let Some(share) = &access.share {
+            return self.shared_cipher(user, &share.item_key).await;
+        }
+
+        let key_cipher = self.key_cipher(user, access.group.organization_id).await;
+        match &access.account.item_key {
+            Some(item_key) => AesGcmCipher::new(&key_cipher.decrypt(item_key)),
+            None => key_cipher,
+        }
+    }
+
+    /// Cipher for the passwords of an account shared with `user`, unwrapping
+    /// the item key with the user's private key.
+    async fn shared_cipher(&self, user: &User, item_key: &[u8]) -> AesGcmCipher {
+        let (_, private_key) = user_key_pair(&self.repository, user).await;
+        let item_key = RsaCipher::from_private_key(&private_key).decrypt(item_key);
+        AesGcmCipher::new(&item_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::rotation_due;
+
+    #[test]
+    fn rotation_due_days_are_rounded_down() {
+        let at = |day, hour| {
+            NaiveDate::from_ymd_opt(2022, 1, day)
+                .and_then(|date| date.and_hms_opt(hour, 0, 0))
+                .unwrap()
+        };
+        let changed_date = at(1, 12);
+
+        let (due_date, overdue_days) = rotation_due(changed_date, 30, at(31, 12)).unwrap();
+        assert_eq!(at(31, 12), due_date);
+        assert_eq!(0, overdue_days);
+        let overdue_days = |now| rotation_due(changed_date, 30, now).unwrap().1;
+        assert_eq!(-1, overdue_days(at(31, 6)));
+        assert_eq!(-10, overdue_days(at(21, 12)));
+        assert_eq!(0, overdue_days(at(31, 23)));
+    }
+
+    #[test]
+    fn rotation_due_out_of_range_is_never_due() {
+        let changed_date = NaiveDate::from_ymd_opt(2022, 1, 1)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .unwrap();
+        assert_eq!(None, rotation_due(changed_date, i32::MAX, changed_date));
+        assert_eq!(None, rotation_due(changed_date, 100_000_000, changed_date));
+    }
 }
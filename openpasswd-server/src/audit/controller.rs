@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use super::{
+    dto::audit_error::{AuditError, AuditResult},
+    service::AuditService,
+};
+use crate::{
+    auth::dto::claims::Claims,
+    repository::models::{account::PageRequest, audit::AuditFilter},
+    repository::Repository,
+};
+use axum::{extract::Query, http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::{DateTime, NaiveDateTime};
+use model::audit::AuditEventType;
+
+const DEFAULT_PAGE_LIMIT: u64 = 50;
+const MAX_PAGE_LIMIT: u64 = 500;
+
+fn parse_date(value: &str) -> AuditResult<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.naive_utc())
+        .map_err(|_| AuditError::InvalidDate)
+}
+
+pub async fn list_events(
+    claims: Claims,
+    Query(params): Query<HashMap<String, String>>,
+    Extension(repository): Extension<Repository>,
+) -> AuditResult<impl IntoResponse> {
+    let event_type = match params.get("event_type") {
+        Some(event_type) => Some(
+            event_type
+                .parse::<AuditEventType>()
+                .map_err(|_| AuditError::InvalidEventType)?
+                .as_str()
+                .to_owned(),
+        ),
+        None => None,
+    };
+    let since = params
+        .get("since")
+        .map(|since| parse_date(since))
+        .transpose()?;
+    let until = params
+        .get("until")
+        .map(|until| parse_date(until))
+        .transpose()?;
+    let filter = AuditFilter {
+        event_type,
+        since,
+        until,
+    };
+
+    let page = if let Some(page) = params.get("page") {
+        page.parse::<u64>().ok().filter(|page| *page > 0)
+    } else {
+        None
+    };
+    let limit = if let Some(limit) = params.get("limit") {
+        limit.parse::<u64>().ok().filter(|limit| *limit > 0)
+    } else {
+        None
+    };
+    let page = PageRequest {
+        page: page.unwrap_or(1),
+        limit: limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT),
+    };
+
+    let audit_service = AuditService::new(repository);
+    let result = audit_service.list_events(claims.sub, filter, page).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+pub type AuditResult<T = ()> = Result<T, AuditError>;
+
+#[derive(Debug)]
+pub enum AuditError {
+    InvalidEventType,
+    InvalidDate,
+}
+
+impl IntoResponse for AuditError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            AuditError::InvalidEventType => {
+                (StatusCode::BAD_REQUEST, String::from("Invalid event type"))
+            }
+            AuditError::InvalidDate => (
+                StatusCode::BAD_REQUEST,
+                String::from("Dates must be RFC 3339 timestamps"),
+            ),
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
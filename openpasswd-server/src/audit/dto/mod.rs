@@ -0,0 +1 @@
+pub mod audit_error;
@@ -0,0 +1,9 @@
+use axum::{routing::get, Router};
+
+pub mod controller;
+pub mod dto;
+mod service;
+
+pub fn route() -> Router {
+    Router::new().route("/api/audit", get(self::controller::list_events))
+}
@@ -0,0 +1,57 @@
+use super::dto::audit_error::AuditResult;
+use crate::repository::models::account::PageRequest;
+use crate::repository::models::audit::AuditFilter;
+use crate::repository::repositories::audit_repository::AuditRepository;
+use chrono::{TimeZone, Utc};
+use model::audit::{AuditEventType, AuditEventView};
+use model::{List, Pagination};
+
+pub struct AuditService<T>
+where
+    T: AuditRepository,
+{
+    repository: T,
+}
+
+impl<T> AuditService<T>
+where
+    T: AuditRepository,
+{
+    pub fn new(repository: T) -> AuditService<T> {
+        AuditService { repository }
+    }
+
+    pub async fn list_events(
+        self,
+        user_id: i32,
+        filter: AuditFilter,
+        page: PageRequest,
+    ) -> AuditResult<List<AuditEventView>> {
+        let (events, total) = self
+            .repository
+            .audit_events_list(user_id, filter, page)
+            .await;
+
+        Ok(List {
+            items: events
+                .into_iter()
+                .filter_map(|event| {
+                    Some(AuditEventView {
+                        id: event.id,
+                        event_type: event.event_type.parse::<AuditEventType>().ok()?,
+                        target: event.target,
+                        device: event.device,
+                        ip_address: event.ip_address,
+                        created_date: Utc.from_utc_datetime(&event.created_date).to_rfc3339(),
+                    })
+                })
+                .collect(),
+            total: total as u32,
+            pagination: Some(Pagination {
+                page: page.page as u32,
+                limit: page.limit as u32,
+                pages: total.div_ceil(page.limit) as u32,
+            }),
+        })
+    }
+}
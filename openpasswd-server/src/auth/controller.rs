@@ -4,7 +4,13 @@ use super::{
 };
 use crate::{
     auth::dto::refresh_token::{RefreshTokenClaims, REFRESH_TOKEN_COOKIE_NAME},
-    core::{cache::Cache, validator::ValidatedJson},
+    core::{
+        audit::RequestOrigin,
+        breached_passwords::{breach_headers, BreachChecker},
+        cache::Cache,
+        password_policy::PasswordPolicy,
+        validator::ValidatedJson,
+    },
     repository::Repository,
 };
 use axum::{
@@ -14,7 +20,8 @@ use axum::{
 };
 
 use model::auth::{
-    LoginRequest, PasswordRecoveryFinish, PasswordRecoveryStart, RefreshTokenType, UserRegister,
+    ElevationRequest, LoginRequest, PasswordChange, PasswordRecoveryFinish, PasswordRecoveryStart,
+    RefreshTokenType, UserRegister,
 };
 
 fn get_refresh_token_cookie(refresh_token: &str) -> String {
@@ -30,68 +37,88 @@ fn get_refresh_token_cookie(refresh_token: &str) -> String {
 }
 
 pub async fn token(
+    origin: RequestOrigin,
     ValidatedJson(login): ValidatedJson<LoginRequest>,
     Extension(repository): Extension<Repository>,
     Extension(cache): Extension<Cache>,
 ) -> AuthResult<impl IntoResponse> {
     let auth_service = AuthService::new(repository, cache);
-    let mut access_token = auth_service.login(&login).await?;
+    let mut access_token = auth_service.login(&login, origin).await?;
 
     let mut headers = HeaderMap::new();
-    match login.refresh_token {
-        Some(RefreshTokenType::Cookie) => {
-            if let Some(refresh_token) = access_token.refresh_token.take() {
-                let cookie = get_refresh_token_cookie(&refresh_token);
-                headers.insert(SET_COOKIE, cookie.parse().unwrap());
-            }
+    if let Some(RefreshTokenType::Cookie) = login.refresh_token {
+        if let Some(refresh_token) = access_token.refresh_token.take() {
+            let cookie = get_refresh_token_cookie(&refresh_token);
+            headers.insert(SET_COOKIE, cookie.parse().unwrap());
         }
-        _ => (),
     }
 
     Ok((StatusCode::OK, headers, Json(access_token)))
 }
 
 pub async fn refresh_token(
+    origin: RequestOrigin,
     refresh_token: RefreshTokenClaims,
     Extension(repository): Extension<Repository>,
     Extension(cache): Extension<Cache>,
 ) -> AuthResult<impl IntoResponse> {
     let auth_service = AuthService::new(repository, cache);
-    let mut access_token = auth_service.refresh_token(&refresh_token).await?;
+    let mut access_token = auth_service.refresh_token(&refresh_token, origin).await?;
 
     let mut headers = HeaderMap::new();
-    match refresh_token.refresh_token_type {
-        RefreshTokenType::Cookie => {
-            if let Some(refresh_token) = access_token.refresh_token.take() {
-                let cookie = get_refresh_token_cookie(&refresh_token);
-                headers.insert(SET_COOKIE, cookie.parse().unwrap());
-            }
+    if refresh_token.refresh_token_type == RefreshTokenType::Cookie {
+        if let Some(refresh_token) = access_token.refresh_token.take() {
+            let cookie = get_refresh_token_cookie(&refresh_token);
+            headers.insert(SET_COOKIE, cookie.parse().unwrap());
         }
-        _ => (),
     }
 
     Ok((StatusCode::OK, headers, Json(access_token)))
 }
 
+pub async fn elevate(
+    origin: RequestOrigin,
+    claims: Claims,
+    ValidatedJson(elevation): ValidatedJson<ElevationRequest>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AuthResult<impl IntoResponse> {
+    let auth_service = AuthService::new(repository, cache);
+    let elevated_token = auth_service
+        .elevate(
+            &claims,
+            &elevation,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(elevated_token)))
+}
+
 pub async fn logout(
+    origin: RequestOrigin,
     claims: Claims,
     refresh_token: Option<RefreshTokenClaims>,
     Extension(repository): Extension<Repository>,
     Extension(cache): Extension<Cache>,
 ) -> AuthResult<impl IntoResponse> {
     let auth_service = AuthService::new(repository, cache);
-    auth_service.logout(claims, refresh_token).await?;
+    auth_service.logout(claims, refresh_token, origin).await?;
     Ok(StatusCode::OK)
 }
 
 pub async fn register(
+    origin: RequestOrigin,
     ValidatedJson(user): ValidatedJson<UserRegister>,
     Extension(repository): Extension<Repository>,
     Extension(cache): Extension<Cache>,
+    Extension(policy): Extension<PasswordPolicy>,
+    Extension(breaches): Extension<BreachChecker>,
 ) -> AuthResult<impl IntoResponse> {
     let auth_service = AuthService::new(repository, cache);
-    auth_service.register(user).await?;
-    Ok(StatusCode::CREATED)
+    let breached = auth_service
+        .register(user, &policy, &breaches, origin)
+        .await?;
+    Ok((StatusCode::CREATED, breach_headers(breached)))
 }
 
 pub async fn get_me(
@@ -106,21 +133,51 @@ pub async fn get_me(
 }
 
 pub async fn password_recovery_start(
+    origin: RequestOrigin,
     ValidatedJson(pass_recovery): ValidatedJson<PasswordRecoveryStart>,
     Extension(repository): Extension<Repository>,
     Extension(cache): Extension<Cache>,
 ) -> AuthResult<impl IntoResponse> {
     let auth_service = AuthService::new(repository, cache);
-    auth_service.password_recovery_start(pass_recovery).await?;
+    auth_service
+        .password_recovery_start(pass_recovery, origin)
+        .await?;
     Ok(StatusCode::CREATED.into_response())
 }
 
 pub async fn password_recovery_finish(
+    origin: RequestOrigin,
     ValidatedJson(pass_recovery): ValidatedJson<PasswordRecoveryFinish>,
     Extension(repository): Extension<Repository>,
     Extension(cache): Extension<Cache>,
+    Extension(policy): Extension<PasswordPolicy>,
+    Extension(breaches): Extension<BreachChecker>,
+) -> AuthResult<impl IntoResponse> {
+    let auth_service = AuthService::new(repository, cache);
+    let breached = auth_service
+        .password_recovery_finish(pass_recovery, &policy, &breaches, origin)
+        .await?;
+    Ok((StatusCode::OK, breach_headers(breached)))
+}
+
+pub async fn change_password(
+    claims: Claims,
+    origin: RequestOrigin,
+    ValidatedJson(change): ValidatedJson<PasswordChange>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+    Extension(policy): Extension<PasswordPolicy>,
+    Extension(breaches): Extension<BreachChecker>,
 ) -> AuthResult<impl IntoResponse> {
     let auth_service = AuthService::new(repository, cache);
-    auth_service.password_recovery_finish(pass_recovery).await?;
-    Ok(StatusCode::OK.into_response())
+    let breached = auth_service
+        .change_password(
+            claims.sub,
+            change,
+            &policy,
+            &breaches,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    Ok((StatusCode::OK, breach_headers(breached)))
 }
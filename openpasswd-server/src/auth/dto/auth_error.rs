@@ -7,6 +7,8 @@ use axum::{
 };
 use model::error::ErrorResponse;
 
+use crate::core::{breached_passwords::breached_response, validator::ServerError};
+
 pub type AuthResult<T = ()> = Result<T, AuthError>;
 
 #[allow(dead_code)]
@@ -23,6 +25,9 @@ pub enum AuthError {
     JwtEncode(String),
     // Create
     EmailAlreadyTaken,
+    // Password policy
+    InvalidPassword(validator::ValidationErrors),
+    PasswordBreached(u64),
     // Password Recovery
     UserNotFound,
 }
@@ -57,6 +62,11 @@ impl IntoResponse for AuthError {
                 StatusCode::BAD_REQUEST,
                 String::from("Email already in use"),
             ),
+            // Password policy
+            AuthError::InvalidPassword(e) => {
+                return ServerError::ValidationError(e).into_response()
+            }
+            AuthError::PasswordBreached(count) => return breached_response(count),
             // Password Recovery
             AuthError::UserNotFound => (StatusCode::BAD_REQUEST, String::from("User not Found")),
         };
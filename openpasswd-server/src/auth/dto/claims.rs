@@ -50,7 +50,7 @@ where
         );
 
         match cache.get::<i32>(&key).await {
-            Some(valid_token) if valid_token == 1 => Ok(token_data.claims),
+            Some(1) => Ok(token_data.claims),
             Some(_) => Err(AuthError::InvalidToken),
             None => Err(AuthError::InvalidToken),
         }
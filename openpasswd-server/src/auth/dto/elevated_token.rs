@@ -0,0 +1,57 @@
+use axum::{
+    async_trait,
+    extract::{FromRequest, RequestParts},
+};
+use model::auth::ELEVATED_TOKEN_HEADER;
+
+use crate::core::cache::Cache;
+
+use super::auth_error::AuthError;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ElevatedClaims {
+    pub jti: String,
+    pub sub: i32,
+    pub exp: i64,
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for ElevatedClaims
+where
+    B: Send,
+{
+    type Rejection = AuthError;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let cache = req
+            .extensions()
+            .get::<Cache>()
+            .ok_or(AuthError::MissingStorage)?
+            .clone();
+
+        let token = req
+            .headers()
+            .get(ELEVATED_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(AuthError::MissingCredentials)?;
+
+        let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        let token_data = jsonwebtoken::decode::<ElevatedClaims>(
+            token,
+            &jsonwebtoken::DecodingKey::from_secret(secret.as_bytes()),
+            &jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS512),
+        )
+        .map_err(|_| AuthError::InvalidToken)?;
+
+        let key = format!(
+            "elevated_token:{}:{}",
+            token_data.claims.sub, token_data.claims.jti
+        );
+
+        match cache.get::<i32>(&key).await {
+            Some(1) => Ok(token_data.claims),
+            Some(_) => Err(AuthError::InvalidToken),
+            None => Err(AuthError::InvalidToken),
+        }
+    }
+}
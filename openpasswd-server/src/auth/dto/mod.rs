@@ -1,3 +1,4 @@
 pub mod auth_error;
 pub mod claims;
+pub mod elevated_token;
 pub mod refresh_token;
@@ -77,7 +77,7 @@ where
         );
 
         match cache.get::<i32>(&key).await {
-            Some(valid_token) if valid_token == 1 => {
+            Some(1) => {
                 cache.set_keepttl(&key, 0).await;
                 Ok(token_data.claims)
             }
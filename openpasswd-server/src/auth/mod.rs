@@ -1,5 +1,5 @@
 use axum::{
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 
@@ -13,11 +13,16 @@ pub fn route() -> Router {
             "/api/auth/user",
             get(self::controller::get_me).post(self::controller::register),
         )
+        .route(
+            "/api/auth/user/password",
+            put(self::controller::change_password),
+        )
         .route("/api/auth/token", post(self::controller::token))
         .route(
             "/api/auth/refresh_token",
             post(self::controller::refresh_token),
         )
+        .route("/api/auth/elevate", post(self::controller::elevate))
         .route("/api/auth/logout", post(self::controller::logout))
         .route(
             "/api/auth/password_recovery",
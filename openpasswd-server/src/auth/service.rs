@@ -1,25 +1,35 @@
 use super::dto::auth_error::{AuthError, AuthResult};
 use super::dto::claims::Claims;
+use super::dto::elevated_token::ElevatedClaims;
 use super::dto::refresh_token::RefreshTokenClaims;
+use crate::core::audit::{self, RequestOrigin};
+use crate::core::breached_passwords::BreachChecker;
 use crate::core::cache::Cache;
+use crate::core::cryptography::{hash_password, AesGcmCipher, Cipher, RsaKeyPair};
 use crate::core::mail_service::{EmailAddress, MailService, MessageBody};
+use crate::core::notifications;
+use crate::core::password_policy::PasswordPolicy;
 use crate::repository::models::user::NewUser;
 use crate::repository::models::user_password_recovery::NewUserPasswordRecovery;
+use crate::repository::repositories::audit_repository::AuditRepository;
 use crate::repository::repositories::devices_repository::DevicesRepository;
 use crate::repository::repositories::users_repository::UsersRepository;
+use crate::repository::repositories::webhooks_repository::WebhooksRepository;
 use chrono::{TimeZone, Utc};
 use entity::users::Model as User;
+use model::audit::AuditEventType;
 use model::auth::{
-    AccessToken, LoginRequest, PasswordRecoveryFinish, PasswordRecoveryStart, RefreshTokenType,
-    UserRegister, UserView,
+    AccessToken, ElevatedToken, ElevationRequest, LoginRequest, PasswordChange,
+    PasswordRecoveryFinish, PasswordRecoveryStart, RefreshTokenType, UserRegister, UserView,
 };
+use model::notifications::{Notification, NotificationEvent};
 use rand::distributions::Alphanumeric;
 use rand::Rng;
 use sha2::{Digest, Sha256};
 
 pub struct AuthService<T>
 where
-    T: UsersRepository + DevicesRepository,
+    T: UsersRepository + DevicesRepository + AuditRepository + WebhooksRepository,
 {
     repository: T,
     cache: Cache,
@@ -27,14 +37,14 @@ where
 
 impl<T> AuthService<T>
 where
-    T: UsersRepository + DevicesRepository,
+    T: UsersRepository + DevicesRepository + AuditRepository + WebhooksRepository,
 {
     pub fn new(repository: T, cache: Cache) -> AuthService<T> {
         AuthService { repository, cache }
     }
 
     fn verify_password(&self, hash_password: &str, password: &str) -> bool {
-        argon2::verify_encoded(&hash_password, password.as_bytes()).unwrap()
+        argon2::verify_encoded(hash_password, password.as_bytes()).unwrap()
     }
 
     async fn verify_user_password(&self, login_password: &str, user: &User) -> AuthResult {
@@ -48,6 +58,25 @@ where
         }
     }
 
+    /// Applies the password policy, then the breach check. The breach count
+    /// is returned when the password was accepted with a warning.
+    async fn check_password_policy(
+        &self,
+        policy: &PasswordPolicy,
+        breaches: &BreachChecker,
+        password: &str,
+        name: &str,
+        email: &str,
+    ) -> AuthResult<Option<u64>> {
+        policy
+            .check("password", password, name, email)
+            .map_err(AuthError::InvalidPassword)?;
+        breaches
+            .check_new_password(password)
+            .await
+            .map_err(AuthError::PasswordBreached)
+    }
+
     async fn find_device_name(&self, login: &LoginRequest, user: &User) -> Option<String> {
         if let Some(device_name) = login.device_name.as_ref() {
             self.repository
@@ -126,7 +155,7 @@ where
         refresh_token_type: Option<&RefreshTokenType>,
     ) -> AuthResult<AccessToken> {
         let expire_at = chrono::Duration::minutes(1);
-        let (access_token, jti) = self.sign_access_token(&user, device_name.clone(), expire_at)?;
+        let (access_token, jti) = self.sign_access_token(user, device_name.clone(), expire_at)?;
         let key = format!("access_token:{}:{}", user.id, jti);
         self.cache
             .set_and_expire(&key, 1, expire_at.num_seconds() as usize)
@@ -135,7 +164,7 @@ where
         let refresh_token = if let Some(refresh_token_type) = refresh_token_type {
             let expire_at = chrono::Duration::minutes(5);
             let (refresh_token, jti) =
-                self.sign_refresh_token(&user, device_name, expire_at, refresh_token_type)?;
+                self.sign_refresh_token(user, device_name, expire_at, refresh_token_type)?;
 
             let key = format!("refresh_token:{}:{}", user.id, jti);
             self.cache
@@ -155,17 +184,48 @@ where
         Ok(token)
     }
 
-    pub async fn login(self, login: &LoginRequest) -> AuthResult<AccessToken> {
+    pub async fn login(
+        self,
+        login: &LoginRequest,
+        origin: RequestOrigin,
+    ) -> AuthResult<AccessToken> {
+        let origin = origin.with_device(login.device_name.clone());
         let user = match self.repository.users_find_by_email(&login.email).await {
             Some(user) => user,
-            None => return Err(AuthError::InvalidCredentials),
+            None => {
+                audit::record(
+                    &self.repository,
+                    None,
+                    AuditEventType::LoginFailed,
+                    Some(format!("email:{}", login.email)),
+                    &origin,
+                )
+                .await;
+                return Err(AuthError::InvalidCredentials);
+            }
         };
 
         // TODO: count wrong passwords
 
-        self.verify_user_password(&login.password, &user).await?;
+        if let Err(e) = self.verify_user_password(&login.password, &user).await {
+            audit::record(
+                &self.repository,
+                Some(user.id),
+                AuditEventType::LoginFailed,
+                None,
+                &origin,
+            )
+            .await;
+            return Err(e);
+        }
 
-        let device_name = self.find_device_name(&login, &user).await;
+        let device_name = self.find_device_name(login, &user).await;
+        let event_type = if login.device_name.is_some() && device_name.is_none() {
+            AuditEventType::LoginNewDevice
+        } else {
+            AuditEventType::Login
+        };
+        audit::record(&self.repository, Some(user.id), event_type, None, &origin).await;
 
         self.repository.users_update_last_login(user.id).await;
 
@@ -173,9 +233,57 @@ where
             .await
     }
 
+    pub async fn elevate(
+        self,
+        claims: &Claims,
+        elevation: &ElevationRequest,
+        origin: RequestOrigin,
+    ) -> AuthResult<ElevatedToken> {
+        let user = match self.repository.users_find_by_id(claims.sub).await {
+            Some(user) => user,
+            None => return Err(AuthError::WrongCredentials),
+        };
+
+        let verified = self.verify_user_password(&elevation.password, &user).await;
+        let event_type = if verified.is_ok() {
+            AuditEventType::Elevated
+        } else {
+            AuditEventType::ElevationFailed
+        };
+        audit::record(&self.repository, Some(user.id), event_type, None, &origin).await;
+        verified?;
+
+        let expire_at = chrono::Duration::minutes(5);
+        let exp = chrono::Utc::now()
+            .checked_add_signed(expire_at)
+            .expect("valid timestamp")
+            .timestamp();
+
+        let jti = uuid::Uuid::new_v4().to_string();
+        let elevated_claims = ElevatedClaims {
+            jti: jti.clone(),
+            sub: user.id,
+            exp,
+        };
+
+        let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        let elevated_token = self.sign_token(elevated_claims, secret)?;
+
+        let key = format!("elevated_token:{}:{}", user.id, jti);
+        self.cache
+            .set_and_expire(&key, 1, expire_at.num_seconds() as usize)
+            .await;
+
+        Ok(ElevatedToken {
+            elevated_token,
+            expires_in: expire_at.num_seconds(),
+        })
+    }
+
     pub async fn refresh_token(
         self,
         refresh_token_claims: &RefreshTokenClaims,
+        origin: RequestOrigin,
     ) -> AuthResult<AccessToken> {
         let user = match self
             .repository
@@ -185,6 +293,14 @@ where
             Some(user) => user,
             None => return Err(AuthError::InvalidCredentials),
         };
+        audit::record(
+            &self.repository,
+            Some(user.id),
+            AuditEventType::TokenRefreshed,
+            None,
+            &origin.with_device(refresh_token_claims.device.clone()),
+        )
+        .await;
         self.get_token(
             &user,
             refresh_token_claims.device.clone(),
@@ -198,20 +314,33 @@ where
         (&mut rng).sample_iter(Alphanumeric).take(size).collect()
     }
 
-    pub fn hash_password(password: String) -> String {
-        let salt = Self::generate_string_vec_u8(12);
-        let config = argon2::Config::default();
-
-        argon2::hash_encoded(password.as_bytes(), &salt, &config).unwrap()
-    }
-
     pub async fn logout(
         self,
         claims: Claims,
         refresh_token: Option<RefreshTokenClaims>,
+        origin: RequestOrigin,
     ) -> AuthResult {
+        audit::record(
+            &self.repository,
+            Some(claims.sub),
+            AuditEventType::Logout,
+            None,
+            &origin.with_device(claims.device.clone()),
+        )
+        .await;
+
         let key = format!("access_token:{}:{}", claims.sub, claims.jti);
         self.cache.set_keepttl(&key, 0).await;
+        notifications::publish(
+            &self.cache,
+            &[claims.sub],
+            Notification {
+                event: NotificationEvent::SessionRevoked,
+                id: None,
+                session: Some(claims.jti.to_owned()),
+            },
+        )
+        .await;
 
         if let Some(refresh_token) = refresh_token {
             let key = format!("refresh_token:{}:{}", refresh_token.sub, refresh_token.jti);
@@ -220,7 +349,15 @@ where
         Ok(())
     }
 
-    pub async fn register(self, user: UserRegister) -> Result<(), AuthError> {
+    /// Returns the breach count of the password when it was accepted with a
+    /// warning.
+    pub async fn register(
+        self,
+        user: UserRegister,
+        policy: &PasswordPolicy,
+        breaches: &BreachChecker,
+        origin: RequestOrigin,
+    ) -> Result<Option<u64>, AuthError> {
         if self
             .repository
             .users_find_by_email(&user.email)
@@ -236,20 +373,38 @@ where
             password,
         } = user;
 
-        let password = Self::hash_password(password);
+        let breached = self
+            .check_password_policy(policy, breaches, &password, &name, &email)
+            .await?;
+        let password = hash_password(password);
 
         let id = uuid::Uuid::new_v4();
         let master_key = id.simple().to_string();
 
+        let key_pair = tokio::task::spawn_blocking(RsaKeyPair::generate)
+            .await
+            .unwrap();
+        let private_key = AesGcmCipher::new(&master_key).encrypt(&key_pair.private_key);
+
         let new_user = NewUser {
             name,
             email,
             password,
             master_key: Some(master_key),
+            public_key: Some(key_pair.public_key),
+            private_key: Some(private_key),
         };
 
-        self.repository.users_insert(new_user).await;
-        Ok(())
+        let user = self.repository.users_insert(new_user).await;
+        audit::record(
+            &self.repository,
+            Some(user.id),
+            AuditEventType::Registered,
+            None,
+            &origin,
+        )
+        .await;
+        Ok(breached)
     }
 
     pub async fn get_me(self, id: i32) -> Result<UserView, AuthError> {
@@ -275,6 +430,7 @@ where
     pub async fn password_recovery_start(
         self,
         pass_recovery: PasswordRecoveryStart,
+        origin: RequestOrigin,
     ) -> Result<(), AuthError> {
         let user = match self
             .repository
@@ -302,6 +458,14 @@ where
         self.repository
             .users_password_recovery_insert(user_password_recovery)
             .await;
+        audit::record(
+            &self.repository,
+            Some(user.id),
+            AuditEventType::PasswordRecoveryRequested,
+            None,
+            &origin,
+        )
+        .await;
 
         MailService::send_email(
             EmailAddress::new(Some("OpenPasswd"), "openpasswd@gmail.com"),
@@ -325,10 +489,49 @@ where
         format!("{:x}", hash_token)
     }
 
+    /// Returns the breach count of the password when it was accepted with a
+    /// warning.
+    pub async fn change_password(
+        self,
+        user_id: i32,
+        change: PasswordChange,
+        policy: &PasswordPolicy,
+        breaches: &BreachChecker,
+        origin: RequestOrigin,
+    ) -> AuthResult<Option<u64>> {
+        let user = match self.repository.users_find_by_id(user_id).await {
+            Some(user) => user,
+            None => return Err(AuthError::WrongCredentials),
+        };
+        self.verify_user_password(&change.current_password, &user)
+            .await?;
+        let breached = self
+            .check_password_policy(policy, breaches, &change.password, &user.name, &user.email)
+            .await?;
+
+        self.repository
+            .users_update_password(user.id, hash_password(change.password))
+            .await;
+        audit::record(
+            &self.repository,
+            Some(user.id),
+            AuditEventType::PasswordChanged,
+            None,
+            &origin,
+        )
+        .await;
+        Ok(breached)
+    }
+
+    /// Returns the breach count of the password when it was accepted with a
+    /// warning. A policy violation leaves the token valid for another try.
     pub async fn password_recovery_finish(
         self,
         pass_recovery: PasswordRecoveryFinish,
-    ) -> Result<(), AuthError> {
+        policy: &PasswordPolicy,
+        breaches: &BreachChecker,
+        origin: RequestOrigin,
+    ) -> AuthResult<Option<u64>> {
         let token = self.hash(&pass_recovery.token);
 
         let user_password_recovery = match self
@@ -339,7 +542,7 @@ where
             Some(user_password_recovery) => user_password_recovery,
             None => {
                 log::warn!("User not found");
-                return Ok(());
+                return Ok(None);
             }
         };
 
@@ -347,16 +550,39 @@ where
             && user_password_recovery.issued_at + chrono::Duration::minutes(5)
                 > chrono::Utc::now().naive_utc()
         {
-            let password = Self::hash_password(pass_recovery.password);
+            let user = self
+                .repository
+                .users_find_by_id(user_password_recovery.user_id)
+                .await
+                .ok_or(AuthError::UserNotFound)?;
+            let breached = self
+                .check_password_policy(
+                    policy,
+                    breaches,
+                    &pass_recovery.password,
+                    &user.name,
+                    &user.email,
+                )
+                .await?;
+            let password = hash_password(pass_recovery.password);
             self.repository
                 .users_password_recovery_invalide(token)
                 .await;
             self.repository
                 .users_update_password(user_password_recovery.user_id, password)
                 .await;
+            audit::record(
+                &self.repository,
+                Some(user_password_recovery.user_id),
+                AuditEventType::PasswordRecovered,
+                None,
+                &origin,
+            )
+            .await;
+            Ok(breached)
         } else {
             log::warn!("Invalid password recovery token");
+            Ok(None)
         }
-        Ok(())
     }
 }
@@ -0,0 +1,101 @@
+use super::webhooks;
+use crate::repository::models::audit::NewAuditEvent;
+use crate::repository::repositories::audit_repository::AuditRepository;
+use crate::repository::repositories::webhooks_repository::WebhooksRepository;
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequest, RequestParts},
+};
+use chrono::{TimeZone, Utc};
+use model::audit::AuditEventType;
+use model::webhooks::WebhookEvent;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+/// Where a request came from. The device is only known once the caller is
+/// authenticated, so handlers fill it in from the claims.
+#[derive(Clone, Debug, Default)]
+pub struct RequestOrigin {
+    pub device: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl RequestOrigin {
+    pub fn with_device(self, device: Option<String>) -> RequestOrigin {
+        RequestOrigin { device, ..self }
+    }
+}
+
+#[async_trait]
+impl<B> FromRequest<B> for RequestOrigin
+where
+    B: Send,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        // X-Forwarded-For can be set by anyone, only trust it behind a proxy
+        // that overwrites it.
+        let forwarded_for = if trust_proxy_headers() {
+            req.headers()
+                .get("x-forwarded-for")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next())
+                .map(|ip| ip.trim().to_owned())
+                .filter(|ip| !ip.is_empty())
+        } else {
+            None
+        };
+
+        let ip_address = forwarded_for.or_else(|| {
+            req.extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string())
+        });
+
+        Ok(RequestOrigin {
+            device: None,
+            ip_address,
+        })
+    }
+}
+
+fn trust_proxy_headers() -> bool {
+    std::env::var("TRUST_PROXY_HEADERS")
+        .map(|value| value == "true")
+        .unwrap_or(false)
+}
+
+/// Appends the event to the audit log and queues it for the webhooks of the
+/// user and their organizations.
+pub async fn record<T: AuditRepository + WebhooksRepository>(
+    repository: &T,
+    user_id: Option<i32>,
+    event_type: AuditEventType,
+    target: Option<String>,
+    origin: &RequestOrigin,
+) {
+    let created_date = Utc::now().naive_utc();
+    repository
+        .audit_events_insert(NewAuditEvent {
+            user_id,
+            event_type: event_type.as_str().to_owned(),
+            target: target.clone(),
+            device: origin.device.clone(),
+            ip_address: origin.ip_address.clone(),
+            created_date,
+        })
+        .await;
+
+    if let Some(user_id) = user_id {
+        let event = WebhookEvent {
+            event_type: event_type.as_str().to_owned(),
+            user_id,
+            target,
+            device: origin.device.clone(),
+            ip_address: origin.ip_address.clone(),
+            created_date: Utc.from_utc_datetime(&created_date).to_rfc3339(),
+        };
+        webhooks::enqueue(repository, event_type, &event).await;
+    }
+}
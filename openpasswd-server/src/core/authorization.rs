@@ -0,0 +1,364 @@
+use crate::repository::repositories::accounts_repository::AccountsRepository;
+use crate::repository::repositories::authorization_repository::AuthorizationRepository;
+use crate::repository::repositories::shares_repository::SharesRepository;
+use model::accounts::SharePermission;
+use model::organizations::{CollectionPermission, OrganizationRole};
+
+#[derive(Debug)]
+pub enum AuthorizationError {
+    NotFound,
+    Forbidden,
+}
+
+pub type AuthorizationResult<T> = Result<T, AuthorizationError>;
+
+pub struct GroupAccess {
+    pub group: entity::account_groups::Model,
+    pub permission: CollectionPermission,
+}
+
+pub struct AccountAccess {
+    pub account: entity::accounts::Model,
+    pub group: entity::account_groups::Model,
+    pub permission: CollectionPermission,
+    /// Set when the account is only reachable through a direct share, its
+    /// item key is then wrapped for the user instead of the group.
+    pub share: Option<entity::account_shares::Model>,
+}
+
+/// Resolves what a user may do with groups and accounts. Personal groups are
+/// fully managed by their owner, organization collections follow the member
+/// role and collection assignment, and directly shared accounts follow the
+/// share permission.
+pub struct Authorizer<T>
+where
+    T: AccountsRepository + AuthorizationRepository + SharesRepository,
+{
+    repository: T,
+}
+
+impl<T> Authorizer<T>
+where
+    T: AccountsRepository + AuthorizationRepository + SharesRepository,
+{
+    pub fn new(repository: T) -> Authorizer<T> {
+        Authorizer { repository }
+    }
+
+    pub async fn groups(&self, user_id: i32) -> Vec<GroupAccess> {
+        let personal_groups = self.repository.authorization_personal_groups(user_id).await;
+        let memberships = self.repository.authorization_memberships(user_id).await;
+        let assignments = self
+            .repository
+            .authorization_collection_members(user_id)
+            .await;
+        let organization_groups = self
+            .repository
+            .authorization_organization_groups(organization_ids(&memberships))
+            .await;
+
+        resolve_groups(
+            personal_groups,
+            organization_groups,
+            &memberships,
+            &assignments,
+        )
+    }
+
+    /// Same as `groups` for the groups in the trash, resolved against the
+    /// current memberships rather than whoever created the group.
+    pub async fn trashed_groups(&self, user_id: i32) -> Vec<GroupAccess> {
+        let personal_groups = self
+            .repository
+            .authorization_trashed_personal_groups(user_id)
+            .await;
+        let memberships = self.repository.authorization_memberships(user_id).await;
+        let assignments = self
+            .repository
+            .authorization_collection_members(user_id)
+            .await;
+        let organization_groups = self
+            .repository
+            .authorization_trashed_organization_groups(organization_ids(&memberships))
+            .await;
+
+        resolve_groups(
+            personal_groups,
+            organization_groups,
+            &memberships,
+            &assignments,
+        )
+    }
+
+    pub async fn group(
+        &self,
+        user_id: i32,
+        group_id: i32,
+        required: CollectionPermission,
+    ) -> AuthorizationResult<GroupAccess> {
+        let access = self
+            .groups(user_id)
+            .await
+            .into_iter()
+            .find(|access| access.group.id == group_id)
+            .ok_or(AuthorizationError::NotFound)?;
+
+        if access.permission < required {
+            return Err(AuthorizationError::Forbidden);
+        }
+        Ok(access)
+    }
+
+    pub async fn account(
+        &self,
+        user_id: i32,
+        account_id: i32,
+        required: CollectionPermission,
+    ) -> AuthorizationResult<AccountAccess> {
+        let account = self
+            .repository
+            .accounts_find_by_id(account_id)
+            .await
+            .ok_or(AuthorizationError::NotFound)?;
+
+        let group_access = self
+            .groups(user_id)
+            .await
+            .into_iter()
+            .find(|access| access.group.id == account.account_groups_id);
+        let share = self.repository.shares_find(account_id, user_id).await;
+        let share_permission = share
+            .as_ref()
+            .and_then(|share| share.permission.parse::<SharePermission>().ok())
+            .map(share_permission);
+
+        let access = match (group_access, share) {
+            (Some(group_access), _) => AccountAccess {
+                account,
+                group: group_access.group,
+                permission: group_access
+                    .permission
+                    .max(share_permission.unwrap_or(CollectionPermission::ViewWithoutPassword)),
+                share: None,
+            },
+            (None, Some(share)) => {
+                let group = self
+                    .repository
+                    .accounts_groups_find_by_id(account.account_groups_id)
+                    .await
+                    .ok_or(AuthorizationError::NotFound)?;
+                AccountAccess {
+                    account,
+                    group,
+                    permission: share_permission
+                        .unwrap_or(CollectionPermission::ViewWithoutPassword),
+                    share: Some(share),
+                }
+            }
+            (None, None) => return Err(AuthorizationError::NotFound),
+        };
+
+        if access.permission < required {
+            return Err(AuthorizationError::Forbidden);
+        }
+        Ok(access)
+    }
+}
+
+fn organization_ids(memberships: &[entity::organization_members::Model]) -> Vec<i32> {
+    memberships
+        .iter()
+        .map(|member| member.organization_id)
+        .collect()
+}
+
+/// Permission of the user over each group, dropping the organization groups
+/// the memberships and assignments don't reach.
+fn resolve_groups(
+    personal_groups: Vec<entity::account_groups::Model>,
+    organization_groups: Vec<entity::account_groups::Model>,
+    memberships: &[entity::organization_members::Model],
+    assignments: &[entity::collection_members::Model],
+) -> Vec<GroupAccess> {
+    let mut groups: Vec<GroupAccess> = personal_groups
+        .into_iter()
+        .map(|group| GroupAccess {
+            group,
+            permission: CollectionPermission::Manage,
+        })
+        .collect();
+
+    for group in organization_groups {
+        let role = memberships
+            .iter()
+            .find(|member| Some(member.organization_id) == group.organization_id)
+            .and_then(|member| member.role.parse::<OrganizationRole>().ok());
+        let assigned = assignments
+            .iter()
+            .find(|assignment| assignment.account_groups_id == group.id)
+            .map(|assignment| {
+                assignment
+                    .permission
+                    .parse()
+                    .unwrap_or(CollectionPermission::ViewWithoutPassword)
+            });
+
+        if let Some(permission) = role.and_then(|role| collection_permission(role, assigned)) {
+            groups.push(GroupAccess { group, permission });
+        }
+    }
+
+    groups
+}
+
+/// Owners and admins run the organization: members, invitations and every
+/// collection.
+pub fn can_administer(role: OrganizationRole) -> bool {
+    matches!(role, OrganizationRole::Owner | OrganizationRole::Admin)
+}
+
+/// Effective permission of a member over a collection, `assigned` being the
+/// permission of the collection assignment, if any.
+pub fn collection_permission(
+    role: OrganizationRole,
+    assigned: Option<CollectionPermission>,
+) -> Option<CollectionPermission> {
+    match role {
+        OrganizationRole::Owner | OrganizationRole::Admin => Some(CollectionPermission::Manage),
+        OrganizationRole::Manager => assigned.map(|_| CollectionPermission::Manage),
+        OrganizationRole::Member => assigned,
+        OrganizationRole::ReadOnly => {
+            assigned.map(|permission| permission.min(CollectionPermission::View))
+        }
+    }
+}
+
+/// Users with at least `permission` on the group: its owner for a personal
+/// group, the members allowed on the collection otherwise.
+pub async fn group_members<T: AuthorizationRepository>(
+    repository: &T,
+    group: &entity::account_groups::Model,
+    permission: CollectionPermission,
+) -> Vec<i32> {
+    let organization_id = match group.organization_id {
+        Some(organization_id) => organization_id,
+        None => return vec![group.user_id],
+    };
+
+    let assignments = repository.authorization_group_assignments(group.id).await;
+    repository
+        .authorization_organization_members(organization_id)
+        .await
+        .into_iter()
+        .filter(|member| {
+            let role = member
+                .role
+                .parse::<OrganizationRole>()
+                .unwrap_or(OrganizationRole::Member);
+            let assigned = assignments
+                .iter()
+                .find(|assignment| assignment.user_id == member.user_id)
+                .map(|assignment| {
+                    assignment
+                        .permission
+                        .parse()
+                        .unwrap_or(CollectionPermission::ViewWithoutPassword)
+                });
+            collection_permission(role, assigned).is_some_and(|allowed| allowed >= permission)
+        })
+        .map(|member| member.user_id)
+        .collect()
+}
+
+fn share_permission(permission: SharePermission) -> CollectionPermission {
+    match permission {
+        SharePermission::ReadOnly => CollectionPermission::View,
+        SharePermission::Edit => CollectionPermission::Edit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collection_permission, resolve_groups};
+    use model::organizations::{CollectionPermission, OrganizationRole};
+
+    fn trashed_collection(created_by: i32) -> entity::account_groups::Model {
+        entity::account_groups::Model {
+            id: 3,
+            user_id: created_by,
+            name: String::from("Infrastructure"),
+            deleted_at: Some(chrono::Utc::now().naive_utc()),
+            organization_id: Some(7),
+            revision: 1,
+            rotation_days: None,
+        }
+    }
+
+    fn membership(user_id: i32, role: &str) -> entity::organization_members::Model {
+        entity::organization_members::Model {
+            organization_id: 7,
+            user_id,
+            role: String::from(role),
+            org_key: Vec::new(),
+            created_date: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn admins_manage_every_collection() {
+        assert_eq!(
+            Some(CollectionPermission::Manage),
+            collection_permission(OrganizationRole::Admin, None)
+        );
+    }
+
+    #[test]
+    fn members_need_an_assignment() {
+        assert_eq!(None, collection_permission(OrganizationRole::Member, None));
+        assert_eq!(
+            Some(CollectionPermission::Edit),
+            collection_permission(OrganizationRole::Member, Some(CollectionPermission::Edit))
+        );
+        assert_eq!(
+            Some(CollectionPermission::Manage),
+            collection_permission(OrganizationRole::Manager, Some(CollectionPermission::View))
+        );
+    }
+
+    #[test]
+    fn read_only_members_never_edit() {
+        assert_eq!(
+            Some(CollectionPermission::View),
+            collection_permission(
+                OrganizationRole::ReadOnly,
+                Some(CollectionPermission::Manage)
+            )
+        );
+        assert_eq!(
+            Some(CollectionPermission::ViewWithoutPassword),
+            collection_permission(
+                OrganizationRole::ReadOnly,
+                Some(CollectionPermission::ViewWithoutPassword)
+            )
+        );
+    }
+
+    #[test]
+    fn admins_manage_collections_they_did_not_create() {
+        let groups = resolve_groups(
+            Vec::new(),
+            vec![trashed_collection(1)],
+            &[membership(2, "admin")],
+            &[],
+        );
+        assert_eq!(1, groups.len());
+        assert_eq!(3, groups[0].group.id);
+        assert_eq!(CollectionPermission::Manage, groups[0].permission);
+    }
+
+    #[test]
+    fn creators_lose_collections_with_their_membership() {
+        let groups = resolve_groups(Vec::new(), vec![trashed_collection(1)], &[], &[]);
+        assert!(groups.is_empty());
+    }
+}
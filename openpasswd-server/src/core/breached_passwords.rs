@@ -0,0 +1,272 @@
+//! Lookup of passwords in a Pwned Passwords dataset. Only the SHA-1 of the
+//! password is used, and only its first five characters leave the server
+//! when a range API is configured (k-anonymity).
+//!
+//! The dataset is either:
+//! - `PWNED_PASSWORDS_PATH` pointing to the file ordered by hash, one
+//!   `HASH:COUNT` per line,
+//! - `PWNED_PASSWORDS_PATH` pointing to a directory of range files named
+//!   after their prefix (`21BD1.txt`), one `SUFFIX:COUNT` per line,
+//! - `PWNED_PASSWORDS_RANGE_URL`, the range API or a local stand-in serving
+//!   the same files, the prefix is appended to the URL.
+//!
+//! `PWNED_PASSWORDS_MODE` is `reject` (default) or `warn`.
+//!
+//! The configuration is read once at startup and shared as an `Extension`.
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use axum::{
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::{auth::PASSWORD_BREACHED_HEADER, error::ErrorResponse};
+use sha1::{Digest, Sha1};
+
+const PREFIX_LENGTH: usize = 5;
+const RANGE_TIMEOUT_SECONDS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreachMode {
+    Reject,
+    Warn,
+}
+
+#[derive(Clone)]
+enum BreachSource {
+    OrderedFile(PathBuf),
+    RangeDirectory(PathBuf),
+    RangeApi(String),
+}
+
+/// An empty variable, as left by `docker-compose.yaml`, counts as unset.
+fn env_non_empty(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+#[derive(Clone)]
+pub struct BreachChecker {
+    source: Option<BreachSource>,
+    mode: BreachMode,
+}
+
+impl BreachChecker {
+    pub fn from_env() -> BreachChecker {
+        let source = match env_non_empty("PWNED_PASSWORDS_PATH") {
+            Some(path) => {
+                let path = PathBuf::from(path);
+                Some(if path.is_dir() {
+                    BreachSource::RangeDirectory(path)
+                } else {
+                    BreachSource::OrderedFile(path)
+                })
+            }
+            None => env_non_empty("PWNED_PASSWORDS_RANGE_URL").map(BreachSource::RangeApi),
+        };
+        let mode = match std::env::var("PWNED_PASSWORDS_MODE").as_deref() {
+            Ok("warn") => BreachMode::Warn,
+            Ok("reject") | Err(_) => BreachMode::Reject,
+            Ok(mode) => {
+                log::warn!("PWNED_PASSWORDS_MODE: unknown mode {mode}");
+                BreachMode::Reject
+            }
+        };
+
+        BreachChecker { source, mode }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Number of times the password appears in the dataset, `None` when no
+    /// dataset is configured or it can't be read.
+    pub async fn breach_count(&self, password: &str) -> Option<u64> {
+        let source = self.source.clone()?;
+        let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+        let (prefix, suffix) = hash.split_at(PREFIX_LENGTH);
+
+        let result = match source {
+            BreachSource::OrderedFile(path) => {
+                let hash = hash.to_owned();
+                tokio::task::spawn_blocking(move || {
+                    let file = File::open(path).map_err(|e| e.to_string())?;
+                    ordered_file_count(BufReader::new(file), &hash).map_err(|e| e.to_string())
+                })
+                .await
+                .unwrap()
+            }
+            BreachSource::RangeDirectory(directory) => {
+                let (prefix, suffix) = (prefix.to_owned(), suffix.to_owned());
+                tokio::task::spawn_blocking(move || {
+                    let range = read_range_file(&directory, &prefix).map_err(|e| e.to_string())?;
+                    Ok(range_count(&range, &suffix))
+                })
+                .await
+                .unwrap()
+            }
+            BreachSource::RangeApi(url) => fetch_range(&url, prefix)
+                .await
+                .map(|range| range_count(&range, suffix)),
+        };
+
+        match result {
+            Ok(count) => Some(count),
+            Err(e) => {
+                log::warn!("breached passwords lookup failed: {e}");
+                None
+            }
+        }
+    }
+
+    /// Checks a password being set. `Err` holds the breach count of a
+    /// password to refuse, `Ok` the one to warn about.
+    pub async fn check_new_password(&self, password: &str) -> Result<Option<u64>, u64> {
+        match self.breach_count(password).await {
+            Some(count) if count > 0 => match self.mode {
+                BreachMode::Reject => Err(count),
+                BreachMode::Warn => Ok(Some(count)),
+            },
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Response headers warning that the password just set was breached.
+pub fn breach_headers(count: Option<u64>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(count) = count {
+        headers.insert(PASSWORD_BREACHED_HEADER, HeaderValue::from(count));
+    }
+    headers
+}
+
+/// Refusal of a breached password, reported on the `password` field like a
+/// validation error.
+pub fn breached_response(count: u64) -> Response {
+    let message =
+        format!("This password appeared {count} times in data breaches, choose another one");
+    let body = Json(ErrorResponse {
+        error: HashMap::from([(String::from("password"), message)]),
+    });
+    (StatusCode::BAD_REQUEST, body).into_response()
+}
+
+fn read_range_file(directory: &Path, prefix: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(directory.join(format!("{prefix}.txt")))
+        .or_else(|_| std::fs::read_to_string(directory.join(prefix)))
+}
+
+async fn fetch_range(url: &str, prefix: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(RANGE_TIMEOUT_SECONDS))
+        .build()
+        .unwrap();
+    let response = client
+        .get(format!("{url}{prefix}"))
+        // Padded answers all have about the same size, the prefix can't be
+        // guessed from the traffic.
+        .header("Add-Padding", "true")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+    response.text().await.map_err(|e| e.to_string())
+}
+
+/// Count of `suffix` in a range answer, padding entries have a count of 0.
+fn range_count(range: &str, suffix: &str) -> u64 {
+    range
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Binary search of `hash` in the dataset ordered by hash. The search is on
+/// byte offsets, each probe reads the first line starting after the offset.
+fn ordered_file_count<R: Read + Seek>(
+    mut reader: BufReader<R>,
+    hash: &str,
+) -> std::io::Result<u64> {
+    let mut low = 0;
+    let mut high = reader.seek(SeekFrom::End(0))?;
+    let mut line = String::new();
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let start = if middle == 0 {
+            reader.seek(SeekFrom::Start(0))?
+        } else {
+            // A line starting right at `middle` is found by skipping the end
+            // of the line before it.
+            reader.seek(SeekFrom::Start(middle - 1))?;
+            let mut skipped = Vec::new();
+            middle - 1 + reader.read_until(b'\n', &mut skipped)? as u64
+        };
+        if start >= high {
+            high = middle;
+            continue;
+        }
+
+        line.clear();
+        let read = reader.read_line(&mut line)? as u64;
+        let (candidate, count) = line.trim().split_once(':').unwrap_or((line.trim(), "0"));
+        match candidate.to_ascii_uppercase().as_str().cmp(hash) {
+            Ordering::Equal => return Ok(count.trim().parse().unwrap_or(0)),
+            Ordering::Less => low = start + read,
+            Ordering::Greater => high = middle,
+        }
+    }
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+
+    use super::{ordered_file_count, range_count};
+
+    #[test]
+    fn counts_in_range_answers() {
+        let range = "0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n\
+                     00D4F6E8FA6EECAD2A3AA415EEC418D38EC:2\r\n\
+                     1E4C9B93F3F0682250B6CF8331B7EE68FD8:3730471\r\n\
+                     FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF:0";
+        assert_eq!(
+            3730471,
+            range_count(range, "1e4c9b93f3f0682250b6cf8331b7ee68fd8")
+        );
+        assert_eq!(0, range_count(range, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"));
+        assert_eq!(0, range_count(range, "0000000000000000000000000000000000A"));
+    }
+
+    #[test]
+    fn binary_search_of_the_ordered_file() {
+        let hashes: Vec<String> = (0..200u32)
+            .map(|i| format!("{:040X}:{}", u128::from(i) * 7919, i + 1))
+            .collect();
+        let file = hashes.join("\r\n");
+
+        for (i, line) in hashes.iter().enumerate() {
+            let hash = line.split_once(':').unwrap().0;
+            let reader = BufReader::new(Cursor::new(file.as_bytes()));
+            assert_eq!(i as u64 + 1, ordered_file_count(reader, hash).unwrap());
+        }
+        for missing in [
+            "0000000000000000000000000000000000000001",
+            "F".repeat(40).as_str(),
+        ] {
+            let reader = BufReader::new(Cursor::new(file.as_bytes()));
+            assert_eq!(0, ordered_file_count(reader, missing).unwrap());
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use deadpool_redis::{Config, Pool, Runtime};
+use deadpool_redis::{Config, Connection, Pool, Runtime};
+use redis::aio::PubSub;
 use redis::{AsyncCommands, FromRedisValue, ToRedisArgs};
 use std::future::Future;
 
@@ -73,6 +74,27 @@ impl Cache {
         let _: () = conn.set_ex(key, value, seconds).await.unwrap();
     }
 
+    pub async fn publish<T>(&self, channel: &str, message: T)
+    where
+        T: ToRedisArgs + Send + Sync,
+    {
+        let mut conn = self.pool.get().await.unwrap();
+        let _: () = conn.publish(channel, message).await.unwrap();
+    }
+
+    /// Takes a connection out of the pool for good, a subscribed connection
+    /// can't run other commands.
+    pub async fn pubsub(&self) -> redis::RedisResult<PubSub> {
+        let conn = self.pool.get().await.map_err(|e| {
+            redis::RedisError::from((
+                redis::ErrorKind::IoError,
+                "Redis pool unavailable",
+                e.to_string(),
+            ))
+        })?;
+        Ok(Connection::take(conn).into_pubsub())
+    }
+
     pub async fn get_or_set<T, F, Fut>(&self, key: &str, f: F) -> T
     where
         T: FromRedisValue + ToRedisArgs + Send + Sync,
@@ -4,8 +4,25 @@ use aes_gcm::{Aes128Gcm, Aes256Gcm, Key, Nonce};
 use rand::distributions::{Alphanumeric, Standard};
 use rand::prelude::Distribution;
 use rand::Rng;
+use rsa::pkcs8::{
+    DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding,
+};
+use rsa::{PaddingScheme, PublicKey, RsaPrivateKey, RsaPublicKey};
 use uuid::Uuid;
 
+const RSA_KEY_BITS: usize = 2048;
+
+/// Argon2 hash of a login password, as stored in `users.password`.
+pub fn hash_password(password: String) -> String {
+    let salt: Vec<u8> = rand::thread_rng()
+        .sample_iter(Alphanumeric)
+        .take(12)
+        .collect();
+    let config = argon2::Config::default();
+
+    argon2::hash_encoded(password.as_bytes(), &salt, &config).unwrap()
+}
+
 pub trait Cipher {
     fn encrypt(&self, value: &str) -> Vec<u8>;
     fn decrypt(&self, value: &[u8]) -> String;
@@ -53,9 +70,91 @@ impl Cipher for AesGcmCipher {
     }
 }
 
+/// Generates a random 32 characters key to be used with `AesGcmCipher`.
+pub fn generate_key() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// PEM encoded RSA key pair, the private key in PKCS#8 and the public key in
+/// SubjectPublicKeyInfo format.
+pub struct RsaKeyPair {
+    pub private_key: String,
+    pub public_key: String,
+}
+
+impl RsaKeyPair {
+    pub fn generate() -> RsaKeyPair {
+        let mut rng = rand::thread_rng();
+        let private_key =
+            RsaPrivateKey::new(&mut rng, RSA_KEY_BITS).expect("failed to generate a key");
+        let public_key = RsaPublicKey::from(&private_key);
+
+        RsaKeyPair {
+            private_key: private_key
+                .to_pkcs8_pem(LineEnding::LF)
+                .expect("failed to encode private key")
+                .to_string(),
+            public_key: public_key
+                .to_public_key_pem(LineEnding::LF)
+                .expect("failed to encode public key"),
+        }
+    }
+}
+
+pub struct RsaCipher {
+    public_key: RsaPublicKey,
+    private_key: Option<RsaPrivateKey>,
+}
+
+impl RsaCipher {
+    /// Cipher able only to encrypt, used to wrap keys for another user.
+    pub fn from_public_key(public_key: &str) -> RsaCipher {
+        let public_key = RsaPublicKey::from_public_key_pem(public_key).unwrap();
+
+        RsaCipher {
+            public_key,
+            private_key: None,
+        }
+    }
+
+    pub fn from_private_key(private_key: &str) -> RsaCipher {
+        let private_key = RsaPrivateKey::from_pkcs8_pem(private_key).unwrap();
+        let public_key = RsaPublicKey::from(&private_key);
+
+        RsaCipher {
+            public_key,
+            private_key: Some(private_key),
+        }
+    }
+}
+
+impl Cipher for RsaCipher {
+    fn encrypt(&self, value: &str) -> Vec<u8> {
+        let mut rng = rand::thread_rng();
+        self.public_key
+            .encrypt(
+                &mut rng,
+                PaddingScheme::new_oaep::<sha2::Sha256>(),
+                value.as_bytes(),
+            )
+            .expect("encryption failure!")
+    }
+
+    fn decrypt(&self, value: &[u8]) -> String {
+        let plaintext = self
+            .private_key
+            .as_ref()
+            .expect("private key required to decrypt")
+            .decrypt(PaddingScheme::new_oaep::<sha2::Sha256>(), value)
+            .expect("decryption failure!");
+
+        String::from_utf8(plaintext).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{AesGcmCipher, Cipher};
+    use super::{generate_key, AesGcmCipher, Cipher, RsaCipher, RsaKeyPair};
 
     const ID: &str = "67dcca6627454cff81cc811e2a4a17b9";
     #[test]
@@ -65,4 +164,14 @@ mod tests {
         let result = cipher.decrypt(&value);
         assert_eq!("hello_world", result);
     }
+
+    #[test]
+    fn is_wrapping_key_with_rsa() {
+        let key_pair = RsaKeyPair::generate();
+        let item_key = generate_key();
+
+        let wrapped = RsaCipher::from_public_key(&key_pair.public_key).encrypt(&item_key);
+        let result = RsaCipher::from_private_key(&key_pair.private_key).decrypt(&wrapped);
+        assert_eq!(item_key, result);
+    }
 }
@@ -0,0 +1,35 @@
+//! `ACCOUNT_ELEVATION_LEVEL`: accounts with a level above it only reveal their
+//! password to an elevated session (see `POST /api/auth/elevate`), 1 by
+//! default.
+//!
+//! The level is read once at startup and shared as an `Extension`.
+
+const DEFAULT_ELEVATION_LEVEL: i16 = 1;
+
+#[derive(Clone, Copy)]
+pub struct ElevationPolicy {
+    pub level: i16,
+}
+
+impl ElevationPolicy {
+    pub fn from_env() -> ElevationPolicy {
+        let level = match std::env::var("ACCOUNT_ELEVATION_LEVEL") {
+            Ok(level) => match level.parse::<i16>() {
+                Ok(level) => level,
+                Err(e) => {
+                    log::warn!("ACCOUNT_ELEVATION_LEVEL: {e}");
+                    DEFAULT_ELEVATION_LEVEL
+                }
+            },
+            Err(_) => DEFAULT_ELEVATION_LEVEL,
+        };
+
+        ElevationPolicy { level }
+    }
+
+    /// Whether the password of an account of `level` needs an elevated
+    /// session to be revealed.
+    pub fn requires_elevation(&self, level: i16) -> bool {
+        level > self.level
+    }
+}
@@ -0,0 +1,107 @@
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Answers `value` as JSON tagged with a strong ETag hashed from the body, or
+/// `304 Not Modified` when the request's `If-None-Match` already holds it.
+pub fn json_with_etag<T: Serialize>(headers: &HeaderMap, value: &T) -> Response {
+    let body = serde_json::to_vec(value).unwrap();
+    let etag = etag(&body);
+
+    let if_none_match = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok());
+    if if_none_match.is_some_and(|if_none_match| matches(if_none_match, &etag)) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+
+    (
+        StatusCode::OK,
+        [
+            (header::ETAG, etag),
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// ETag of a single item, its revision.
+pub fn revision_etag(revision: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{revision}\"")).unwrap()
+}
+
+/// Revision required by the request's `If-Match`, `None` when it is absent
+/// or `*`. Anything else than a strong revision ETag is an error.
+pub fn if_match_revision(headers: &HeaderMap) -> Result<Option<i32>, ()> {
+    let if_match = match headers.get(header::IF_MATCH) {
+        Some(if_match) => if_match.to_str().map_err(|_| ())?.trim(),
+        None => return Ok(None),
+    };
+    if if_match == "*" {
+        return Ok(None);
+    }
+
+    if_match
+        .strip_prefix('"')
+        .and_then(|if_match| if_match.strip_suffix('"'))
+        .and_then(|revision| revision.parse::<i32>().ok())
+        .map(Some)
+        .ok_or(())
+}
+
+fn etag(body: &[u8]) -> HeaderValue {
+    let mut hasher = Sha256::new();
+    hasher.update(body);
+    HeaderValue::from_str(&format!("\"{:x}\"", hasher.finalize())).unwrap()
+}
+
+/// `If-None-Match` uses the weak comparison: `W/` prefixes are ignored.
+fn matches(if_none_match: &str, etag: &HeaderValue) -> bool {
+    let etag = etag.to_str().unwrap();
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{etag, if_match_revision, matches};
+    use axum::http::{header, HeaderMap, HeaderValue};
+
+    #[test]
+    fn if_none_match_comparison() {
+        let tag = etag(b"[]");
+        let value = tag.to_str().unwrap();
+
+        assert!(matches(value, &tag));
+        assert!(matches(&format!("W/{value}"), &tag));
+        assert!(matches(&format!("\"other\", {value}"), &tag));
+        assert!(matches("*", &tag));
+        assert!(!matches("\"other\"", &tag));
+        assert!(!matches(etag(b"{}").to_str().unwrap(), &tag));
+    }
+
+    #[test]
+    fn if_match_holds_a_strong_revision() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Ok(None), if_match_revision(&headers));
+
+        for (value, expected) in [
+            ("\"3\"", Ok(Some(3))),
+            ("*", Ok(None)),
+            ("W/\"3\"", Err(())),
+            ("3", Err(())),
+            ("\"abc\"", Err(())),
+        ] {
+            headers.insert(header::IF_MATCH, HeaderValue::from_static(value));
+            assert_eq!(expected, if_match_revision(&headers), "{value}");
+        }
+    }
+}
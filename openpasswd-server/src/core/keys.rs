@@ -0,0 +1,44 @@
+use crate::core::cryptography::{AesGcmCipher, Cipher, RsaCipher, RsaKeyPair};
+use crate::repository::repositories::organizations_repository::OrganizationsRepository;
+use crate::repository::repositories::users_repository::UsersRepository;
+use entity::users::Model as User;
+
+/// Returns the PEM encoded public and private keys of `user`, generating them
+/// for users registered before key pairs existed.
+pub async fn user_key_pair<T: UsersRepository>(repository: &T, user: &User) -> (String, String) {
+    let master_cipher = AesGcmCipher::new(user.master_key.as_ref().unwrap());
+    if let (Some(public_key), Some(private_key)) = (&user.public_key, &user.private_key) {
+        return (public_key.to_owned(), master_cipher.decrypt(private_key));
+    }
+
+    let key_pair = tokio::task::spawn_blocking(RsaKeyPair::generate)
+        .await
+        .unwrap();
+    repository
+        .users_update_key_pair(
+            user.id,
+            key_pair.public_key.to_owned(),
+            master_cipher.encrypt(&key_pair.private_key),
+        )
+        .await;
+
+    (key_pair.public_key, key_pair.private_key)
+}
+
+/// Unwraps the key of `organization_id` with the private key of `user`,
+/// `None` when the user is not a member.
+pub async fn organization_key<T>(
+    repository: &T,
+    user: &User,
+    organization_id: i32,
+) -> Option<String>
+where
+    T: OrganizationsRepository + UsersRepository,
+{
+    let member = repository
+        .organization_members_find(organization_id, user.id)
+        .await?;
+    let (_, private_key) = user_key_pair(repository, user).await;
+
+    Some(RsaCipher::from_private_key(&private_key).decrypt(&member.org_key))
+}
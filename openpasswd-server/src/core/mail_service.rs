@@ -50,6 +50,16 @@ pub enum MailError {
     EmailError(EmailError),
 }
 
+impl std::fmt::Display for MailError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MailError::MissingSMTPConfiguration(message) => write!(f, "{message}"),
+            MailError::SmtpError(e) => write!(f, "SMTP error: {e}"),
+            MailError::EmailError(e) => write!(f, "Email error: {e}"),
+        }
+    }
+}
+
 pub struct MailService {}
 
 impl Clone for MailService {
@@ -131,7 +141,7 @@ impl MailService {
                             .singlepart(
                                 SinglePart::builder()
                                     .header(header::ContentType::TEXT_HTML)
-                                    .body(String::from(html)),
+                                    .body(html),
                             ),
                     )
                     .map_err(MailError::EmailError)?
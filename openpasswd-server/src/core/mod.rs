@@ -1,5 +1,16 @@
+pub mod audit;
+pub mod authorization;
+pub mod breached_passwords;
 pub mod cache;
 pub mod cryptography;
+pub mod elevation;
+pub mod etag;
+pub mod keys;
 pub mod mail_service;
+pub mod notifications;
+pub mod password_age;
+pub mod password_policy;
+pub mod password_strength;
 pub mod result;
 pub mod validator;
+pub mod webhooks;
@@ -0,0 +1,104 @@
+use super::authorization::group_members;
+use super::cache::Cache;
+use crate::repository::repositories::authorization_repository::AuthorizationRepository;
+use crate::repository::repositories::shares_repository::SharesRepository;
+use futures::StreamExt;
+use model::notifications::{Notification, NotificationEvent};
+use model::organizations::CollectionPermission;
+use tokio::sync::broadcast;
+
+const CHANNEL_PREFIX: &str = "notifications:";
+const HUB_CAPACITY: usize = 1024;
+const RECONNECT_SECONDS: u64 = 5;
+
+/// Fans the notifications published on Redis by any server instance out to
+/// the streams open on this one.
+#[derive(Clone)]
+pub struct NotificationHub {
+    sender: broadcast::Sender<(i32, Notification)>,
+}
+
+impl NotificationHub {
+    pub fn new() -> NotificationHub {
+        let (sender, _) = broadcast::channel(HUB_CAPACITY);
+        NotificationHub { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<(i32, Notification)> {
+        self.sender.subscribe()
+    }
+
+    pub async fn listen(self, cache: Cache) {
+        loop {
+            if let Err(e) = self.forward(&cache).await {
+                log::error!("Notifications subscription lost: {e}");
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(RECONNECT_SECONDS)).await;
+        }
+    }
+
+    async fn forward(&self, cache: &Cache) -> redis::RedisResult<()> {
+        let mut pubsub = cache.pubsub().await?;
+        pubsub.psubscribe(format!("{CHANNEL_PREFIX}*")).await?;
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let user_id = message
+                .get_channel_name()
+                .strip_prefix(CHANNEL_PREFIX)
+                .and_then(|user_id| user_id.parse::<i32>().ok());
+            let notification = message
+                .get_payload::<String>()
+                .ok()
+                .and_then(|payload| serde_json::from_str::<Notification>(&payload).ok());
+
+            if let (Some(user_id), Some(notification)) = (user_id, notification) {
+                // Nobody listening is not an error.
+                let _ = self.sender.send((user_id, notification));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub async fn publish(cache: &Cache, user_ids: &[i32], notification: Notification) {
+    let payload = serde_json::to_string(&notification).unwrap();
+    for user_id in user_ids {
+        cache
+            .publish(&format!("{CHANNEL_PREFIX}{user_id}"), payload.as_str())
+            .await;
+    }
+}
+
+/// Notifies the users of a change already recorded in their vault history.
+pub async fn notify_users(cache: &Cache, user_ids: &[i32], event: NotificationEvent, id: i32) {
+    publish(
+        cache,
+        user_ids,
+        Notification {
+            event,
+            id: Some(id),
+            session: None,
+        },
+    )
+    .await;
+}
+
+/// Everyone who can see the group and, for an account, the users it's
+/// directly shared with.
+pub async fn group_recipients<T: AuthorizationRepository + SharesRepository>(
+    repository: &T,
+    group: &entity::account_groups::Model,
+    account_id: Option<i32>,
+) -> Vec<i32> {
+    let mut user_ids =
+        group_members(repository, group, CollectionPermission::ViewWithoutPassword).await;
+    if let Some(account_id) = account_id {
+        for (share, _) in repository.shares_list_by_account_id(account_id).await {
+            if !user_ids.contains(&share.user_id) {
+                user_ids.push(share.user_id);
+            }
+        }
+    }
+    user_ids
+}
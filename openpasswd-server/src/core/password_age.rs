@@ -0,0 +1,32 @@
+//! `PASSWORD_MAX_AGE_DAYS`: age after which the health report lists a
+//! password as old when the request doesn't say otherwise, 365 by default.
+//!
+//! The age is read once at startup and shared as an `Extension`.
+
+const DEFAULT_MAX_AGE_DAYS: i64 = 365;
+
+#[derive(Clone, Copy)]
+pub struct PasswordAgePolicy {
+    pub max_age_days: i64,
+}
+
+impl PasswordAgePolicy {
+    pub fn from_env() -> PasswordAgePolicy {
+        let max_age_days = match std::env::var("PASSWORD_MAX_AGE_DAYS") {
+            Ok(days) => match days.parse::<i64>() {
+                Ok(days) if days > 0 => days,
+                Ok(days) => {
+                    log::warn!("PASSWORD_MAX_AGE_DAYS: {days} is not positive");
+                    DEFAULT_MAX_AGE_DAYS
+                }
+                Err(e) => {
+                    log::warn!("PASSWORD_MAX_AGE_DAYS: {e}");
+                    DEFAULT_MAX_AGE_DAYS
+                }
+            },
+            Err(_) => DEFAULT_MAX_AGE_DAYS,
+        };
+
+        PasswordAgePolicy { max_age_days }
+    }
+}
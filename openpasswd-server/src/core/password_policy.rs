@@ -0,0 +1,188 @@
+//! Rules a login password has to follow, checked whenever one is set:
+//! registration, change, recovery and emergency takeover.
+//!
+//! - `PASSWORD_MIN_LENGTH`, in characters, 8 by default,
+//! - `PASSWORD_MIN_SCORE`, from 0 to 4 as estimated by `password_strength`,
+//!   2 by default,
+//! - `PASSWORD_BLOCKLIST_PATH`, a file of refused passwords, one per line,
+//!   compared case-insensitively.
+//!
+//! Passwords containing the user's name or the local part of their email are
+//! always refused.
+//!
+//! The policy is read once at startup and shared as an `Extension`.
+
+use std::{borrow::Cow, collections::HashSet, sync::Arc};
+
+use validator::{ValidationError, ValidationErrors};
+
+use super::password_strength;
+
+const DEFAULT_MIN_LENGTH: usize = 8;
+const DEFAULT_MIN_SCORE: u8 = 2;
+/// Shorter names and email local parts would refuse too many passwords.
+const MIN_PERSONAL_LENGTH: usize = 3;
+
+#[derive(Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub min_score: u8,
+    /// Lower case, shared so a clone for each request stays cheap.
+    pub blocklist: Arc<HashSet<String>>,
+}
+
+impl PasswordPolicy {
+    pub fn from_env() -> PasswordPolicy {
+        let min_length = match std::env::var("PASSWORD_MIN_LENGTH") {
+            Ok(length) => match length.parse::<usize>() {
+                Ok(length) => length,
+                Err(e) => {
+                    log::warn!("PASSWORD_MIN_LENGTH: {e}");
+                    DEFAULT_MIN_LENGTH
+                }
+            },
+            Err(_) => DEFAULT_MIN_LENGTH,
+        };
+        let min_score = match std::env::var("PASSWORD_MIN_SCORE") {
+            Ok(score) => match score.parse::<u8>() {
+                Ok(score) if score <= 4 => score,
+                Ok(score) => {
+                    log::warn!("PASSWORD_MIN_SCORE: {score} is above 4");
+                    DEFAULT_MIN_SCORE
+                }
+                Err(e) => {
+                    log::warn!("PASSWORD_MIN_SCORE: {e}");
+                    DEFAULT_MIN_SCORE
+                }
+            },
+            Err(_) => DEFAULT_MIN_SCORE,
+        };
+        let blocklist = match std::env::var("PASSWORD_BLOCKLIST_PATH") {
+            Ok(path) if !path.is_empty() => match std::fs::read_to_string(&path) {
+                Ok(content) => content
+                    .lines()
+                    .map(|line| line.trim().to_lowercase())
+                    .filter(|line| !line.is_empty())
+                    .collect(),
+                Err(e) => {
+                    log::warn!("PASSWORD_BLOCKLIST_PATH: {path}: {e}");
+                    HashSet::new()
+                }
+            },
+            _ => HashSet::new(),
+        };
+
+        PasswordPolicy {
+            min_length,
+            min_score,
+            blocklist: Arc::new(blocklist),
+        }
+    }
+
+    /// The first rule broken is reported on `field`, the error code naming
+    /// the rule.
+    pub fn check(
+        &self,
+        field: &'static str,
+        password: &str,
+        name: &str,
+        email: &str,
+    ) -> Result<(), ValidationErrors> {
+        let error = |code: &'static str, message: String| {
+            let mut error = ValidationError::new(code);
+            error.message = Some(Cow::from(message));
+            let mut errors = ValidationErrors::new();
+            errors.add(field, error);
+            Err(errors)
+        };
+
+        let length = password.chars().count();
+        if length < self.min_length {
+            return error(
+                "password_length",
+                format!(
+                    "Password must be at least {} characters long",
+                    self.min_length
+                ),
+            );
+        }
+
+        let lower = password.to_lowercase();
+        let email_name = email.split('@').next().unwrap_or_default();
+        let personal = [name, email_name].into_iter().find(|personal| {
+            personal.chars().count() >= MIN_PERSONAL_LENGTH
+                && lower.contains(&personal.to_lowercase())
+        });
+        if personal.is_some() {
+            return error(
+                "password_personal",
+                String::from("Password must not contain your name or email"),
+            );
+        }
+
+        if self.blocklist.contains(&lower) {
+            return error(
+                "password_blocklist",
+                String::from("This password is not allowed, choose another one"),
+            );
+        }
+
+        let strength = password_strength::estimate(password, &[name, email_name]);
+        if strength.score < self.min_score {
+            return error(
+                "password_strength",
+                String::from("Password is too easy to guess, use a longer or less common one"),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Arc};
+
+    use super::PasswordPolicy;
+
+    fn policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 10,
+            min_score: 3,
+            blocklist: Arc::new(HashSet::from([String::from("correcthorsebattery")])),
+        }
+    }
+
+    fn broken_rule(password: &str) -> Option<String> {
+        policy()
+            .check("password", password, "Jane Doe", "jdoe@example.com")
+            .err()
+            .map(|errors| errors.field_errors()["password"][0].code.to_string())
+    }
+
+    #[test]
+    fn each_rule_is_reported() {
+        assert_eq!(Some("password_length"), broken_rule("xK9#mQ2").as_deref());
+        assert_eq!(
+            Some("password_personal"),
+            broken_rule("tVb7JDOE9LsK4e").as_deref()
+        );
+        assert_eq!(
+            Some("password_blocklist"),
+            broken_rule("CorrectHorseBattery").as_deref()
+        );
+        assert_eq!(
+            Some("password_strength"),
+            broken_rule("password1234").as_deref()
+        );
+    }
+
+    #[test]
+    fn strong_passwords_pass() {
+        assert_eq!(None, broken_rule("c8Tq-Lw3z-Hn5v-Rb2k"));
+        // Too short to refuse anything.
+        assert!(policy()
+            .check("password", "c8Tq-Lw3z-Hn5v-Rb2k", "Al", "c8@example.com")
+            .is_ok());
+    }
+}
@@ -0,0 +1,370 @@
+//! Password strength estimated the way zxcvbn does: the password is covered
+//! with the cheapest sequence of guessable patterns (common passwords, words
+//! the user gave, repeats, sequences, keyboard rows, years), what no pattern
+//! covers is brute forced, and the cost of the whole cover is its entropy.
+
+/// Most common passwords, the most common first. Also matched as words inside
+/// longer passwords.
+const COMMON_PASSWORDS: [&str; 100] = [
+    "123456",
+    "password",
+    "12345678",
+    "qwerty",
+    "123456789",
+    "12345",
+    "1234",
+    "111111",
+    "1234567",
+    "dragon",
+    "123123",
+    "baseball",
+    "abc123",
+    "football",
+    "monkey",
+    "letmein",
+    "696969",
+    "shadow",
+    "master",
+    "666666",
+    "qwertyuiop",
+    "123321",
+    "mustang",
+    "1234567890",
+    "michael",
+    "654321",
+    "superman",
+    "1qaz2wsx",
+    "7777777",
+    "121212",
+    "000000",
+    "qazwsx",
+    "123qwe",
+    "killer",
+    "trustno1",
+    "jordan",
+    "jennifer",
+    "zxcvbnm",
+    "asdfgh",
+    "hunter",
+    "buster",
+    "soccer",
+    "harley",
+    "batman",
+    "andrew",
+    "tigger",
+    "sunshine",
+    "iloveyou",
+    "2000",
+    "charlie",
+    "robert",
+    "thomas",
+    "hockey",
+    "ranger",
+    "daniel",
+    "starwars",
+    "klaster",
+    "112233",
+    "george",
+    "computer",
+    "michelle",
+    "jessica",
+    "pepper",
+    "1111",
+    "zxcvbn",
+    "555555",
+    "11111111",
+    "131313",
+    "freedom",
+    "777777",
+    "pass",
+    "maggie",
+    "159753",
+    "aaaaaa",
+    "ginger",
+    "princess",
+    "joshua",
+    "cheese",
+    "amanda",
+    "summer",
+    "love",
+    "ashley",
+    "nicole",
+    "chelsea",
+    "biteme",
+    "matthew",
+    "access",
+    "yankees",
+    "987654321",
+    "dallas",
+    "austin",
+    "thunder",
+    "taylor",
+    "matrix",
+    "welcome",
+    "admin",
+    "secret",
+    "login",
+    "hello",
+    "passw0rd",
+];
+
+const KEYBOARD_ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+const MIN_MATCH_LENGTH: usize = 3;
+/// Beyond this length the pattern search is skipped, brute force alone
+/// already puts such passwords out of reach.
+const MAX_ANALYZED_LENGTH: usize = 64;
+
+/// Entropy, in bits, from which each score is reached.
+const SCORE_THRESHOLDS: [f64; 4] = [28.0, 36.0, 48.0, 64.0];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PasswordStrength {
+    pub entropy_bits: f64,
+    /// From 0, guessed almost instantly, to 4, out of reach of an offline
+    /// attack.
+    pub score: u8,
+}
+
+/// `user_inputs` are words an attacker targeting this password would try
+/// first: the account name, the username, the website.
+pub fn estimate(password: &str, user_inputs: &[&str]) -> PasswordStrength {
+    let chars: Vec<char> = password.chars().collect();
+    let entropy_bits = if chars.len() > MAX_ANALYZED_LENGTH {
+        chars.len() as f64 * cardinality(&chars).log2()
+    } else {
+        minimum_entropy(&chars, user_inputs)
+    };
+    let score = SCORE_THRESHOLDS
+        .iter()
+        .take_while(|threshold| entropy_bits >= **threshold)
+        .count() as u8;
+
+    PasswordStrength {
+        entropy_bits,
+        score,
+    }
+}
+
+/// Size of the alphabet a brute force of the password has to go through.
+fn cardinality(chars: &[char]) -> f64 {
+    let mut lower = false;
+    let mut upper = false;
+    let mut digit = false;
+    let mut symbol = false;
+    let mut other = false;
+    for c in chars {
+        match c {
+            'a'..='z' => lower = true,
+            'A'..='Z' => upper = true,
+            '0'..='9' => digit = true,
+            c if c.is_ascii() => symbol = true,
+            _ => other = true,
+        }
+    }
+    let cardinality = [
+        (lower, 26),
+        (upper, 26),
+        (digit, 10),
+        (symbol, 33),
+        (other, 100),
+    ]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, size)| size)
+    .sum::<u32>();
+    f64::from(cardinality.max(1))
+}
+
+/// Cheapest cover of the password, `best[i]` being the entropy of its first
+/// `i` characters.
+fn minimum_entropy(chars: &[char], user_inputs: &[&str]) -> f64 {
+    let brute_force = cardinality(chars).log2();
+    let matches = find_matches(chars, user_inputs);
+
+    let mut best = vec![f64::INFINITY; chars.len() + 1];
+    best[0] = 0.0;
+    for end in 1..=chars.len() {
+        best[end] = best[end - 1] + brute_force;
+        for (start, _, entropy) in matches.iter().filter(|(_, e, _)| *e == end) {
+            best[end] = best[end].min(best[*start] + entropy);
+        }
+    }
+    best[chars.len()]
+}
+
+/// Every pattern found in the password as `(start, end, entropy)`.
+fn find_matches(chars: &[char], user_inputs: &[&str]) -> Vec<(usize, usize, f64)> {
+    let mut matches = Vec::new();
+    let user_inputs: Vec<String> = user_inputs
+        .iter()
+        .map(|input| input.to_lowercase())
+        .filter(|input| input.chars().count() >= MIN_MATCH_LENGTH)
+        .collect();
+
+    for start in 0..chars.len() {
+        for end in start + MIN_MATCH_LENGTH..=chars.len() {
+            let token = &chars[start..end];
+            let mut candidates = Vec::new();
+            if let Some(entropy) = dictionary_entropy(token, &user_inputs) {
+                candidates.push(entropy);
+            }
+            if let Some(entropy) = repeat_entropy(token) {
+                candidates.push(entropy);
+            }
+            if let Some(entropy) = sequence_entropy(token) {
+                candidates.push(entropy);
+            }
+            if let Some(entropy) = keyboard_entropy(token) {
+                candidates.push(entropy);
+            }
+            if let Some(entropy) = year_entropy(token) {
+                candidates.push(entropy);
+            }
+            if let Some(entropy) = candidates.into_iter().reduce(f64::min) {
+                matches.push((start, end, entropy));
+            }
+        }
+    }
+    matches
+}
+
+/// A common password or a user input, possibly capitalized or written in
+/// leetspeak.
+fn dictionary_entropy(token: &[char], user_inputs: &[String]) -> Option<f64> {
+    let lower: String = token.iter().flat_map(|c| c.to_lowercase()).collect();
+    let unleet: String = lower.chars().map(unleet).collect();
+
+    let rank = [&lower, &unleet]
+        .iter()
+        .filter_map(|word| {
+            if user_inputs.contains(word) {
+                return Some(1);
+            }
+            COMMON_PASSWORDS
+                .iter()
+                .position(|common| common == word)
+                .map(|position| position + 1)
+        })
+        .min()?;
+
+    let mut entropy = (rank as f64).log2();
+    if token.iter().any(|c| c.is_uppercase()) {
+        entropy += if token[1..].iter().any(|c| c.is_uppercase()) {
+            token.len() as f64
+        } else {
+            1.0
+        };
+    }
+    if lower != unleet {
+        entropy += 1.0;
+    }
+    Some(entropy)
+}
+
+fn unleet(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        c => c,
+    }
+}
+
+/// `aaaa`
+fn repeat_entropy(token: &[char]) -> Option<f64> {
+    if token.iter().all(|c| *c == token[0]) {
+        Some(cardinality(&token[..1]).log2() + (token.len() as f64).log2())
+    } else {
+        None
+    }
+}
+
+/// `abcd`, `4321`
+fn sequence_entropy(token: &[char]) -> Option<f64> {
+    let delta = token[1] as i64 - token[0] as i64;
+    if delta.abs() != 1
+        || token
+            .windows(2)
+            .any(|pair| pair[1] as i64 - pair[0] as i64 != delta)
+    {
+        return None;
+    }
+    let start = if matches!(token[0], 'a' | 'z' | 'A' | 'Z' | '0' | '1' | '9') {
+        1.0
+    } else {
+        cardinality(&token[..1]).log2()
+    };
+    let descending = if delta < 0 { 1.0 } else { 0.0 };
+    Some(start + (token.len() as f64).log2() + descending)
+}
+
+/// `qwerty`, `lkjh`
+fn keyboard_entropy(token: &[char]) -> Option<f64> {
+    if token.len() < 4 {
+        return None;
+    }
+    let lower: String = token.iter().flat_map(|c| c.to_lowercase()).collect();
+    let reversed: String = lower.chars().rev().collect();
+    KEYBOARD_ROWS
+        .iter()
+        .any(|row| row.contains(&lower) || row.contains(&reversed))
+        .then(|| 47f64.log2() + (token.len() as f64).log2())
+}
+
+/// Years from 1900 to 2049.
+fn year_entropy(token: &[char]) -> Option<f64> {
+    if token.len() != 4 {
+        return None;
+    }
+    let year: String = token.iter().collect();
+    match year.parse::<u32>() {
+        Ok(1900..=2049) => Some(150f64.log2()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::estimate;
+
+    #[test]
+    fn common_passwords_and_patterns_are_weak() {
+        for password in [
+            "password",
+            "P@ssw0rd",
+            "qwerty123",
+            "aaaaaaaaaaaa",
+            "abcdefgh",
+            "iloveyou1990",
+            "dragon2020!",
+        ] {
+            let strength = estimate(password, &[]);
+            assert!(strength.score <= 1, "{password}: {strength:?}");
+        }
+    }
+
+    #[test]
+    fn user_inputs_are_guessed_first() {
+        let alone = estimate("jsmith-mail", &[]);
+        let targeted = estimate("jsmith-mail", &["jsmith", "mail"]);
+        assert!(targeted.entropy_bits < alone.entropy_bits);
+        assert!(targeted.score <= 1, "{targeted:?}");
+    }
+
+    #[test]
+    fn random_passwords_are_strong() {
+        for password in [
+            "xK9#mQ2$vL7!pR4&",
+            "c8Tq-Lw3z-Hn5v-Rb2k",
+            "tVb7Qz2hWm9LsK4e",
+        ] {
+            let strength = estimate(password, &[]);
+            assert_eq!(4, strength.score, "{password}: {strength:?}");
+        }
+        assert_eq!(0, estimate("", &[]).score);
+    }
+}
@@ -48,7 +48,7 @@ impl IntoResponse for ServerError {
                     for validation_error_item in validation_error {
                         let value = match validation_error_item.message.as_ref() {
                             Some(x) => x.to_string().replace('"', ""),
-                            None => String::from(format!("Field {field} has an invalid value")),
+                            None => format!("Field {field} has an invalid value"),
                         };
 
                         message.insert(field.to_owned(), value);
@@ -0,0 +1,297 @@
+use crate::repository::models::webhook::{NewWebhookDelivery, WebhookDeliveryAttempt};
+use crate::repository::repositories::webhooks_repository::WebhooksRepository;
+use axum::http::header::CONTENT_TYPE;
+use hmac::{Hmac, Mac};
+use model::audit::AuditEventType;
+use model::webhooks::{
+    WebhookDeliveryStatus, WebhookEvent, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER,
+    WEBHOOK_SIGNATURE_HEADER,
+};
+use sha2::Sha256;
+
+const MAX_ATTEMPTS: i16 = 10;
+const RETRY_BASE_SECONDS: i64 = 30;
+const RETRY_MAX_SECONDS: i64 = 6 * 60 * 60;
+const DELIVERY_TIMEOUT_SECONDS: u64 = 10;
+const ERROR_MAX_LEN: usize = 255;
+
+/// Event types are stored comma separated, an empty list subscribes to
+/// every event.
+pub fn join_event_types(event_types: &[AuditEventType]) -> String {
+    event_types
+        .iter()
+        .map(|event_type| event_type.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn split_event_types(event_types: &str) -> Vec<AuditEventType> {
+    event_types
+        .split(',')
+        .filter_map(|event_type| event_type.parse().ok())
+        .collect()
+}
+
+fn is_subscribed(webhook: &entity::webhooks::Model, event_type: AuditEventType) -> bool {
+    webhook.event_types.is_empty() || split_event_types(&webhook.event_types).contains(&event_type)
+}
+
+/// Organization an event belongs to, from its target: the organization of
+/// the collection holding the account, or the organization itself.
+async fn target_organization<T: WebhooksRepository>(
+    repository: &T,
+    target: Option<&str>,
+) -> Option<i32> {
+    let (kind, id) = target?.split_once(':')?;
+    let id = id.parse().ok()?;
+    match kind {
+        "account" => repository.webhooks_account_organization(id).await,
+        "organization" => Some(id),
+        _ => None,
+    }
+}
+
+/// Personal webhooks get every event of their user, organization webhooks
+/// only the events of that organization, never the members' own vaults.
+fn is_recipient(webhook: &entity::webhooks::Model, organization_id: Option<i32>) -> bool {
+    match webhook.organization_id {
+        Some(webhook_organization_id) => organization_id == Some(webhook_organization_id),
+        None => true,
+    }
+}
+
+/// Queues a delivery for every webhook listening to the events of the user.
+pub async fn enqueue<T: WebhooksRepository>(
+    repository: &T,
+    event_type: AuditEventType,
+    event: &WebhookEvent,
+) {
+    let webhooks = repository.webhooks_list_subscribed(event.user_id).await;
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let organization_id = if webhooks
+        .iter()
+        .any(|webhook| webhook.organization_id.is_some())
+    {
+        target_organization(repository, event.target.as_deref()).await
+    } else {
+        None
+    };
+    let payload = serde_json::to_string(event).unwrap();
+    let now = chrono::Utc::now().naive_utc();
+    for webhook in webhooks.iter().filter(|webhook| {
+        is_subscribed(webhook, event_type) && is_recipient(webhook, organization_id)
+    }) {
+        repository
+            .webhook_deliveries_insert(NewWebhookDelivery {
+                webhook_id: webhook.id,
+                event_type: event.event_type.to_owned(),
+                payload: payload.to_owned(),
+                status: WebhookDeliveryStatus::Pending.as_str().to_owned(),
+                next_attempt_at: now,
+                created_date: now,
+            })
+            .await;
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of the payload.
+pub fn sign(secret: &str, payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Wait before the next attempt, doubling from 30 seconds up to 6 hours.
+pub fn retry_delay(attempts: i16) -> chrono::Duration {
+    let exponent = (attempts.max(1) - 1).min(16) as u32;
+    let seconds = RETRY_BASE_SECONDS.saturating_mul(2i64.pow(exponent));
+    chrono::Duration::seconds(seconds.min(RETRY_MAX_SECONDS))
+}
+
+/// Posts the delivery to the webhook, anything but a 2xx answer is retried
+/// until `MAX_ATTEMPTS` is reached.
+pub async fn deliver(
+    webhook: &entity::webhooks::Model,
+    delivery: &entity::webhook_deliveries::Model,
+) -> WebhookDeliveryAttempt {
+    let attempts = delivery.attempts + 1;
+    let now = chrono::Utc::now().naive_utc();
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(DELIVERY_TIMEOUT_SECONDS))
+        .build()
+        .unwrap();
+    let result = client
+        .post(&webhook.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_EVENT_HEADER, &delivery.event_type)
+        .header(WEBHOOK_DELIVERY_HEADER, delivery.id.to_string())
+        .header(
+            WEBHOOK_SIGNATURE_HEADER,
+            format!("sha256={}", sign(&webhook.secret, &delivery.payload)),
+        )
+        .body(delivery.payload.to_owned())
+        .send()
+        .await;
+
+    let (response_status, error) = match result {
+        Ok(response) if response.status().is_success() => {
+            return WebhookDeliveryAttempt {
+                status: WebhookDeliveryStatus::Delivered.as_str().to_owned(),
+                attempts,
+                next_attempt_at: now,
+                response_status: Some(response.status().as_u16() as i16),
+                error: None,
+                delivered_at: Some(now),
+            };
+        }
+        Ok(response) => (
+            Some(response.status().as_u16() as i16),
+            format!("Unexpected status {}", response.status()),
+        ),
+        Err(e) => (None, e.to_string()),
+    };
+
+    let status = if attempts >= MAX_ATTEMPTS {
+        WebhookDeliveryStatus::Failed
+    } else {
+        WebhookDeliveryStatus::Pending
+    };
+    WebhookDeliveryAttempt {
+        status: status.as_str().to_owned(),
+        attempts,
+        next_attempt_at: now + retry_delay(attempts),
+        response_status,
+        error: Some(error.chars().take(ERROR_MAX_LEN).collect()),
+        delivered_at: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deliver, is_recipient, retry_delay, sign, split_event_types};
+    use axum::{http::HeaderMap, routing::post, Router};
+    use model::audit::AuditEventType;
+    use model::webhooks::WEBHOOK_SIGNATURE_HEADER;
+    use std::net::{SocketAddr, TcpListener};
+
+    #[test]
+    fn is_signing_with_hmac_sha256() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            sign("Jefe", "what do ya want for nothing?")
+        );
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_a_cap() {
+        assert_eq!(30, retry_delay(1).num_seconds());
+        assert_eq!(60, retry_delay(2).num_seconds());
+        assert_eq!(240, retry_delay(4).num_seconds());
+        assert_eq!(6 * 60 * 60, retry_delay(i16::MAX).num_seconds());
+    }
+
+    #[test]
+    fn unknown_event_types_are_ignored() {
+        assert_eq!(
+            vec![AuditEventType::Login, AuditEventType::PasswordRevealed],
+            split_event_types("login,unknown,password_revealed")
+        );
+        assert!(split_event_types("").is_empty());
+    }
+
+    fn webhook(url: String) -> entity::webhooks::Model {
+        entity::webhooks::Model {
+            id: 1,
+            user_id: Some(1),
+            organization_id: None,
+            url,
+            secret: String::from("secret"),
+            event_types: String::new(),
+            enabled: true,
+            created_date: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn organization_webhooks_skip_personal_events() {
+        let mut organization_webhook = webhook(String::from("https://example.com/hook"));
+        organization_webhook.user_id = None;
+        organization_webhook.organization_id = Some(7);
+
+        assert!(!is_recipient(&organization_webhook, None));
+        assert!(!is_recipient(&organization_webhook, Some(8)));
+        assert!(is_recipient(&organization_webhook, Some(7)));
+        assert!(is_recipient(
+            &webhook(String::from("https://example.com/hook")),
+            None
+        ));
+    }
+
+    fn delivery(attempts: i16) -> entity::webhook_deliveries::Model {
+        let now = chrono::Utc::now().naive_utc();
+        entity::webhook_deliveries::Model {
+            id: 1,
+            webhook_id: 1,
+            event_type: String::from("login"),
+            payload: String::from(r#"{"event_type":"login"}"#),
+            status: String::from("pending"),
+            attempts,
+            next_attempt_at: now,
+            response_status: None,
+            error: None,
+            created_date: now,
+            delivered_at: None,
+        }
+    }
+
+    /// Starts a receiver that accepts correctly signed deliveries only.
+    fn receiver() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route(
+            "/hook",
+            post(|headers: HeaderMap, body: String| async move {
+                let expected = format!("sha256={}", sign("secret", &body));
+                if headers[WEBHOOK_SIGNATURE_HEADER] == expected.as_str() {
+                    axum::http::StatusCode::NO_CONTENT
+                } else {
+                    axum::http::StatusCode::UNAUTHORIZED
+                }
+            }),
+        );
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+        addr
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_payloads() {
+        let addr = receiver();
+        let attempt = deliver(&webhook(format!("http://{addr}/hook")), &delivery(0)).await;
+
+        assert_eq!("delivered", attempt.status);
+        assert_eq!(Some(204), attempt.response_status);
+        assert_eq!(1, attempt.attempts);
+    }
+
+    #[tokio::test]
+    async fn retries_until_attempts_run_out() {
+        let addr = receiver();
+        let url = format!("http://{addr}/missing");
+
+        let attempt = deliver(&webhook(url.to_owned()), &delivery(0)).await;
+        assert_eq!("pending", attempt.status);
+        assert_eq!(Some(404), attempt.response_status);
+
+        let attempt = deliver(&webhook(url), &delivery(9)).await;
+        assert_eq!("failed", attempt.status);
+    }
+}
@@ -1,6 +1,5 @@
 use axum::Router;
 pub mod controller;
-pub mod dto;
 mod service;
 
 pub fn route() -> Router {
@@ -0,0 +1,119 @@
+use super::{dto::emergency_access_error::EmergencyAccessResult, service::EmergencyAccessService};
+use crate::{
+    auth::dto::claims::Claims,
+    core::{
+        breached_passwords::{breach_headers, BreachChecker},
+        password_policy::PasswordPolicy,
+        validator::ValidatedJson,
+    },
+    repository::Repository,
+};
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use model::emergency_access::{EmergencyAccessRegister, EmergencyAccessTakeover};
+
+pub async fn invite(
+    claims: Claims,
+    ValidatedJson(register): ValidatedJson<EmergencyAccessRegister>,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service
+        .invite(claims.sub, register)
+        .await?;
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+pub async fn list_trusted(
+    claims: Claims,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service.list_trusted(claims.sub).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn list_granted(
+    claims: Claims,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service.list_granted(claims.sub).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn delete(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    emergency_access_service.delete(claims.sub, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn accept(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service.accept(claims.sub, id).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn request_recovery(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service
+        .request_recovery(claims.sub, id)
+        .await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn approve(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service.approve(claims.sub, id).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn reject(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service.reject(claims.sub, id).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn view_vault(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let result = emergency_access_service.view_vault(claims.sub, id).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn takeover(
+    claims: Claims,
+    Path(id): Path<i32>,
+    ValidatedJson(takeover): ValidatedJson<EmergencyAccessTakeover>,
+    Extension(repository): Extension<Repository>,
+    Extension(policy): Extension<PasswordPolicy>,
+    Extension(breaches): Extension<BreachChecker>,
+) -> EmergencyAccessResult<impl IntoResponse> {
+    let emergency_access_service = EmergencyAccessService::new(repository);
+    let breached = emergency_access_service
+        .takeover(claims.sub, id, takeover, &policy, &breaches)
+        .await?;
+    Ok((StatusCode::NO_CONTENT, breach_headers(breached)))
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+use crate::core::{breached_passwords::breached_response, validator::ServerError};
+
+pub type EmergencyAccessResult<T = ()> = Result<T, EmergencyAccessError>;
+
+#[derive(Debug)]
+pub enum EmergencyAccessError {
+    NotFound,
+    InvalidContact,
+    AlreadyInvited,
+    InvalidState,
+    TakeoverNotAllowed,
+    InvalidPassword(validator::ValidationErrors),
+    PasswordBreached(u64),
+}
+
+impl IntoResponse for EmergencyAccessError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            EmergencyAccessError::NotFound => (StatusCode::NOT_FOUND, String::from("Invalid Path")),
+            EmergencyAccessError::InvalidContact => {
+                (StatusCode::BAD_REQUEST, String::from("Invalid Contact"))
+            }
+            EmergencyAccessError::AlreadyInvited => (
+                StatusCode::CONFLICT,
+                String::from("Contact is already a trusted contact"),
+            ),
+            EmergencyAccessError::InvalidState => (
+                StatusCode::CONFLICT,
+                String::from("Emergency access is not in a valid state for this action"),
+            ),
+            EmergencyAccessError::TakeoverNotAllowed => (
+                StatusCode::FORBIDDEN,
+                String::from("Emergency access does not allow takeover"),
+            ),
+            EmergencyAccessError::InvalidPassword(e) => {
+                return ServerError::ValidationError(e).into_response()
+            }
+            EmergencyAccessError::PasswordBreached(count) => return breached_response(count),
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
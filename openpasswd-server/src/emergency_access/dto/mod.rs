@@ -0,0 +1 @@
+pub mod emergency_access_error;
@@ -0,0 +1,59 @@
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::repository::Repository;
+
+pub mod controller;
+pub mod dto;
+mod service;
+
+pub fn route() -> Router {
+    Router::new()
+        .route("/api/emergency-access", post(self::controller::invite))
+        .route(
+            "/api/emergency-access/trusted",
+            get(self::controller::list_trusted),
+        )
+        .route(
+            "/api/emergency-access/granted",
+            get(self::controller::list_granted),
+        )
+        .route(
+            "/api/emergency-access/:id",
+            delete(self::controller::delete),
+        )
+        .route(
+            "/api/emergency-access/:id/accept",
+            post(self::controller::accept),
+        )
+        .route(
+            "/api/emergency-access/:id/request",
+            post(self::controller::request_recovery),
+        )
+        .route(
+            "/api/emergency-access/:id/approve",
+            post(self::controller::approve),
+        )
+        .route(
+            "/api/emergency-access/:id/reject",
+            post(self::controller::reject),
+        )
+        .route(
+            "/api/emergency-access/:id/vault",
+            get(self::controller::view_vault),
+        )
+        .route(
+            "/api/emergency-access/:id/takeover",
+            post(self::controller::takeover),
+        )
+}
+
+/// Approves recovery requests whose wait period ran out without the grantor
+/// rejecting them.
+pub async fn approval_task(repository: Repository) {
+    service::EmergencyAccessService::new(repository)
+        .approval_task()
+        .await
+}
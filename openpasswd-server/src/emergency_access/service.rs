@@ -0,0 +1,509 @@
+use crate::core::breached_passwords::BreachChecker;
+use crate::core::cryptography::{hash_password, AesGcmCipher, Cipher, RsaCipher};
+use crate::core::keys::user_key_pair;
+use crate::core::mail_service::{EmailAddress, MailService, MessageBody};
+use crate::core::password_policy::PasswordPolicy;
+use crate::repository::models::emergency_access::NewEmergencyAccess;
+use crate::repository::repositories::emergency_access_repository::EmergencyAccessRepository;
+use crate::repository::repositories::users_repository::UsersRepository;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use entity::emergency_access::Model as EmergencyAccess;
+use model::accounts::AccountWithPasswordView;
+use model::emergency_access::{
+    EmergencyAccessRegister, EmergencyAccessStatus, EmergencyAccessTakeover, EmergencyAccessType,
+    EmergencyAccessView,
+};
+use model::List;
+
+use super::dto::emergency_access_error::{EmergencyAccessError, EmergencyAccessResult};
+
+const APPROVAL_INTERVAL_SECONDS: u64 = 60 * 60;
+
+fn to_rfc3339(datetime: NaiveDateTime) -> String {
+    Utc.from_utc_datetime(&datetime).to_rfc3339()
+}
+
+fn status(emergency_access: &EmergencyAccess) -> EmergencyAccessStatus {
+    emergency_access
+        .status
+        .parse()
+        .unwrap_or(EmergencyAccessStatus::Invited)
+}
+
+fn access_type(emergency_access: &EmergencyAccess) -> EmergencyAccessType {
+    emergency_access
+        .access_type
+        .parse()
+        .unwrap_or(EmergencyAccessType::View)
+}
+
+/// When a pending recovery request gets approved if the grantor doesn't
+/// reject it.
+fn approval_date(emergency_access: &EmergencyAccess) -> Option<NaiveDateTime> {
+    emergency_access.recovery_requested_at.map(|requested_at| {
+        requested_at + chrono::Duration::days(emergency_access.wait_days as i64)
+    })
+}
+
+fn to_view(emergency_access: &EmergencyAccess, email: &str) -> EmergencyAccessView {
+    EmergencyAccessView {
+        id: emergency_access.id,
+        email: email.to_owned(),
+        access_type: access_type(emergency_access),
+        status: status(emergency_access),
+        wait_days: emergency_access.wait_days,
+        recovery_requested_at: emergency_access.recovery_requested_at.map(to_rfc3339),
+    }
+}
+
+async fn notify(to: &entity::users::Model, subject: String, body: String) {
+    if let Err(e) = MailService::send_email_from_system(
+        EmailAddress::new(Some(&to.name), &to.email),
+        subject,
+        MessageBody::Text(body),
+    )
+    .await
+    {
+        log::error!("{}", e);
+    }
+}
+
+pub struct EmergencyAccessService<T>
+where
+    T: EmergencyAccessRepository + UsersRepository,
+{
+    repository: T,
+}
+
+impl<T> EmergencyAccessService<T>
+where
+    T: EmergencyAccessRepository + UsersRepository,
+{
+    pub fn new(repository: T) -> EmergencyAccessService<T> {
+        EmergencyAccessService { repository }
+    }
+
+    pub async fn invite(
+        self,
+        user_id: i32,
+        register: EmergencyAccessRegister,
+    ) -> EmergencyAccessResult<EmergencyAccessView> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        if register.email.eq_ignore_ascii_case(&user.email) {
+            return Err(EmergencyAccessError::InvalidContact);
+        }
+        if self
+            .repository
+            .emergency_access_list_by_grantor_id(user_id)
+            .await
+            .iter()
+            .any(|e| e.email.eq_ignore_ascii_case(&register.email))
+        {
+            return Err(EmergencyAccessError::AlreadyInvited);
+        }
+
+        let emergency_access = self
+            .repository
+            .emergency_access_insert(NewEmergencyAccess {
+                grantor_id: user_id,
+                email: register.email.to_lowercase(),
+                access_type: register.access_type.as_str().to_owned(),
+                status: EmergencyAccessStatus::Invited.as_str().to_owned(),
+                wait_days: register.wait_days,
+                created_date: Utc::now().naive_utc(),
+            })
+            .await;
+
+        if let Err(e) = MailService::send_email_from_system(
+            EmailAddress::new(None, &emergency_access.email),
+            String::from("Emergency access invitation"),
+            MessageBody::Text(format!(
+                "{} added you as an emergency contact on OpenPasswd. \
+                 Accept the invitation from your account to be able to request access to their vault.",
+                user.name
+            )),
+        )
+        .await
+        {
+            log::error!("{}", e);
+        }
+
+        Ok(to_view(&emergency_access, &emergency_access.email))
+    }
+
+    pub async fn list_trusted(
+        self,
+        user_id: i32,
+    ) -> EmergencyAccessResult<List<EmergencyAccessView>> {
+        let result = self
+            .repository
+            .emergency_access_list_by_grantor_id(user_id)
+            .await;
+
+        Ok(List {
+            items: result.iter().map(|e| to_view(e, &e.email)).collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    pub async fn list_granted(
+        self,
+        user_id: i32,
+    ) -> EmergencyAccessResult<List<EmergencyAccessView>> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let result = self
+            .repository
+            .emergency_access_list_by_grantee(user_id, &user.email.to_lowercase())
+            .await;
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|(e, grantor)| to_view(e, &grantor.email))
+                .collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    /// Grantors remove trusted contacts, contacts may step down on their own.
+    pub async fn delete(self, user_id: i32, id: i32) -> EmergencyAccessResult {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let emergency_access = self.find(id).await?;
+        if emergency_access.grantor_id != user_id && !Self::is_grantee(&emergency_access, &user) {
+            return Err(EmergencyAccessError::NotFound);
+        }
+
+        self.repository.emergency_access_delete(id).await;
+        Ok(())
+    }
+
+    pub async fn accept(self, user_id: i32, id: i32) -> EmergencyAccessResult<EmergencyAccessView> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let emergency_access = self.find_as_grantee(&user, id).await?;
+        if status(&emergency_access) != EmergencyAccessStatus::Invited {
+            return Err(EmergencyAccessError::InvalidState);
+        }
+
+        // The master key of the grantor is wrapped for the contact right
+        // away, so that access can be granted later without the grantor.
+        let grantor = self
+            .repository
+            .users_find_by_id(emergency_access.grantor_id)
+            .await
+            .unwrap();
+        let (public_key, _) = user_key_pair(&self.repository, &user).await;
+        let master_key =
+            RsaCipher::from_public_key(&public_key).encrypt(grantor.master_key.as_ref().unwrap());
+        self.repository
+            .emergency_access_accept(id, user_id, master_key)
+            .await;
+
+        notify(
+            &grantor,
+            String::from("Emergency contact accepted"),
+            format!(
+                "{} accepted to be your emergency contact on OpenPasswd.",
+                user.name
+            ),
+        )
+        .await;
+
+        let emergency_access = self.find(id).await?;
+        Ok(to_view(&emergency_access, &grantor.email))
+    }
+
+    pub async fn request_recovery(
+        self,
+        user_id: i32,
+        id: i32,
+    ) -> EmergencyAccessResult<EmergencyAccessView> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let emergency_access = self.find_as_grantee(&user, id).await?;
+        if status(&emergency_access) != EmergencyAccessStatus::Accepted {
+            return Err(EmergencyAccessError::InvalidState);
+        }
+
+        let requested_at = Utc::now().naive_utc();
+        self.repository
+            .emergency_access_update_status(
+                id,
+                EmergencyAccessStatus::RecoveryRequested.as_str(),
+                Some(requested_at),
+            )
+            .await;
+
+        let grantor = self
+            .repository
+            .users_find_by_id(emergency_access.grantor_id)
+            .await
+            .unwrap();
+        notify(
+            &grantor,
+            String::from("Emergency access requested"),
+            format!(
+                "{} requested emergency access to your OpenPasswd vault. \
+                 Access will be granted on {} unless you reject the request.",
+                user.name,
+                to_rfc3339(
+                    requested_at + chrono::Duration::days(emergency_access.wait_days as i64)
+                )
+            ),
+        )
+        .await;
+
+        let emergency_access = self.find(id).await?;
+        Ok(to_view(&emergency_access, &grantor.email))
+    }
+
+    pub async fn approve(
+        self,
+        user_id: i32,
+        id: i32,
+    ) -> EmergencyAccessResult<EmergencyAccessView> {
+        let emergency_access = self.find_as_grantor(user_id, id).await?;
+        if status(&emergency_access) != EmergencyAccessStatus::RecoveryRequested {
+            return Err(EmergencyAccessError::InvalidState);
+        }
+
+        self.approve_request(&emergency_access).await;
+
+        let emergency_access = self.find(id).await?;
+        Ok(to_view(&emergency_access, &emergency_access.email))
+    }
+
+    /// Rejects a pending request or revokes an approved one, the contact
+    /// stays trusted and may request access again.
+    pub async fn reject(self, user_id: i32, id: i32) -> EmergencyAccessResult<EmergencyAccessView> {
+        let emergency_access = self.find_as_grantor(user_id, id).await?;
+        if !matches!(
+            status(&emergency_access),
+            EmergencyAccessStatus::RecoveryRequested | EmergencyAccessStatus::Approved
+        ) {
+            return Err(EmergencyAccessError::InvalidState);
+        }
+
+        self.repository
+            .emergency_access_update_status(id, EmergencyAccessStatus::Accepted.as_str(), None)
+            .await;
+
+        if let Some(grantee_id) = emergency_access.grantee_id {
+            let grantor = self.repository.users_find_by_id(user_id).await.unwrap();
+            let grantee = self.repository.users_find_by_id(grantee_id).await.unwrap();
+            notify(
+                &grantee,
+                String::from("Emergency access rejected"),
+                format!(
+                    "{} rejected your emergency access to their OpenPasswd vault.",
+                    grantor.name
+                ),
+            )
+            .await;
+        }
+
+        let emergency_access = self.find(id).await?;
+        Ok(to_view(&emergency_access, &emergency_access.email))
+    }
+
+    pub async fn view_vault(
+        self,
+        user_id: i32,
+        id: i32,
+    ) -> EmergencyAccessResult<List<AccountWithPasswordView>> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let emergency_access = self.find_approved(&user, id).await?;
+
+        let (_, private_key) = user_key_pair(&self.repository, &user).await;
+        let master_key = RsaCipher::from_private_key(&private_key)
+            .decrypt(emergency_access.master_key.as_ref().unwrap());
+        let key_cipher = AesGcmCipher::new(&master_key);
+
+        let result = self
+            .repository
+            .emergency_access_vault(emergency_access.grantor_id)
+            .await;
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|(account, account_passwords)| {
+                    let cipher = account
+                        .item_key
+                        .as_ref()
+                        .map(|item_key| AesGcmCipher::new(&key_cipher.decrypt(item_key)));
+                    let cipher = cipher.as_ref().unwrap_or(&key_cipher);
+                    let latest = account_passwords.iter().max_by_key(|p| p.id);
+
+                    AccountWithPasswordView {
+                        id: account.id,
+                        name: account.name.to_owned(),
+                        username: latest.map(|latest| latest.username.to_owned()),
+                        password: latest.map(|latest| cipher.decrypt(&latest.password)),
+                        revision: account.revision,
+                    }
+                })
+                .collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    /// Sets a new login password on the grantor's account. The vault stays
+    /// readable since it's encrypted with the master key, not the password.
+    /// The password follows the same policy as any other login password, the
+    /// breach count comes along when it was accepted with a warning.
+    pub async fn takeover(
+        self,
+        user_id: i32,
+        id: i32,
+        takeover: EmergencyAccessTakeover,
+        policy: &PasswordPolicy,
+        breaches: &BreachChecker,
+    ) -> EmergencyAccessResult<Option<u64>> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let emergency_access = self.find_approved(&user, id).await?;
+        if access_type(&emergency_access) != EmergencyAccessType::Takeover {
+            return Err(EmergencyAccessError::TakeoverNotAllowed);
+        }
+
+        let grantor = self
+            .repository
+            .users_find_by_id(emergency_access.grantor_id)
+            .await
+            .unwrap();
+        policy
+            .check(
+                "password",
+                &takeover.password,
+                &grantor.name,
+                &grantor.email,
+            )
+            .map_err(EmergencyAccessError::InvalidPassword)?;
+        let breached = breaches
+            .check_new_password(&takeover.password)
+            .await
+            .map_err(EmergencyAccessError::PasswordBreached)?;
+
+        self.repository
+            .users_update_password(grantor.id, hash_password(takeover.password))
+            .await;
+
+        notify(
+            &grantor,
+            String::from("Emergency takeover"),
+            format!(
+                "{} used emergency access to set a new password on your OpenPasswd account.",
+                user.name
+            ),
+        )
+        .await;
+
+        Ok(breached)
+    }
+
+    pub async fn approval_task(self) {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(APPROVAL_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+
+            let now = Utc::now().naive_utc();
+            for emergency_access in self
+                .repository
+                .emergency_access_list_by_status(EmergencyAccessStatus::RecoveryRequested.as_str())
+                .await
+            {
+                if approval_date(&emergency_access).is_some_and(|date| date <= now) {
+                    self.approve_request(&emergency_access).await;
+                    log::info!("Emergency access {} auto-approved", emergency_access.id);
+                }
+            }
+        }
+    }
+
+    async fn approve_request(&self, emergency_access: &EmergencyAccess) {
+        self.repository
+            .emergency_access_update_status(
+                emergency_access.id,
+                EmergencyAccessStatus::Approved.as_str(),
+                emergency_access.recovery_requested_at,
+            )
+            .await;
+
+        if let Some(grantee_id) = emergency_access.grantee_id {
+            let grantor = self
+                .repository
+                .users_find_by_id(emergency_access.grantor_id)
+                .await
+                .unwrap();
+            let grantee = self.repository.users_find_by_id(grantee_id).await.unwrap();
+            notify(
+                &grantee,
+                String::from("Emergency access approved"),
+                format!(
+                    "You now have emergency access to the OpenPasswd vault of {}.",
+                    grantor.name
+                ),
+            )
+            .await;
+        }
+    }
+
+    async fn find(&self, id: i32) -> EmergencyAccessResult<EmergencyAccess> {
+        self.repository
+            .emergency_access_find_by_id(id)
+            .await
+            .ok_or(EmergencyAccessError::NotFound)
+    }
+
+    async fn find_as_grantor(
+        &self,
+        user_id: i32,
+        id: i32,
+    ) -> EmergencyAccessResult<EmergencyAccess> {
+        match self.find(id).await? {
+            emergency_access if emergency_access.grantor_id == user_id => Ok(emergency_access),
+            _ => Err(EmergencyAccessError::NotFound),
+        }
+    }
+
+    async fn find_as_grantee(
+        &self,
+        user: &entity::users::Model,
+        id: i32,
+    ) -> EmergencyAccessResult<EmergencyAccess> {
+        match self.find(id).await? {
+            emergency_access if Self::is_grantee(&emergency_access, user) => Ok(emergency_access),
+            _ => Err(EmergencyAccessError::NotFound),
+        }
+    }
+
+    /// Finds an access the contact may use, approving requests whose wait
+    /// period is over without waiting for the approval task.
+    async fn find_approved(
+        &self,
+        user: &entity::users::Model,
+        id: i32,
+    ) -> EmergencyAccessResult<EmergencyAccess> {
+        let emergency_access = self.find_as_grantee(user, id).await?;
+        match status(&emergency_access) {
+            EmergencyAccessStatus::Approved => Ok(emergency_access),
+            EmergencyAccessStatus::RecoveryRequested
+                if approval_date(&emergency_access)
+                    .is_some_and(|date| date <= Utc::now().naive_utc()) =>
+            {
+                self.approve_request(&emergency_access).await;
+                Ok(emergency_access)
+            }
+            _ => Err(EmergencyAccessError::InvalidState),
+        }
+    }
+
+    fn is_grantee(emergency_access: &EmergencyAccess, user: &entity::users::Model) -> bool {
+        match emergency_access.grantee_id {
+            Some(grantee_id) => grantee_id == user.id,
+            None => emergency_access.email.eq_ignore_ascii_case(&user.email),
+        }
+    }
+}
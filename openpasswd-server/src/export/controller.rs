@@ -0,0 +1,32 @@
+use super::{
+    dto::export_error::{ExportError, ExportResult},
+    service::ExportService,
+};
+use crate::{
+    auth::dto::{claims::Claims, elevated_token::ElevatedClaims},
+    core::audit::RequestOrigin,
+    repository::Repository,
+};
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+
+/// Every password of the vault leaves in clear, the session must be elevated
+/// whatever the level of the accounts.
+pub async fn export(
+    claims: Claims,
+    origin: RequestOrigin,
+    elevated_claims: Option<ElevatedClaims>,
+    Extension(repository): Extension<Repository>,
+) -> ExportResult<impl IntoResponse> {
+    let elevated = elevated_claims
+        .map(|elevated_claims| elevated_claims.sub == claims.sub)
+        .unwrap_or(false);
+    if !elevated {
+        return Err(ExportError::ElevationRequired);
+    }
+
+    let export_service = ExportService::new(repository);
+    let result = export_service
+        .export(claims.sub, origin.with_device(claims.device.clone()))
+        .await?;
+    Ok((StatusCode::OK, Json(result)))
+}
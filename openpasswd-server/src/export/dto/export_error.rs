@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+pub type ExportResult<T = ()> = Result<T, ExportError>;
+
+#[derive(Debug)]
+pub enum ExportError {
+    ElevationRequired,
+}
+
+impl IntoResponse for ExportError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            ExportError::ElevationRequired => (
+                StatusCode::FORBIDDEN,
+                String::from("Password confirmation required"),
+            ),
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
@@ -0,0 +1,9 @@
+use axum::{routing::get, Router};
+
+pub mod controller;
+pub mod dto;
+mod service;
+
+pub fn route() -> Router {
+    Router::new().route("/api/export", get(self::controller::export))
+}
@@ -0,0 +1,126 @@
+use super::dto::export_error::ExportResult;
+use crate::core::audit::{self, RequestOrigin};
+use crate::core::cryptography::{AesGcmCipher, Cipher};
+use crate::repository::repositories::accounts_repository::AccountsRepository;
+use crate::repository::repositories::audit_repository::AuditRepository;
+use crate::repository::repositories::authorization_repository::AuthorizationRepository;
+use crate::repository::repositories::sync_repository::SyncRepository;
+use crate::repository::repositories::users_repository::UsersRepository;
+use crate::repository::repositories::webhooks_repository::WebhooksRepository;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use model::audit::AuditEventType;
+use model::export::{
+    ExportedAccount, ExportedGroup, ExportedPassword, VaultExport, EXPORT_VERSION,
+};
+
+fn to_rfc3339(datetime: NaiveDateTime) -> String {
+    Utc.from_utc_datetime(&datetime).to_rfc3339()
+}
+
+pub struct ExportService<T>
+where
+    T: AccountsRepository
+        + AuditRepository
+        + AuthorizationRepository
+        + SyncRepository
+        + UsersRepository
+        + WebhooksRepository,
+{
+    repository: T,
+}
+
+impl<T> ExportService<T>
+where
+    T: AccountsRepository
+        + AuditRepository
+        + AuthorizationRepository
+        + SyncRepository
+        + UsersRepository
+        + WebhooksRepository,
+{
+    pub fn new(repository: T) -> ExportService<T> {
+        ExportService { repository }
+    }
+
+    /// The personal groups of the user with every version of the
+    /// credentials. Organization collections belong to the organization and
+    /// are left out.
+    pub async fn export(self, user_id: i32, origin: RequestOrigin) -> ExportResult<VaultExport> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let key_cipher = AesGcmCipher::new(user.master_key.as_ref().unwrap());
+
+        let groups = self.repository.authorization_personal_groups(user_id).await;
+        let accounts = self
+            .repository
+            .sync_accounts_by_group_ids(groups.iter().map(|group| group.id).collect())
+            .await;
+        let account_tags = self
+            .repository
+            .account_tags_list_by_account_ids(accounts.iter().map(|account| account.id).collect())
+            .await;
+
+        let mut exported_groups = Vec::new();
+        for group in groups {
+            let mut exported_accounts = Vec::new();
+            for account in accounts
+                .iter()
+                .filter(|account| account.account_groups_id == group.id)
+            {
+                // Accounts without an item key predate sharing and use the
+                // key of the group directly.
+                let item_cipher = account
+                    .item_key
+                    .as_ref()
+                    .map(|item_key| AesGcmCipher::new(&key_cipher.decrypt(item_key)));
+                let cipher = item_cipher.as_ref().unwrap_or(&key_cipher);
+
+                let mut account_passwords = self
+                    .repository
+                    .accounts_passwords_list_account_id(account.id)
+                    .await;
+                account_passwords.sort_by_key(|password| password.id);
+
+                exported_accounts.push(ExportedAccount {
+                    name: account.name.to_owned(),
+                    url: account.url.to_owned(),
+                    level: account.level,
+                    favorite: account.favorite,
+                    two_factor: account.two_factor,
+                    tags: account_tags
+                        .iter()
+                        .filter(|(account_id, _)| *account_id == account.id)
+                        .map(|(_, name)| name.to_owned())
+                        .collect(),
+                    passwords: account_passwords
+                        .into_iter()
+                        .map(|password| ExportedPassword {
+                            username: password.username,
+                            password: cipher.decrypt(&password.password),
+                            created_date: to_rfc3339(password.created_date),
+                        })
+                        .collect(),
+                });
+            }
+
+            exported_groups.push(ExportedGroup {
+                name: group.name,
+                accounts: exported_accounts,
+            });
+        }
+
+        audit::record(
+            &self.repository,
+            Some(user_id),
+            AuditEventType::VaultExported,
+            None,
+            &origin,
+        )
+        .await;
+
+        Ok(VaultExport {
+            version: EXPORT_VERSION,
+            exported_at: Utc::now().to_rfc3339(),
+            groups: exported_groups,
+        })
+    }
+}
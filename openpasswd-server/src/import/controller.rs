@@ -0,0 +1,31 @@
+use super::{dto::import_error::ImportResult, service::ImportService};
+use crate::{
+    auth::dto::claims::Claims,
+    core::{audit::RequestOrigin, cache::Cache, validator::ValidatedJson},
+    repository::Repository,
+};
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+use model::import::ImportRequest;
+
+pub async fn import(
+    claims: Claims,
+    origin: RequestOrigin,
+    ValidatedJson(request): ValidatedJson<ImportRequest>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> ImportResult<impl IntoResponse> {
+    let import_service = ImportService::new(repository, cache);
+    let result = import_service
+        .import(
+            claims.sub,
+            request,
+            origin.with_device(claims.device.clone()),
+        )
+        .await?;
+    let status = if result.dry_run {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    Ok((status, Json(result)))
+}
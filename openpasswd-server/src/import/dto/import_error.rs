@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+pub type ImportResult<T = ()> = Result<T, ImportError>;
+
+#[derive(Debug)]
+pub enum ImportError {
+    InvalidFile(String),
+}
+
+impl IntoResponse for ImportError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            ImportError::InvalidFile(reason) => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid export file: {reason}"),
+            ),
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
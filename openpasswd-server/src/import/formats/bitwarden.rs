@@ -0,0 +1,168 @@
+use serde::Deserialize;
+
+use super::{ImportedItem, ParseResult, ParsedExport};
+
+const LOGIN_ITEM: u8 = 1;
+
+#[derive(Deserialize)]
+struct Export {
+    #[serde(default)]
+    encrypted: bool,
+    #[serde(default)]
+    folders: Vec<Folder>,
+    #[serde(default)]
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+struct Folder {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Item {
+    #[serde(rename = "type")]
+    item_type: u8,
+    name: String,
+    folder_id: Option<String>,
+    #[serde(default)]
+    favorite: bool,
+    notes: Option<String>,
+    login: Option<Login>,
+    fields: Option<Vec<Field>>,
+}
+
+#[derive(Deserialize)]
+struct Login {
+    username: Option<String>,
+    password: Option<String>,
+    totp: Option<String>,
+    uris: Option<Vec<Uri>>,
+}
+
+#[derive(Deserialize)]
+struct Uri {
+    uri: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Field {
+    name: Option<String>,
+}
+
+pub fn parse(data: &str) -> ParseResult {
+    let export: Export = serde_json::from_str(data).map_err(|e| e.to_string())?;
+    if export.encrypted {
+        return Err(String::from(
+            "encrypted exports can't be read, export the vault as unencrypted JSON",
+        ));
+    }
+
+    let mut parsed = ParsedExport::default();
+    for item in export.items {
+        let login = match (item.item_type, item.login) {
+            (LOGIN_ITEM, Some(login)) => login,
+            (item_type, _) => {
+                let kind = match item_type {
+                    2 => "secure note",
+                    3 => "card",
+                    4 => "identity",
+                    _ => "item without login",
+                };
+                parsed.skip(&item.name, format!("{kind} items are not supported"));
+                continue;
+            }
+        };
+
+        if item.notes.is_some_and(|notes| !notes.is_empty()) {
+            parsed.unsupported(&item.name, "notes");
+        }
+        if login.totp.is_some_and(|totp| !totp.is_empty()) {
+            parsed.unsupported(&item.name, "TOTP secret");
+        }
+        for field in item.fields.unwrap_or_default() {
+            let name = field.name.unwrap_or_default();
+            parsed.unsupported(&item.name, format!("custom field {name}"));
+        }
+
+        let mut uris = login
+            .uris
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|uri| uri.uri);
+        let url = uris.next();
+        for uri in uris {
+            parsed.unsupported(&item.name, format!("additional URL {uri}"));
+        }
+
+        let group = item.folder_id.and_then(|folder_id| {
+            export
+                .folders
+                .iter()
+                .find(|folder| folder.id == folder_id)
+                .map(|folder| folder.name.to_owned())
+        });
+        parsed.push(ImportedItem {
+            group,
+            name: item.name,
+            url,
+            username: login.username.unwrap_or_default(),
+            password: login.password.unwrap_or_default(),
+            favorite: item.favorite,
+            ..Default::default()
+        });
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    const EXPORT: &str = r#"{
+        "encrypted": false,
+        "folders": [{ "id": "f1", "name": "Work" }],
+        "items": [
+            {
+                "type": 1,
+                "name": "GitHub",
+                "folderId": "f1",
+                "favorite": true,
+                "notes": "recovery codes in the safe",
+                "login": {
+                    "username": "octocat",
+                    "password": "hunter2",
+                    "totp": null,
+                    "uris": [{ "uri": "https://github.com" }, { "uri": "https://gist.github.com" }]
+                }
+            },
+            { "type": 3, "name": "Visa", "folderId": null, "card": {} }
+        ]
+    }"#;
+
+    #[test]
+    fn maps_logins_and_reports_the_rest() {
+        let parsed = parse(EXPORT).unwrap();
+
+        assert_eq!(1, parsed.items.len());
+        let item = &parsed.items[0];
+        assert_eq!(Some("Work"), item.group.as_deref());
+        assert_eq!(Some("https://github.com"), item.url.as_deref());
+        assert_eq!("octocat", item.username);
+        assert!(item.favorite);
+
+        let unsupported: Vec<_> = parsed.unsupported.iter().map(|i| &i.detail).collect();
+        assert_eq!(
+            vec!["notes", "additional URL https://gist.github.com"],
+            unsupported
+        );
+        assert_eq!("Visa", parsed.skipped[0].item);
+    }
+
+    #[test]
+    fn refuses_encrypted_exports() {
+        assert!(parse(r#"{ "encrypted": true, "items": [] }"#).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use super::ParseResult;
+
+/// CSV export with a header row, columns are looked up by name since every
+/// tool orders them differently.
+pub struct CsvExport {
+    headers: Vec<String>,
+    pub records: Vec<csv::StringRecord>,
+}
+
+impl CsvExport {
+    pub fn parse(data: &str) -> ParseResult<CsvExport> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(data.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|e| e.to_string())?
+            .iter()
+            .map(|header| header.trim().to_ascii_lowercase())
+            .collect();
+        let records = reader
+            .records()
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+        Ok(CsvExport { headers, records })
+    }
+
+    /// Index of the first of `names` present in the header.
+    pub fn column(&self, names: &[&str]) -> Option<usize> {
+        names
+            .iter()
+            .find_map(|name| self.headers.iter().position(|header| header == name))
+    }
+
+    pub fn require(&self, names: &[&str]) -> ParseResult<usize> {
+        self.column(names)
+            .ok_or_else(|| format!("missing column {}", names[0]))
+    }
+}
+
+pub fn field(record: &csv::StringRecord, column: Option<usize>) -> String {
+    column
+        .and_then(|column| record.get(column))
+        .unwrap_or_default()
+        .to_owned()
+}
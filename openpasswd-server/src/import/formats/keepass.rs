@@ -0,0 +1,196 @@
+use roxmltree::{Document, Node};
+
+use super::csv_export::{field, CsvExport};
+use super::{ImportedItem, ParseResult, ParsedExport};
+
+/// KeePassXC writes `Group`, `Title`, `Username`, `Password`, `URL`,
+/// `Notes` and `TOTP`, KeePass 2 `Account`, `Login Name`, `Password`,
+/// `Web Site` and `Comments`.
+pub fn parse_csv(data: &str) -> ParseResult {
+    let export = CsvExport::parse(data)?;
+    let group = export.column(&["group"]);
+    let title = export.column(&["title", "account"]);
+    let username = export.column(&["username", "login name", "user name"]);
+    let password = export.require(&["password"])?;
+    let url = export.column(&["url", "web site"]);
+    let notes = export.column(&["notes", "comments"]);
+    let totp = export.column(&["totp"]);
+
+    let mut parsed = ParsedExport::default();
+    for record in export.records.iter() {
+        let name = field(record, title);
+        if !field(record, notes).is_empty() {
+            parsed.unsupported(&name, "notes");
+        }
+        if !field(record, totp).is_empty() {
+            parsed.unsupported(&name, "TOTP secret");
+        }
+
+        // KeePassXC paths start with the root group, which is the database
+        // itself rather than a folder.
+        let group = field(record, group);
+        parsed.push(ImportedItem {
+            group: group.split_once('/').map(|(_, path)| path.to_owned()),
+            name,
+            url: Some(field(record, url)),
+            username: field(record, username),
+            password: field(record, Some(password)),
+            ..Default::default()
+        });
+    }
+    Ok(parsed)
+}
+
+pub fn parse_xml(data: &str) -> ParseResult {
+    let document = Document::parse(data).map_err(|e| e.to_string())?;
+    let keepass = document.root_element();
+    if !keepass.has_tag_name("KeePassFile") {
+        return Err(String::from("not a KeePass XML export"));
+    }
+
+    let recycle_bin = child(keepass, "Meta").and_then(|meta| child_text(meta, "RecycleBinUUID"));
+    let root = child(keepass, "Root")
+        .and_then(|root| child(root, "Group"))
+        .ok_or_else(|| String::from("missing root group"))?;
+
+    let mut parsed = ParsedExport::default();
+    walk(root, None, recycle_bin, &mut parsed);
+    Ok(parsed)
+}
+
+fn walk(group: Node, path: Option<String>, recycle_bin: Option<&str>, parsed: &mut ParsedExport) {
+    for entry in group.children().filter(|node| node.has_tag_name("Entry")) {
+        parse_entry(entry, path.clone(), parsed);
+    }
+
+    for group in group.children().filter(|node| node.has_tag_name("Group")) {
+        if recycle_bin.is_some() && child_text(group, "UUID") == recycle_bin {
+            continue;
+        }
+        let name = child_text(group, "Name").unwrap_or_default();
+        let path = match &path {
+            Some(path) => format!("{path}/{name}"),
+            None => name.to_owned(),
+        };
+        walk(group, Some(path), recycle_bin, parsed);
+    }
+}
+
+fn parse_entry(entry: Node, group: Option<String>, parsed: &mut ParsedExport) {
+    let mut item = ImportedItem {
+        group,
+        tags: child_text(entry, "Tags")
+            .unwrap_or_default()
+            .split([';', ','])
+            .map(|tag| tag.trim().to_owned())
+            .collect(),
+        ..Default::default()
+    };
+    let mut unsupported = Vec::new();
+
+    // Past versions of the entry live under `History`, only the direct
+    // `String` children are current.
+    for string in entry.children().filter(|node| node.has_tag_name("String")) {
+        let key = child_text(string, "Key").unwrap_or_default();
+        let value = child_text(string, "Value").unwrap_or_default().to_owned();
+        match key {
+            "Title" => item.name = value,
+            "UserName" => item.username = value,
+            "Password" => item.password = value,
+            "URL" => item.url = Some(value),
+            _ if value.is_empty() => {}
+            "Notes" => unsupported.push(String::from("notes")),
+            key => unsupported.push(format!("custom field {key}")),
+        }
+    }
+    if entry.children().any(|node| node.has_tag_name("Binary")) {
+        unsupported.push(String::from("attachments"));
+    }
+
+    for detail in unsupported {
+        parsed.unsupported(&item.name, detail);
+    }
+    parsed.push(item);
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|child| child.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_csv, parse_xml};
+
+    #[test]
+    fn keepassxc_csv() {
+        let parsed = parse_csv(
+            "\"Group\",\"Title\",\"Username\",\"Password\",\"URL\",\"Notes\",\"TOTP\"\n\
+             \"Root\",\"Mail\",\"me\",\"secret\",\"https://mail.example\",\"\",\"\"\n\
+             \"Root/Work/Servers\",\"ssh\",\"admin\",\"toor\",\"\",\"port 2222\",\"\"\n\
+             \"Root\",\"Empty\",\"nobody\",\"\",\"\",\"\",\"\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(2, parsed.items.len());
+        assert_eq!(None, parsed.items[0].group);
+        assert_eq!(Some("Work/Servers"), parsed.items[1].group.as_deref());
+        assert_eq!(None, parsed.items[1].url);
+        assert_eq!("notes", parsed.unsupported[0].detail);
+        assert_eq!("Empty", parsed.skipped[0].item);
+    }
+
+    #[test]
+    fn keepass_xml_groups_and_recycle_bin() {
+        let parsed = parse_xml(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <KeePassFile>
+                <Meta><RecycleBinUUID>bin</RecycleBinUUID></Meta>
+                <Root>
+                    <Group>
+                        <UUID>root</UUID>
+                        <Name>Database</Name>
+                        <Entry>
+                            <Tags>mail;personal</Tags>
+                            <String><Key>Title</Key><Value>Mail</Value></String>
+                            <String><Key>UserName</Key><Value>me</Value></String>
+                            <String><Key>Password</Key><Value ProtectInMemory="True">secret</Value></String>
+                            <String><Key>PIN</Key><Value>1234</Value></String>
+                            <History>
+                                <Entry>
+                                    <String><Key>Password</Key><Value>old</Value></String>
+                                </Entry>
+                            </History>
+                        </Entry>
+                        <Group>
+                            <UUID>work</UUID>
+                            <Name>Work</Name>
+                            <Entry>
+                                <String><Key>Title</Key><Value>VPN</Value></String>
+                                <String><Key>Password</Key><Value>vpn</Value></String>
+                            </Entry>
+                        </Group>
+                        <Group>
+                            <UUID>bin</UUID>
+                            <Name>Recycle Bin</Name>
+                            <Entry>
+                                <String><Key>Title</Key><Value>Gone</Value></String>
+                                <String><Key>Password</Key><Value>gone</Value></String>
+                            </Entry>
+                        </Group>
+                    </Group>
+                </Root>
+            </KeePassFile>"#,
+        )
+        .unwrap();
+
+        assert_eq!(2, parsed.items.len());
+        assert_eq!("secret", parsed.items[0].password);
+        assert_eq!(vec!["mail", "personal"], parsed.items[0].tags);
+        assert_eq!(Some("Work"), parsed.items[1].group.as_deref());
+        assert_eq!("custom field PIN", parsed.unsupported[0].detail);
+    }
+}
@@ -0,0 +1,64 @@
+use super::csv_export::{field, CsvExport};
+use super::{is_true, ImportedItem, ParseResult, ParsedExport};
+
+/// Secure notes are exported as items with this URL.
+const SECURE_NOTE_URL: &str = "http://sn";
+
+pub fn parse(data: &str) -> ParseResult {
+    let export = CsvExport::parse(data)?;
+    let url = export.require(&["url"])?;
+    let username = export.column(&["username"]);
+    let password = export.require(&["password"])?;
+    let totp = export.column(&["totp"]);
+    let extra = export.column(&["extra"]);
+    let name = export.column(&["name"]);
+    let grouping = export.column(&["grouping"]);
+    let favorite = export.column(&["fav"]);
+
+    let mut parsed = ParsedExport::default();
+    for record in export.records.iter() {
+        let name = field(record, name);
+        let url = field(record, Some(url));
+        if url == SECURE_NOTE_URL {
+            parsed.skip(&name, "secure note items are not supported");
+            continue;
+        }
+        if !field(record, extra).is_empty() {
+            parsed.unsupported(&name, "notes");
+        }
+        if !field(record, totp).is_empty() {
+            parsed.unsupported(&name, "TOTP secret");
+        }
+
+        parsed.push(ImportedItem {
+            group: Some(field(record, grouping).replace('\\', "/")),
+            name,
+            url: Some(url),
+            username: field(record, username),
+            password: field(record, Some(password)),
+            favorite: is_true(&field(record, favorite)),
+            ..Default::default()
+        });
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn lastpass_csv() {
+        let parsed = parse(
+            "url,username,password,totp,extra,name,grouping,fav\n\
+             https://example.com,me,secret,,,Example,Social\\Forums,1\n\
+             http://sn,,,,wifi key,Home wifi,,0\n",
+        )
+        .unwrap();
+
+        assert_eq!(1, parsed.items.len());
+        assert_eq!(Some("Social/Forums"), parsed.items[0].group.as_deref());
+        assert!(parsed.items[0].favorite);
+        assert_eq!("Home wifi", parsed.skipped[0].item);
+    }
+}
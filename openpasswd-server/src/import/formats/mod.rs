@@ -0,0 +1,163 @@
+use chrono::NaiveDateTime;
+use model::import::{ImportFormat, ImportIssue};
+
+mod bitwarden;
+mod csv_export;
+mod keepass;
+mod lastpass;
+mod onepassword;
+mod openpasswd;
+
+/// Column lengths of `account_groups.name`, `accounts.name`, `accounts.url`,
+/// `account_passwords.username` and `tags.name`.
+const MAX_GROUP_LENGTH: usize = 50;
+const MAX_NAME_LENGTH: usize = 50;
+const MAX_URL_LENGTH: usize = 255;
+const MAX_USERNAME_LENGTH: usize = 100;
+const MAX_TAG_LENGTH: usize = 50;
+
+/// Login found in an export, before it's mapped onto the vault.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportedItem {
+    /// Folder path, `None` for items at the top of the export.
+    pub group: Option<String>,
+    pub name: String,
+    pub url: Option<String>,
+    pub username: String,
+    pub password: String,
+    pub favorite: bool,
+    pub two_factor: bool,
+    pub tags: Vec<String>,
+    pub level: Option<i16>,
+    /// Previous credentials, oldest first.
+    pub history: Vec<ImportedPassword>,
+    pub created_date: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImportedPassword {
+    pub username: String,
+    pub password: String,
+    pub created_date: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedExport {
+    /// Groups to create even when no item goes into them.
+    pub groups: Vec<String>,
+    pub items: Vec<ImportedItem>,
+    pub unsupported: Vec<ImportIssue>,
+    pub skipped: Vec<ImportIssue>,
+}
+
+impl ParsedExport {
+    fn push(&mut self, mut item: ImportedItem) {
+        if item.name.trim().is_empty() {
+            item.name = item.url.clone().unwrap_or_else(|| String::from("Untitled"));
+        }
+        if item.password.is_empty() {
+            self.skip(&item.name, "no password");
+            return;
+        }
+        item.url = item.url.filter(|url| !url.trim().is_empty());
+        item.group = item.group.filter(|group| !group.trim().is_empty());
+        item.tags = item
+            .tags
+            .iter()
+            .map(|tag| tag.trim().to_owned())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if let Some(detail) = too_long(&item) {
+            self.skip(&item.name, detail);
+            return;
+        }
+        self.items.push(item);
+    }
+
+    /// Group to create even if no item goes into it.
+    fn push_group(&mut self, name: String) {
+        if name.chars().count() > MAX_GROUP_LENGTH {
+            self.skip(
+                &name,
+                format!("folder name longer than {MAX_GROUP_LENGTH} characters"),
+            );
+            return;
+        }
+        self.groups.push(name);
+    }
+
+    fn unsupported(&mut self, item: &str, detail: impl Into<String>) {
+        self.unsupported.push(ImportIssue {
+            item: item.to_owned(),
+            detail: detail.into(),
+        });
+    }
+
+    fn skip(&mut self, item: &str, detail: impl Into<String>) {
+        self.skipped.push(ImportIssue {
+            item: item.to_owned(),
+            detail: detail.into(),
+        });
+    }
+}
+
+pub type ParseResult<T = ParsedExport> = Result<T, String>;
+
+pub fn parse(format: ImportFormat, data: &str) -> ParseResult {
+    // Exports written on Windows often start with a byte order mark.
+    let data = data.trim_start_matches('\u{feff}');
+    match format {
+        ImportFormat::Bitwarden => bitwarden::parse(data),
+        ImportFormat::KeepassCsv => keepass::parse_csv(data),
+        ImportFormat::KeepassXml => keepass::parse_xml(data),
+        ImportFormat::OnePassword => onepassword::parse(data),
+        ImportFormat::Lastpass => lastpass::parse(data),
+        ImportFormat::OpenPasswd => openpasswd::parse(data),
+    }
+}
+
+/// What doesn't fit in the vault columns, the item is skipped rather than
+/// truncated.
+fn too_long(item: &ImportedItem) -> Option<String> {
+    let longer = |value: &str, max: usize| value.chars().count() > max;
+    if longer(&item.name, MAX_NAME_LENGTH) {
+        return Some(format!("name longer than {MAX_NAME_LENGTH} characters"));
+    }
+    if item
+        .group
+        .as_deref()
+        .is_some_and(|group| longer(group, MAX_GROUP_LENGTH))
+    {
+        return Some(format!(
+            "folder name longer than {MAX_GROUP_LENGTH} characters"
+        ));
+    }
+    if item
+        .url
+        .as_deref()
+        .is_some_and(|url| longer(url, MAX_URL_LENGTH))
+    {
+        return Some(format!("URL longer than {MAX_URL_LENGTH} characters"));
+    }
+    if longer(&item.username, MAX_USERNAME_LENGTH)
+        || item
+            .history
+            .iter()
+            .any(|password| longer(&password.username, MAX_USERNAME_LENGTH))
+    {
+        return Some(format!(
+            "username longer than {MAX_USERNAME_LENGTH} characters"
+        ));
+    }
+    if item.tags.iter().any(|tag| longer(tag, MAX_TAG_LENGTH)) {
+        return Some(format!("tag longer than {MAX_TAG_LENGTH} characters"));
+    }
+    None
+}
+
+fn is_true(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes"
+    )
+}
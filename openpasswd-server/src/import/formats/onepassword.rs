@@ -0,0 +1,97 @@
+use super::csv_export::{field, CsvExport};
+use super::{is_true, ImportedItem, ParseResult, ParsedExport};
+
+/// 1Password CSV exports have no vault or folder column, every item lands in
+/// the default import group.
+pub fn parse(data: &str) -> ParseResult {
+    let export = CsvExport::parse(data)?;
+    let title = export.column(&["title"]);
+    let url = export.column(&["url", "website", "login url"]);
+    let username = export.column(&["username", "login username"]);
+    let password = export.require(&["password", "login password"])?;
+    let otp = export.column(&["otpauth", "one-time password"]);
+    let favorite = export.column(&["favorite"]);
+    let archived = export.column(&["archived"]);
+    let tags = export.column(&["tags"]);
+    let notes = export.column(&["notes", "notesplain"]);
+
+    let mut parsed = ParsedExport::default();
+    for record in export.records.iter() {
+        let name = field(record, title);
+        if is_true(&field(record, archived)) {
+            parsed.skip(&name, "archived");
+            continue;
+        }
+        if !field(record, notes).is_empty() {
+            parsed.unsupported(&name, "notes");
+        }
+        if !field(record, otp).is_empty() {
+            parsed.unsupported(&name, "TOTP secret");
+        }
+
+        parsed.push(ImportedItem {
+            group: None,
+            name,
+            url: Some(field(record, url)),
+            username: field(record, username),
+            password: field(record, Some(password)),
+            favorite: is_true(&field(record, favorite)),
+            tags: field(record, tags)
+                .split(';')
+                .map(|tag| tag.trim().to_owned())
+                .collect(),
+            ..Default::default()
+        });
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn onepassword_csv() {
+        let parsed = parse(
+            "\"Title\",\"Url\",\"Username\",\"Password\",\"OTPAuth\",\"Favorite\",\"Archived\",\"Tags\",\"Notes\"\n\
+             \"Bank\",\"https://bank.example\",\"me\",\"secret\",\"otpauth://totp/x\",\"true\",\"false\",\"finance;home\",\"\"\n\
+             \"Old\",\"\",\"me\",\"old\",\"\",\"false\",\"true\",\"\",\"\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(1, parsed.items.len());
+        assert_eq!(vec!["finance", "home"], parsed.items[0].tags);
+        assert!(parsed.items[0].favorite);
+        assert_eq!("TOTP secret", parsed.unsupported[0].detail);
+        assert_eq!("archived", parsed.skipped[0].detail);
+    }
+
+    #[test]
+    fn skips_what_does_not_fit_the_vault() {
+        let long_name = "n".repeat(51);
+        let long_tag = "t".repeat(51);
+        let parsed = parse(&format!(
+            "\"Title\",\"Username\",\"Password\",\"Tags\"\n\
+             \"{long_name}\",\"me\",\"secret\",\"\"\n\
+             \"Mail\",\"me\",\"secret\",\"{long_tag}\"\n\
+             \"Bank\",\"me\",\"secret\",\"finance; ;home;\"\n",
+        ))
+        .unwrap();
+
+        assert_eq!(1, parsed.items.len());
+        assert_eq!("Bank", parsed.items[0].name);
+        assert_eq!(vec!["finance", "home"], parsed.items[0].tags);
+        let skipped: Vec<_> = parsed
+            .skipped
+            .iter()
+            .map(|issue| (issue.item.as_str(), issue.detail.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                (long_name.as_str(), "name longer than 50 characters"),
+                ("Mail", "tag longer than 50 characters"),
+            ],
+            skipped
+        );
+    }
+}
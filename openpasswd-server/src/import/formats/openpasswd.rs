@@ -0,0 +1,97 @@
+use chrono::{DateTime, NaiveDateTime};
+use model::export::{VaultExport, EXPORT_VERSION};
+
+use super::{ImportedItem, ImportedPassword, ParseResult, ParsedExport};
+
+/// Restores an export of `GET /api/export`, decrypted by the client if it was
+/// encrypted.
+pub fn parse(data: &str) -> ParseResult {
+    let export: VaultExport = serde_json::from_str(data).map_err(|e| e.to_string())?;
+    if export.version > EXPORT_VERSION {
+        return Err(format!("unknown export version {}", export.version));
+    }
+
+    let mut parsed = ParsedExport::default();
+    for group in export.groups {
+        parsed.push_group(group.name.to_owned());
+        for account in group.accounts {
+            let mut passwords: Vec<ImportedPassword> = account
+                .passwords
+                .into_iter()
+                .map(|password| ImportedPassword {
+                    username: password.username,
+                    password: password.password,
+                    created_date: parse_date(&password.created_date),
+                })
+                .collect();
+            let current = match passwords.pop() {
+                Some(current) => current,
+                None => {
+                    parsed.skip(&account.name, "no password");
+                    continue;
+                }
+            };
+
+            parsed.push(ImportedItem {
+                group: Some(group.name.to_owned()),
+                name: account.name,
+                url: account.url,
+                username: current.username,
+                password: current.password,
+                favorite: account.favorite,
+                two_factor: account.two_factor,
+                tags: account.tags,
+                level: Some(account.level),
+                history: passwords,
+                created_date: current.created_date,
+            });
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|datetime| datetime.naive_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn restores_history_and_empty_groups() {
+        let parsed = parse(
+            r#"{
+                "version": 1,
+                "exported_at": "2022-09-01T10:00:00+00:00",
+                "groups": [
+                    {
+                        "name": "Personal",
+                        "accounts": [{
+                            "name": "Mail",
+                            "level": 3,
+                            "favorite": true,
+                            "tags": ["mail"],
+                            "passwords": [
+                                { "username": "me", "password": "old", "created_date": "2022-01-01T00:00:00+00:00" },
+                                { "username": "me", "password": "new", "created_date": "2022-06-01T00:00:00+00:00" }
+                            ]
+                        }]
+                    },
+                    { "name": "Empty", "accounts": [] }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(vec!["Personal", "Empty"], parsed.groups);
+        let item = &parsed.items[0];
+        assert_eq!("new", item.password);
+        assert_eq!(Some(3), item.level);
+        assert_eq!(1, item.history.len());
+        assert_eq!("old", item.history[0].password);
+        assert!(item.created_date.is_some());
+    }
+}
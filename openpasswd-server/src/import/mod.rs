@@ -0,0 +1,10 @@
+use axum::{routing::post, Router};
+
+pub mod controller;
+pub mod dto;
+mod formats;
+mod service;
+
+pub fn route() -> Router {
+    Router::new().route("/api/import", post(self::controller::import))
+}
@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+
+use super::dto::import_error::{ImportError, ImportResult};
+use super::formats;
+use crate::core::audit::{self, RequestOrigin};
+use crate::core::cache::Cache;
+use crate::core::cryptography::{generate_key, AesGcmCipher, Cipher};
+use crate::core::notifications::{group_recipients, notify_users};
+use crate::repository::models::import::{NewImportedAccount, NewImportedPassword};
+use crate::repository::repositories::audit_repository::AuditRepository;
+use crate::repository::repositories::authorization_repository::AuthorizationRepository;
+use crate::repository::repositories::import_repository::ImportRepository;
+use crate::repository::repositories::shares_repository::SharesRepository;
+use crate::repository::repositories::sync_repository::SyncRepository;
+use crate::repository::repositories::users_repository::UsersRepository;
+use crate::repository::repositories::webhooks_repository::WebhooksRepository;
+use model::audit::AuditEventType;
+use model::import::{ImportIssue, ImportReport, ImportRequest};
+use model::notifications::NotificationEvent;
+use model::sync::{SyncAction, SyncItemType};
+
+/// Group of the items exported outside of any folder.
+const DEFAULT_GROUP: &str = "Imported";
+const DEFAULT_ACCOUNT_LEVEL: i16 = 1;
+const MIN_ACCOUNT_LEVEL: i16 = 1;
+const MAX_ACCOUNT_LEVEL: i16 = 5;
+
+pub struct ImportService<T>
+where
+    T: AuditRepository
+        + AuthorizationRepository
+        + ImportRepository
+        + SharesRepository
+        + SyncRepository
+        + UsersRepository
+        + WebhooksRepository,
+{
+    repository: T,
+    cache: Cache,
+}
+
+impl<T> ImportService<T>
+where
+    T: AuditRepository
+        + AuthorizationRepository
+        + ImportRepository
+        + SharesRepository
+        + SyncRepository
+        + UsersRepository
+        + WebhooksRepository,
+{
+    pub fn new(repository: T, cache: Cache) -> ImportService<T> {
+        ImportService { repository, cache }
+    }
+
+    /// Imports the export into the personal groups of the user, folders
+    /// being matched to groups by name.
+    pub async fn import(
+        self,
+        user_id: i32,
+        request: ImportRequest,
+        origin: RequestOrigin,
+    ) -> ImportResult<ImportReport> {
+        let parsed =
+            formats::parse(request.format, &request.data).map_err(ImportError::InvalidFile)?;
+
+        let groups = self.repository.authorization_personal_groups(user_id).await;
+        let mut group_ids: HashMap<String, i32> = HashMap::new();
+        for group in groups.iter() {
+            group_ids.entry(group.name.to_owned()).or_insert(group.id);
+        }
+        let mut logins: HashSet<(String, String, String)> = self
+            .repository
+            .import_logins(groups.iter().map(|group| group.id).collect())
+            .await
+            .into_iter()
+            .filter_map(|(group_id, name, username)| {
+                groups
+                    .iter()
+                    .find(|group| group.id == group_id)
+                    .map(|group| (group.name.to_owned(), name, username))
+            })
+            .collect();
+
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let key_cipher = AesGcmCipher::new(user.master_key.as_ref().unwrap());
+
+        // In the order of the file, each name once.
+        let mut seen_groups: HashSet<String> = HashSet::new();
+        let mut new_groups: Vec<String> = parsed
+            .groups
+            .into_iter()
+            .filter(|group| !group_ids.contains_key(group) && seen_groups.insert(group.to_owned()))
+            .collect();
+        let mut accounts = Vec::new();
+        let now = chrono::Utc::now().naive_utc();
+        let mut duplicates = Vec::new();
+        for item in parsed.items {
+            let group = item.group.unwrap_or_else(|| String::from(DEFAULT_GROUP));
+            if !logins.insert((
+                group.to_owned(),
+                item.name.to_owned(),
+                item.username.to_owned(),
+            )) {
+                duplicates.push(ImportIssue {
+                    item: item.name,
+                    detail: format!("already in {group}"),
+                });
+                continue;
+            }
+            if !group_ids.contains_key(&group) && !new_groups.contains(&group) {
+                new_groups.push(group.to_owned());
+            }
+
+            let item_key = generate_key();
+            let item_cipher = AesGcmCipher::new(&item_key);
+            let mut passwords: Vec<NewImportedPassword> = item
+                .history
+                .into_iter()
+                .map(|password| NewImportedPassword {
+                    username: password.username,
+                    password: item_cipher.encrypt(&password.password),
+                    created_date: password.created_date.unwrap_or(now),
+                })
+                .collect();
+            passwords.push(NewImportedPassword {
+                username: item.username,
+                password: item_cipher.encrypt(&item.password),
+                created_date: item.created_date.unwrap_or(now),
+            });
+            let mut tags: Vec<String> = item.tags.iter().map(|tag| tag.trim().to_owned()).collect();
+            tags.sort();
+            tags.dedup();
+            accounts.push(NewImportedAccount {
+                group,
+                name: item.name,
+                level: item
+                    .level
+                    .map(|level| level.clamp(MIN_ACCOUNT_LEVEL, MAX_ACCOUNT_LEVEL))
+                    .unwrap_or(DEFAULT_ACCOUNT_LEVEL),
+                url: item.url,
+                favorite: item.favorite,
+                two_factor: item.two_factor,
+                item_key: key_cipher.encrypt(&item_key),
+                passwords,
+                tags,
+            });
+        }
+
+        let report = ImportReport {
+            dry_run: request.dry_run,
+            imported: accounts.len() as u32,
+            groups_created: new_groups.clone(),
+            duplicates,
+            unsupported: parsed.unsupported,
+            skipped: parsed.skipped,
+        };
+        if request.dry_run || (accounts.is_empty() && report.groups_created.is_empty()) {
+            return Ok(report);
+        }
+
+        let (created_groups, created_accounts) = self
+            .repository
+            .import_vault(user_id, group_ids, new_groups, accounts)
+            .await;
+
+        // A new group brings its accounts along on the clients, the accounts
+        // added to existing groups are announced one by one.
+        for group in created_groups.iter() {
+            let user_ids = group_recipients(&self.repository, group, None).await;
+            self.repository
+                .sync_record(
+                    &user_ids,
+                    SyncItemType::Group.as_str(),
+                    group.id,
+                    SyncAction::Created.as_str(),
+                )
+                .await;
+            notify_users(
+                &self.cache,
+                &user_ids,
+                NotificationEvent::GroupCreated,
+                group.id,
+            )
+            .await;
+        }
+        for account in created_accounts.iter() {
+            if let Some(group) = groups
+                .iter()
+                .find(|group| group.id == account.account_groups_id)
+            {
+                let user_ids = group_recipients(&self.repository, group, Some(account.id)).await;
+                self.repository
+                    .sync_record(
+                        &user_ids,
+                        SyncItemType::Account.as_str(),
+                        account.id,
+                        SyncAction::Created.as_str(),
+                    )
+                    .await;
+                notify_users(
+                    &self.cache,
+                    &user_ids,
+                    NotificationEvent::ItemCreated,
+                    account.id,
+                )
+                .await;
+            }
+        }
+
+        audit::record(
+            &self.repository,
+            Some(user_id),
+            AuditEventType::VaultImported,
+            Some(format!("import:{}", request.format.as_str())),
+            &origin,
+        )
+        .await;
+
+        Ok(report)
+    }
+}
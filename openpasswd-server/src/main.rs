@@ -1,5 +1,12 @@
 use crate::{
-    core::cache::Cache, core::mail_service::EmailAddress, core::mail_service::MailService,
+    core::cache::Cache,
+    core::mail_service::EmailAddress,
+    core::mail_service::MailService,
+    core::{
+        breached_passwords::BreachChecker, elevation::ElevationPolicy,
+        notifications::NotificationHub, password_age::PasswordAgePolicy,
+        password_policy::PasswordPolicy,
+    },
     repository::Repository,
 };
 use axum::{
@@ -15,10 +22,20 @@ use tokio::signal;
 use tower_http::cors::CorsLayer;
 
 mod accounts;
+mod audit;
 mod auth;
 mod core;
 mod devices;
+mod emergency_access;
+mod export;
+mod import;
+mod notifications;
+mod organizations;
+mod reports;
 mod repository;
+mod sync;
+mod trash;
+mod webhooks;
 
 #[tokio::main]
 async fn main() {
@@ -29,14 +46,40 @@ async fn main() {
     repository.migration_run().await;
 
     let cache = Cache::new().unwrap();
+    let hub = NotificationHub::new();
+    let password_policy = PasswordPolicy::from_env();
+    let elevation = ElevationPolicy::from_env();
+    let breaches = BreachChecker::from_env();
+    let password_age = PasswordAgePolicy::from_env();
+
+    tokio::spawn(trash::purge_task(repository.clone(), cache.clone()));
+    tokio::spawn(accounts::reminder_task(repository.clone(), cache.clone()));
+    tokio::spawn(emergency_access::approval_task(repository.clone()));
+    tokio::spawn(webhooks::delivery_task(repository.clone()));
+    tokio::spawn(notifications::listen_task(hub.clone(), cache.clone()));
 
     let mut app = Router::new()
         .merge(root())
         .merge(auth::route())
         .merge(accounts::route())
+        .merge(audit::route())
         .merge(devices::route())
+        .merge(emergency_access::route())
+        .merge(export::route())
+        .merge(import::route())
+        .merge(notifications::route())
+        .merge(organizations::route())
+        .merge(reports::route())
+        .merge(sync::route())
+        .merge(trash::route())
+        .merge(webhooks::route())
         .layer(Extension(repository))
         .layer(Extension(cache))
+        .layer(Extension(hub))
+        .layer(Extension(password_policy))
+        .layer(Extension(elevation))
+        .layer(Extension(breaches))
+        .layer(Extension(password_age))
         .fallback(handler_404.into_service());
 
     if let Ok(allow_origin) = std::env::var("CORS_ALLOW_ORIGIN") {
@@ -52,7 +95,7 @@ async fn main() {
     let addr = SocketAddr::from(([0, 0, 0, 0], 7777));
     log::info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await
         .unwrap();
@@ -94,7 +137,7 @@ async fn mail_check() -> impl IntoResponse {
     {
         Ok(()) => StatusCode::OK,
         Err(e) => {
-            log::error!("{:?}", e);
+            log::error!("{}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         }
     }
@@ -0,0 +1,48 @@
+use crate::{auth::dto::claims::Claims, core::notifications::NotificationHub};
+use axum::{
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use futures::{stream, Stream};
+use model::notifications::NotificationEvent;
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Server-sent events stream of the changes visible to the caller. It ends
+/// when the session it was opened with logs out.
+pub async fn stream(
+    claims: Claims,
+    Extension(hub): Extension<NotificationHub>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = hub.subscribe();
+    let Claims { sub, jti, .. } = claims;
+
+    let events = stream::unfold(Some(receiver), move |receiver| {
+        let jti = jti.to_owned();
+        async move {
+            let mut receiver = receiver?;
+            loop {
+                match receiver.recv().await {
+                    Ok((user_id, notification)) if user_id == sub => {
+                        let event = Event::default()
+                            .event(notification.event.as_str())
+                            .json_data(&notification)
+                            .unwrap();
+                        let revoked = notification.event == NotificationEvent::SessionRevoked
+                            && notification.session.as_deref() == Some(jti.as_str());
+                        let next = if revoked { None } else { Some(receiver) };
+                        return Some((Ok(event), next));
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        log::warn!("Notification stream of user {sub} skipped {skipped} events");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
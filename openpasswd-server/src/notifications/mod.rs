@@ -0,0 +1,14 @@
+use axum::{routing::get, Router};
+
+use crate::core::{cache::Cache, notifications::NotificationHub};
+
+pub mod controller;
+
+pub fn route() -> Router {
+    Router::new().route("/api/notifications", get(self::controller::stream))
+}
+
+/// Relays the notifications published on Redis to the local streams.
+pub async fn listen_task(hub: NotificationHub, cache: Cache) {
+    hub.listen(cache).await
+}
@@ -0,0 +1,158 @@
+use super::{dto::organizations_error::OrganizationResult, service::OrganizationService};
+use crate::{
+    auth::dto::claims::Claims,
+    core::{cache::Cache, validator::ValidatedJson},
+    repository::Repository,
+};
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use model::{
+    accounts::AccountGroupRegister,
+    organizations::{
+        CollectionMemberRegister, OrganizationInvitationAccept, OrganizationInvite,
+        OrganizationMemberUpdate, OrganizationRegister,
+    },
+};
+
+pub async fn register_organization(
+    claims: Claims,
+    ValidatedJson(organization): ValidatedJson<OrganizationRegister>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service
+        .register_organization(claims.sub, organization)
+        .await?;
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+pub async fn list_organizations(
+    claims: Claims,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service.list_organizations(claims.sub).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn list_members(
+    claims: Claims,
+    Path(organization_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service
+        .list_members(claims.sub, organization_id)
+        .await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn remove_member(
+    claims: Claims,
+    Path((organization_id, user_id)): Path<(i32, i32)>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    organization_service
+        .remove_member(claims.sub, organization_id, user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn update_member(
+    claims: Claims,
+    Path((organization_id, user_id)): Path<(i32, i32)>,
+    ValidatedJson(update): ValidatedJson<OrganizationMemberUpdate>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service
+        .update_member(claims.sub, organization_id, user_id, update)
+        .await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn invite_member(
+    claims: Claims,
+    Path(organization_id): Path<i32>,
+    ValidatedJson(invite): ValidatedJson<OrganizationInvite>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service
+        .invite_member(claims.sub, organization_id, invite)
+        .await?;
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+pub async fn accept_invitation(
+    claims: Claims,
+    ValidatedJson(accept): ValidatedJson<OrganizationInvitationAccept>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service
+        .accept_invitation(claims.sub, accept)
+        .await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn list_collections(
+    claims: Claims,
+    Path(organization_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service
+        .list_collections(claims.sub, organization_id)
+        .await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn register_collection(
+    claims: Claims,
+    Path(organization_id): Path<i32>,
+    ValidatedJson(collection): ValidatedJson<AccountGroupRegister>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    let result = organization_service
+        .register_collection(claims.sub, organization_id, collection)
+        .await?;
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+pub async fn assign_collection_member(
+    claims: Claims,
+    Path((organization_id, group_id)): Path<(i32, i32)>,
+    ValidatedJson(member): ValidatedJson<CollectionMemberRegister>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    organization_service
+        .assign_collection_member(claims.sub, organization_id, group_id, member)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn unassign_collection_member(
+    claims: Claims,
+    Path((organization_id, group_id, user_id)): Path<(i32, i32, i32)>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> OrganizationResult<impl IntoResponse> {
+    let organization_service = OrganizationService::new(repository, cache);
+    organization_service
+        .unassign_collection_member(claims.sub, organization_id, group_id, user_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
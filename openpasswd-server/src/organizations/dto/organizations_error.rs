@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+pub type OrganizationResult<T = ()> = Result<T, OrganizationError>;
+
+#[derive(Debug)]
+pub enum OrganizationError {
+    NotFound,
+    Forbidden,
+    InvalidInvitation,
+    AlreadyMember,
+    InvalidMember,
+}
+
+impl IntoResponse for OrganizationError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            OrganizationError::NotFound => (StatusCode::NOT_FOUND, String::from("Invalid Path")),
+            OrganizationError::Forbidden => (
+                StatusCode::FORBIDDEN,
+                String::from("Not allowed in this organization"),
+            ),
+            OrganizationError::InvalidInvitation => {
+                (StatusCode::BAD_REQUEST, String::from("Invalid Invitation"))
+            }
+            OrganizationError::AlreadyMember => (
+                StatusCode::CONFLICT,
+                String::from("User is already a member"),
+            ),
+            OrganizationError::InvalidMember => {
+                (StatusCode::BAD_REQUEST, String::from("Invalid Member"))
+            }
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
@@ -0,0 +1,44 @@
+use axum::{
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub mod controller;
+pub mod dto;
+mod service;
+
+pub fn route() -> Router {
+    Router::new()
+        .route(
+            "/api/organizations",
+            get(self::controller::list_organizations).post(self::controller::register_organization),
+        )
+        .route(
+            "/api/organizations/invitations/accept",
+            post(self::controller::accept_invitation),
+        )
+        .route(
+            "/api/organizations/:id/members",
+            get(self::controller::list_members),
+        )
+        .route(
+            "/api/organizations/:id/members/:user_id",
+            put(self::controller::update_member).delete(self::controller::remove_member),
+        )
+        .route(
+            "/api/organizations/:id/invitations",
+            post(self::controller::invite_member),
+        )
+        .route(
+            "/api/organizations/:id/collections",
+            get(self::controller::list_collections).post(self::controller::register_collection),
+        )
+        .route(
+            "/api/organizations/:id/collections/:group_id/members",
+            post(self::controller::assign_collection_member),
+        )
+        .route(
+            "/api/organizations/:id/collections/:group_id/members/:user_id",
+            delete(self::controller::unassign_collection_member),
+        )
+}
@@ -0,0 +1,631 @@
+use crate::core::authorization::{can_administer, collection_permission};
+use crate::core::cache::Cache;
+use crate::core::cryptography::{generate_key, AesGcmCipher, Cipher, RsaCipher};
+use crate::core::keys::{organization_key, user_key_pair};
+use crate::core::mail_service::{EmailAddress, MailService, MessageBody};
+use crate::core::notifications::{group_recipients, notify_users};
+use crate::repository::models::account::NewAccountGroup;
+use crate::repository::models::organization::{
+    NewOrganization, NewOrganizationInvitation, NewOrganizationMember,
+};
+use crate::repository::repositories::accounts_repository::AccountsRepository;
+use crate::repository::repositories::authorization_repository::AuthorizationRepository;
+use crate::repository::repositories::organizations_repository::OrganizationsRepository;
+use crate::repository::repositories::shares_repository::SharesRepository;
+use crate::repository::repositories::sync_repository::SyncRepository;
+use crate::repository::repositories::users_repository::UsersRepository;
+use model::accounts::{AccountGroupRegister, AccountGroupView};
+use model::notifications::NotificationEvent;
+use model::organizations::{
+    CollectionMemberRegister, CollectionPermission, OrganizationInvitationAccept,
+    OrganizationInvitationView, OrganizationInvite, OrganizationMemberUpdate,
+    OrganizationMemberView, OrganizationRegister, OrganizationRole, OrganizationView,
+};
+use model::sync::{SyncAction, SyncItemType};
+use model::List;
+use sha2::{Digest, Sha256};
+
+use super::dto::organizations_error::{OrganizationError, OrganizationResult};
+
+const INVITATION_VALIDITY_DAYS: i64 = 7;
+
+pub struct OrganizationService<T>
+where
+    T: AccountsRepository
+        + AuthorizationRepository
+        + OrganizationsRepository
+        + SharesRepository
+        + SyncRepository
+        + UsersRepository,
+{
+    repository: T,
+    cache: Cache,
+}
+
+impl<T> OrganizationService<T>
+where
+    T: AccountsRepository
+        + AuthorizationRepository
+        + OrganizationsRepository
+        + SharesRepository
+        + SyncRepository
+        + UsersRepository,
+{
+    pub fn new(repository: T, cache: Cache) -> OrganizationService<T> {
+        OrganizationService { repository, cache }
+    }
+
+    pub async fn register_organization(
+        self,
+        user_id: i32,
+        organization: OrganizationRegister,
+    ) -> OrganizationResult<OrganizationView> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let (public_key, _) = user_key_pair(&self.repository, &user).await;
+
+        let org_key = generate_key();
+        let owner_org_key = RsaCipher::from_public_key(&public_key).encrypt(&org_key);
+
+        let new_organization = NewOrganization {
+            name: organization.name,
+            created_date: chrono::Utc::now().naive_utc(),
+        };
+        let organization = self
+            .repository
+            .organizations_insert(new_organization, user_id, owner_org_key)
+            .await;
+
+        Ok(OrganizationView {
+            id: organization.id,
+            name: organization.name,
+            role: OrganizationRole::Owner,
+        })
+    }
+
+    pub async fn list_organizations(
+        self,
+        user_id: i32,
+    ) -> OrganizationResult<List<OrganizationView>> {
+        let result = self.repository.organizations_list_by_user_id(user_id).await;
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|(member, organization)| OrganizationView {
+                    id: organization.id,
+                    name: organization.name.to_owned(),
+                    role: Self::role(&member.role),
+                })
+                .collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    pub async fn list_members(
+        self,
+        user_id: i32,
+        organization_id: i32,
+    ) -> OrganizationResult<List<OrganizationMemberView>> {
+        self.member_role(organization_id, user_id).await?;
+
+        let result = self
+            .repository
+            .organization_members_list(organization_id)
+            .await;
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|(member, user)| OrganizationMemberView {
+                    user_id: user.id,
+                    name: user.name.to_owned(),
+                    email: user.email.to_owned(),
+                    role: Self::role(&member.role),
+                })
+                .collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    pub async fn remove_member(
+        self,
+        user_id: i32,
+        organization_id: i32,
+        member_id: i32,
+    ) -> OrganizationResult {
+        let role = self.member_role(organization_id, user_id).await?;
+        let member_role = match self
+            .repository
+            .organization_members_find(organization_id, member_id)
+            .await
+        {
+            Some(member) => Self::role(&member.role),
+            None => return Err(OrganizationError::NotFound),
+        };
+
+        // Members may leave on their own, owners can't be removed at all so
+        // that an organization never ends up without one, and only owners
+        // remove admins.
+        if member_role == OrganizationRole::Owner
+            || (member_id != user_id && !can_administer(role))
+            || (member_id != user_id
+                && member_role == OrganizationRole::Admin
+                && role != OrganizationRole::Owner)
+        {
+            return Err(OrganizationError::Forbidden);
+        }
+
+        let visible = self.visible_collections(organization_id, member_id).await;
+        self.repository
+            .organization_members_delete(organization_id, member_id)
+            .await;
+        self.notify_access(organization_id, member_id, visible)
+            .await;
+        Ok(())
+    }
+
+    pub async fn update_member(
+        self,
+        user_id: i32,
+        organization_id: i32,
+        member_id: i32,
+        update: OrganizationMemberUpdate,
+    ) -> OrganizationResult<OrganizationMemberView> {
+        let role = self.require_admin(organization_id, user_id).await?;
+        let member_role = self.member_role(organization_id, member_id).await?;
+
+        // Owners stay owners, and only they hand out or take away the admin
+        // and owner roles.
+        if member_role == OrganizationRole::Owner
+            || (role != OrganizationRole::Owner
+                && (member_role == OrganizationRole::Admin
+                    || matches!(
+                        update.role,
+                        OrganizationRole::Owner | OrganizationRole::Admin
+                    )))
+        {
+            return Err(OrganizationError::Forbidden);
+        }
+
+        let visible = self.visible_collections(organization_id, member_id).await;
+        self.repository
+            .organization_members_update_role(
+                organization_id,
+                member_id,
+                update.role.as_str().to_owned(),
+            )
+            .await;
+        self.notify_access(organization_id, member_id, visible)
+            .await;
+
+        let member = self.repository.users_find_by_id(member_id).await.unwrap();
+        Ok(OrganizationMemberView {
+            user_id: member.id,
+            name: member.name,
+            email: member.email,
+            role: update.role,
+        })
+    }
+
+    pub async fn invite_member(
+        self,
+        user_id: i32,
+        organization_id: i32,
+        invite: OrganizationInvite,
+    ) -> OrganizationResult<OrganizationInvitationView> {
+        let inviter_role = self.require_admin(organization_id, user_id).await?;
+        let organization = self
+            .repository
+            .organizations_find_by_id(organization_id)
+            .await
+            .unwrap();
+
+        if let Some(invitee) = self.repository.users_find_by_email(&invite.email).await {
+            if self
+                .repository
+                .organization_members_find(organization_id, invitee.id)
+                .await
+                .is_some()
+            {
+                return Err(OrganizationError::AlreadyMember);
+            }
+        }
+
+        // The invitee may not have an account yet, so the organization key
+        // travels encrypted with the invitation token until it's accepted.
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        let org_key = organization_key(&self.repository, &user, organization_id)
+            .await
+            .unwrap();
+        let token = generate_key();
+        let role = invite.role.unwrap_or(OrganizationRole::Member);
+        if inviter_role != OrganizationRole::Owner
+            && matches!(role, OrganizationRole::Owner | OrganizationRole::Admin)
+        {
+            return Err(OrganizationError::Forbidden);
+        }
+
+        let invitation = NewOrganizationInvitation {
+            organization_id,
+            email: invite.email,
+            role: role.as_str().to_owned(),
+            token: Self::hash(&token),
+            org_key: AesGcmCipher::new(&token).encrypt(&org_key),
+            invited_by: user_id,
+            created_date: chrono::Utc::now().naive_utc(),
+        };
+        let invitation = self
+            .repository
+            .organization_invitations_insert(invitation)
+            .await;
+
+        if let Err(e) = MailService::send_email_from_system(
+            EmailAddress::new(None, &invitation.email),
+            format!("Invitation to {}", organization.name),
+            MessageBody::Text(format!(
+                "{} invited you to join {} on OpenPasswd: {}",
+                user.name, organization.name, token
+            )),
+        )
+        .await
+        {
+            log::error!("{}", e);
+        }
+
+        Ok(OrganizationInvitationView {
+            id: invitation.id,
+            organization_id,
+            email: invitation.email,
+            role,
+        })
+    }
+
+    pub async fn accept_invitation(
+        self,
+        user_id: i32,
+        accept: OrganizationInvitationAccept,
+    ) -> OrganizationResult<OrganizationView> {
+        let invitation = match self
+            .repository
+            .organization_invitations_find_by_token(&Self::hash(&accept.token))
+            .await
+        {
+            Some(invitation) => invitation,
+            None => return Err(OrganizationError::InvalidInvitation),
+        };
+
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+        if !invitation.email.eq_ignore_ascii_case(&user.email)
+            || invitation.created_date + chrono::Duration::days(INVITATION_VALIDITY_DAYS)
+                < chrono::Utc::now().naive_utc()
+        {
+            return Err(OrganizationError::InvalidInvitation);
+        }
+
+        if self
+            .repository
+            .organization_members_find(invitation.organization_id, user_id)
+            .await
+            .is_some()
+        {
+            self.repository
+                .organization_invitations_delete(invitation.id)
+                .await;
+            return Err(OrganizationError::AlreadyMember);
+        }
+
+        let org_key = AesGcmCipher::new(&accept.token).decrypt(&invitation.org_key);
+        let (public_key, _) = user_key_pair(&self.repository, &user).await;
+        let member = NewOrganizationMember {
+            organization_id: invitation.organization_id,
+            user_id,
+            role: invitation.role.to_owned(),
+            org_key: RsaCipher::from_public_key(&public_key).encrypt(&org_key),
+            created_date: chrono::Utc::now().naive_utc(),
+        };
+        self.repository.organization_members_insert(member).await;
+        self.repository
+            .organization_invitations_delete(invitation.id)
+            .await;
+        self.notify_access(invitation.organization_id, user_id, Vec::new())
+            .await;
+
+        let organization = self
+            .repository
+            .organizations_find_by_id(invitation.organization_id)
+            .await
+            .unwrap();
+
+        Ok(OrganizationView {
+            id: organization.id,
+            name: organization.name,
+            role: Self::role(&invitation.role),
+        })
+    }
+
+    pub async fn list_collections(
+        self,
+        user_id: i32,
+        organization_id: i32,
+    ) -> OrganizationResult<List<AccountGroupView>> {
+        let role = self.member_role(organization_id, user_id).await?;
+        let assignments = self
+            .repository
+            .authorization_collection_members(user_id)
+            .await;
+
+        let result: Vec<_> = self
+            .repository
+            .collections_list(organization_id)
+            .await
+            .into_iter()
+            .filter(|group| {
+                let assigned = assignments
+                    .iter()
+                    .find(|assignment| assignment.account_groups_id == group.id)
+                    .and_then(|assignment| assignment.permission.parse().ok());
+                collection_permission(role, assigned).is_some()
+            })
+            .collect();
+
+        Ok(List {
+            items: result
+                .iter()
+                .map(|r| AccountGroupView {
+                    id: r.id,
+                    name: r.name.to_owned(),
+                    organization_id: r.organization_id,
+                    revision: r.revision,
+                    rotation_days: r.rotation_days,
+                })
+                .collect(),
+            total: result.len() as u32,
+            pagination: None,
+        })
+    }
+
+    pub async fn register_collection(
+        self,
+        user_id: i32,
+        organization_id: i32,
+        collection: AccountGroupRegister,
+    ) -> OrganizationResult<AccountGroupView> {
+        self.require_admin(organization_id, user_id).await?;
+
+        let account_group = NewAccountGroup {
+            name: collection.name,
+            user_id,
+            organization_id: Some(organization_id),
+            rotation_days: collection.rotation_days,
+        };
+        let account_group = self
+            .repository
+            .accounts_groups_insert(account_group)
+            .await
+            .unwrap();
+        let user_ids = group_recipients(&self.repository, &account_group, None).await;
+        self.repository
+            .sync_record(
+                &user_ids,
+                SyncItemType::Group.as_str(),
+                account_group.id,
+                SyncAction::Created.as_str(),
+            )
+            .await;
+        notify_users(
+            &self.cache,
+            &user_ids,
+            NotificationEvent::GroupCreated,
+            account_group.id,
+        )
+        .await;
+
+        Ok(AccountGroupView {
+            id: account_group.id,
+            name: account_group.name,
+            organization_id: account_group.organization_id,
+            revision: account_group.revision,
+            rotation_days: account_group.rotation_days,
+        })
+    }
+
+    pub async fn assign_collection_member(
+        self,
+        user_id: i32,
+        organization_id: i32,
+        group_id: i32,
+        member: CollectionMemberRegister,
+    ) -> OrganizationResult {
+        self.require_collection_manager(organization_id, group_id, user_id)
+            .await?;
+        if self
+            .repository
+            .organization_members_find(organization_id, member.user_id)
+            .await
+            .is_none()
+        {
+            return Err(OrganizationError::InvalidMember);
+        }
+
+        let visible = self
+            .visible_collections(organization_id, member.user_id)
+            .await;
+        self.repository
+            .collection_members_upsert(
+                group_id,
+                member.user_id,
+                member.permission.as_str().to_owned(),
+            )
+            .await;
+        self.notify_access(organization_id, member.user_id, visible)
+            .await;
+        Ok(())
+    }
+
+    pub async fn unassign_collection_member(
+        self,
+        user_id: i32,
+        organization_id: i32,
+        group_id: i32,
+        member_id: i32,
+    ) -> OrganizationResult {
+        self.require_collection_manager(organization_id, group_id, user_id)
+            .await?;
+
+        let visible = self.visible_collections(organization_id, member_id).await;
+        if self
+            .repository
+            .collection_members_delete(group_id, member_id)
+            .await
+        {
+            self.notify_access(organization_id, member_id, visible)
+                .await;
+            Ok(())
+        } else {
+            Err(OrganizationError::NotFound)
+        }
+    }
+
+    async fn member_role(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+    ) -> OrganizationResult<OrganizationRole> {
+        match self
+            .repository
+            .organization_members_find(organization_id, user_id)
+            .await
+        {
+            Some(member) => Ok(Self::role(&member.role)),
+            None => Err(OrganizationError::NotFound),
+        }
+    }
+
+    async fn require_admin(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+    ) -> OrganizationResult<OrganizationRole> {
+        let role = self.member_role(organization_id, user_id).await?;
+        if can_administer(role) {
+            Ok(role)
+        } else {
+            Err(OrganizationError::Forbidden)
+        }
+    }
+
+    /// Collection assignments are handled by admins and by the managers the
+    /// collection is assigned to.
+    async fn require_collection_manager(
+        &self,
+        organization_id: i32,
+        group_id: i32,
+        user_id: i32,
+    ) -> OrganizationResult {
+        let role = self.member_role(organization_id, user_id).await?;
+        if self
+            .repository
+            .collections_find_by_id(organization_id, group_id)
+            .await
+            .is_none()
+        {
+            return Err(OrganizationError::NotFound);
+        }
+
+        let assigned = self
+            .repository
+            .authorization_collection_members(user_id)
+            .await
+            .into_iter()
+            .find(|assignment| assignment.account_groups_id == group_id)
+            .and_then(|assignment| assignment.permission.parse().ok());
+        match collection_permission(role, assigned) {
+            Some(CollectionPermission::Manage) => Ok(()),
+            Some(_) => Err(OrganizationError::Forbidden),
+            None => Err(OrganizationError::NotFound),
+        }
+    }
+
+    /// Collections of the organization the user can currently see.
+    async fn visible_collections(&self, organization_id: i32, user_id: i32) -> Vec<i32> {
+        let role = match self
+            .repository
+            .organization_members_find(organization_id, user_id)
+            .await
+        {
+            Some(member) => Self::role(&member.role),
+            None => return Vec::new(),
+        };
+        let assignments = self
+            .repository
+            .authorization_collection_members(user_id)
+            .await;
+
+        self.repository
+            .collections_list(organization_id)
+            .await
+            .into_iter()
+            .filter(|group| {
+                let assigned = assignments
+                    .iter()
+                    .find(|assignment| assignment.account_groups_id == group.id)
+                    .and_then(|assignment| assignment.permission.parse().ok());
+                collection_permission(role, assigned).is_some()
+            })
+            .map(|group| group.id)
+            .collect()
+    }
+
+    /// Tells the user about the collections gained or lost since `visible`
+    /// was taken, membership and role changes move whole collections in or
+    /// out of a vault.
+    async fn notify_access(&self, organization_id: i32, user_id: i32, visible: Vec<i32>) {
+        let current = self.visible_collections(organization_id, user_id).await;
+        for group_id in current.iter().filter(|id| !visible.contains(id)) {
+            self.repository
+                .sync_record(
+                    &[user_id],
+                    SyncItemType::Group.as_str(),
+                    *group_id,
+                    SyncAction::Created.as_str(),
+                )
+                .await;
+            notify_users(
+                &self.cache,
+                &[user_id],
+                NotificationEvent::GroupCreated,
+                *group_id,
+            )
+            .await;
+        }
+        for group_id in visible.iter().filter(|id| !current.contains(id)) {
+            self.repository
+                .sync_record(
+                    &[user_id],
+                    SyncItemType::Group.as_str(),
+                    *group_id,
+                    SyncAction::Deleted.as_str(),
+                )
+                .await;
+            notify_users(
+                &self.cache,
+                &[user_id],
+                NotificationEvent::GroupDeleted,
+                *group_id,
+            )
+            .await;
+        }
+    }
+
+    fn role(role: &str) -> OrganizationRole {
+        role.parse().unwrap_or(OrganizationRole::Member)
+    }
+
+    fn hash(data: impl AsRef<[u8]>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(data);
+        format!("{:x}", hasher.finalize())
+    }
+}
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use super::{
+    dto::reports_error::{ReportsError, ReportsResult},
+    service::ReportsService,
+};
+use crate::{
+    auth::dto::claims::Claims,
+    core::{
+        authorization::Authorizer, breached_passwords::BreachChecker,
+        password_age::PasswordAgePolicy,
+    },
+    repository::Repository,
+};
+use axum::{extract::Query, http::StatusCode, response::IntoResponse, Extension, Json};
+
+pub async fn health(
+    claims: Claims,
+    Query(params): Query<HashMap<String, String>>,
+    Extension(repository): Extension<Repository>,
+    Extension(breaches): Extension<BreachChecker>,
+    Extension(age_policy): Extension<PasswordAgePolicy>,
+) -> ReportsResult<impl IntoResponse> {
+    let max_age_days = match params.get("max_age_days") {
+        Some(max_age_days) => max_age_days
+            .parse::<i64>()
+            .ok()
+            .filter(|max_age_days| *max_age_days > 0)
+            .ok_or(ReportsError::InvalidMaxAge)?,
+        None => age_policy.max_age_days,
+    };
+
+    let groups = Authorizer::new(repository.clone()).groups(claims.sub).await;
+    let reports_service = ReportsService::new(repository);
+    let result = reports_service
+        .health(claims.sub, groups, max_age_days, &breaches)
+        .await?;
+    Ok((StatusCode::OK, Json(result)))
+}
@@ -0,0 +1 @@
+pub mod reports_error;
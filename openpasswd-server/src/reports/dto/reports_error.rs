@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+pub type ReportsResult<T = ()> = Result<T, ReportsError>;
+
+#[derive(Debug)]
+pub enum ReportsError {
+    InvalidMaxAge,
+}
+
+impl IntoResponse for ReportsError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            ReportsError::InvalidMaxAge => (
+                StatusCode::BAD_REQUEST,
+                String::from("max_age_days must be a positive number of days"),
+            ),
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
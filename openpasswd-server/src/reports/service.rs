@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use super::dto::reports_error::ReportsResult;
+use crate::core::authorization::GroupAccess;
+use crate::core::breached_passwords::BreachChecker;
+use crate::core::cryptography::{AesGcmCipher, Cipher};
+use crate::core::keys::organization_key;
+use crate::core::password_strength;
+use crate::repository::repositories::organizations_repository::OrganizationsRepository;
+use crate::repository::repositories::reports_repository::ReportsRepository;
+use crate::repository::repositories::sync_repository::SyncRepository;
+use crate::repository::repositories::users_repository::UsersRepository;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use model::organizations::CollectionPermission;
+use model::reports::{
+    BreachedPasswordView, HealthReport, OldPasswordView, ReportAccountView, ReusedPasswordView,
+    WeakPasswordView,
+};
+use sha2::{Digest, Sha256};
+
+/// Passwords scoring below this are reported as weak.
+const STRONG_SCORE: u8 = 3;
+
+fn to_rfc3339(datetime: NaiveDateTime) -> String {
+    Utc.from_utc_datetime(&datetime).to_rfc3339()
+}
+
+/// Host of an account's URL, a word an attacker would try.
+fn url_host(url: &str) -> &str {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    url.split(['/', ':', '?', '#']).next().unwrap_or(url)
+}
+
+pub struct ReportsService<T>
+where
+    T: OrganizationsRepository + ReportsRepository + SyncRepository + UsersRepository,
+{
+    repository: T,
+}
+
+impl<T> ReportsService<T>
+where
+    T: OrganizationsRepository + ReportsRepository + SyncRepository + UsersRepository,
+{
+    pub fn new(repository: T) -> ReportsService<T> {
+        ReportsService { repository }
+    }
+
+    /// Passwords are decrypted in memory only, reuse is found by comparing
+    /// their digests.
+    pub async fn health(
+        self,
+        user_id: i32,
+        groups: Vec<GroupAccess>,
+        max_age_days: i64,
+        breaches: &BreachChecker,
+    ) -> ReportsResult<HealthReport> {
+        let user = self.repository.users_find_by_id(user_id).await.unwrap();
+
+        // Collections whose passwords the user can't see are left out, the
+        // report would tell them apart otherwise.
+        let mut key_ciphers: HashMap<i32, AesGcmCipher> = HashMap::new();
+        for access in groups
+            .iter()
+            .filter(|access| access.permission >= CollectionPermission::View)
+        {
+            let key = match access.group.organization_id {
+                Some(organization_id) => {
+                    match organization_key(&self.repository, &user, organization_id).await {
+                        Some(key) => key,
+                        None => continue,
+                    }
+                }
+                None => user.master_key.to_owned().unwrap(),
+            };
+            key_ciphers.insert(access.group.id, AesGcmCipher::new(&key));
+        }
+
+        let accounts = self
+            .repository
+            .sync_accounts_by_group_ids(key_ciphers.keys().copied().collect())
+            .await;
+        let passwords = self
+            .repository
+            .reports_current_passwords(accounts.iter().map(|account| account.id).collect())
+            .await;
+
+        let now = Utc::now().naive_utc();
+        let user_name = user.email.split('@').next().unwrap_or_default();
+        let mut digests: Vec<(Vec<u8>, Vec<ReportAccountView>)> = Vec::new();
+        let mut report = HealthReport {
+            generated_at: to_rfc3339(now),
+            accounts_checked: 0,
+            max_age_days,
+            reused: Vec::new(),
+            weak: Vec::new(),
+            old: Vec::new(),
+            no_two_factor: Vec::new(),
+            breaches_checked: breaches.enabled(),
+            breached: Vec::new(),
+        };
+
+        for account in accounts.iter() {
+            let current = match passwords
+                .iter()
+                .find(|password| password.account_id == account.id)
+            {
+                Some(current) => current,
+                None => continue,
+            };
+            let key_cipher = &key_ciphers[&account.account_groups_id];
+            let item_cipher = account
+                .item_key
+                .as_ref()
+                .map(|item_key| AesGcmCipher::new(&key_cipher.decrypt(item_key)));
+            let password = item_cipher
+                .as_ref()
+                .unwrap_or(key_cipher)
+                .decrypt(&current.password);
+
+            let view = || ReportAccountView {
+                id: account.id,
+                name: account.name.to_owned(),
+                group_id: account.account_groups_id,
+            };
+            report.accounts_checked += 1;
+
+            let digest = Sha256::digest(password.as_bytes()).to_vec();
+            match digests.iter_mut().find(|(known, _)| *known == digest) {
+                Some((_, accounts)) => accounts.push(view()),
+                None => digests.push((digest, vec![view()])),
+            }
+
+            let mut user_inputs = vec![
+                account.name.as_str(),
+                current.username.as_str(),
+                user.name.as_str(),
+                user_name,
+            ];
+            if let Some(url) = &account.url {
+                user_inputs.push(url_host(url));
+            }
+            let strength = password_strength::estimate(&password, &user_inputs);
+            if strength.score < STRONG_SCORE {
+                report.weak.push(WeakPasswordView {
+                    account: view(),
+                    score: strength.score,
+                    entropy_bits: strength.entropy_bits.round() as u32,
+                });
+            }
+
+            let age_days = (now - current.created_date).num_days();
+            if age_days > max_age_days {
+                report.old.push(OldPasswordView {
+                    account: view(),
+                    changed_date: to_rfc3339(current.created_date),
+                    age_days,
+                });
+            }
+
+            if !account.two_factor {
+                report.no_two_factor.push(view());
+            }
+
+            if report.breaches_checked {
+                match breaches.breach_count(&password).await {
+                    Some(count) if count > 0 => report.breached.push(BreachedPasswordView {
+                        account: view(),
+                        count,
+                    }),
+                    _ => {}
+                }
+            }
+        }
+
+        report.reused = digests
+            .into_iter()
+            .filter(|(_, accounts)| accounts.len() > 1)
+            .map(|(_, accounts)| ReusedPasswordView { accounts })
+            .collect();
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::url_host;
+
+    #[test]
+    fn host_of_account_urls() {
+        assert_eq!(
+            "mail.example.com",
+            url_host("https://mail.example.com/inbox")
+        );
+        assert_eq!("example.com", url_host("example.com:8443?login"));
+        assert_eq!("example.com", url_host("example.com"));
+    }
+}
@@ -1,5 +1,5 @@
-pub use repository::Repository;
+pub use database::Repository;
 
+mod database;
 pub mod models;
 pub mod repositories;
-mod repository;
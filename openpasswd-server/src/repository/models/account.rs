@@ -3,13 +3,76 @@ use chrono::NaiveDateTime;
 pub struct NewAccountGroup {
     pub user_id: i32,
     pub name: String,
+    pub organization_id: Option<i32>,
+    pub rotation_days: Option<i32>,
 }
 
 pub struct NewAccount {
     pub user_id: i32,
     pub name: String,
-    pub level: Option<i16>,
+    pub level: i16,
     pub account_groups_id: i32,
+    pub url: Option<String>,
+    pub favorite: bool,
+    pub two_factor: bool,
+    pub rotation_days: Option<i32>,
+    /// JSON of a `GeneratorProfile`.
+    pub generator_profile: Option<String>,
+    pub item_key: Option<Vec<u8>>,
+}
+
+pub struct AccountChanges {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub two_factor: Option<bool>,
+    /// `Some(None)` removes the interval.
+    pub rotation_days: Option<Option<i32>>,
+    pub generator_profile: Option<String>,
+}
+
+pub struct NewAccountShare {
+    pub account_id: i32,
+    pub user_id: i32,
+    pub item_key: Vec<u8>,
+    pub permission: String,
+    pub created_date: NaiveDateTime,
+}
+
+#[derive(Default)]
+pub struct AccountFilter {
+    pub group_id: Option<i32>,
+    pub tag: Option<String>,
+    pub favorite: Option<bool>,
+    pub search: Option<String>,
+}
+
+#[derive(Default)]
+pub enum AccountSort {
+    #[default]
+    NameAsc,
+    NameDesc,
+    CreatedAsc,
+    CreatedDesc,
+}
+
+impl std::str::FromStr for AccountSort {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(AccountSort::NameAsc),
+            "-name" => Ok(AccountSort::NameDesc),
+            "created" => Ok(AccountSort::CreatedAsc),
+            "-created" => Ok(AccountSort::CreatedDesc),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct PageRequest {
+    pub page: u64,
+    pub limit: u64,
 }
 
 pub struct NewAccountPassword {
@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+
+pub struct NewAuditEvent {
+    pub user_id: Option<i32>,
+    pub event_type: String,
+    pub target: Option<String>,
+    pub device: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_date: NaiveDateTime,
+}
+
+#[derive(Default)]
+pub struct AuditFilter {
+    pub event_type: Option<String>,
+    pub since: Option<NaiveDateTime>,
+    pub until: Option<NaiveDateTime>,
+}
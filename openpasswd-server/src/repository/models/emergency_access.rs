@@ -0,0 +1,10 @@
+use chrono::NaiveDateTime;
+
+pub struct NewEmergencyAccess {
+    pub grantor_id: i32,
+    pub email: String,
+    pub access_type: String,
+    pub status: String,
+    pub wait_days: i16,
+    pub created_date: NaiveDateTime,
+}
@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+
+pub struct NewImportedAccount {
+    /// Name of the personal group the account goes into.
+    pub group: String,
+    pub name: String,
+    pub level: i16,
+    pub url: Option<String>,
+    pub favorite: bool,
+    pub two_factor: bool,
+    pub item_key: Vec<u8>,
+    /// Credentials oldest first, the last one being the current.
+    pub passwords: Vec<NewImportedPassword>,
+    pub tags: Vec<String>,
+}
+
+pub struct NewImportedPassword {
+    pub username: String,
+    pub password: Vec<u8>,
+    pub created_date: NaiveDateTime,
+}
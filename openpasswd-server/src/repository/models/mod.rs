@@ -1,4 +1,9 @@
 pub mod account;
+pub mod audit;
+pub mod emergency_access;
+pub mod import;
 // pub mod device;
+pub mod organization;
 pub mod user;
 pub mod user_password_recovery;
+pub mod webhook;
@@ -0,0 +1,24 @@
+use chrono::NaiveDateTime;
+
+pub struct NewOrganization {
+    pub name: String,
+    pub created_date: NaiveDateTime,
+}
+
+pub struct NewOrganizationMember {
+    pub organization_id: i32,
+    pub user_id: i32,
+    pub role: String,
+    pub org_key: Vec<u8>,
+    pub created_date: NaiveDateTime,
+}
+
+pub struct NewOrganizationInvitation {
+    pub organization_id: i32,
+    pub email: String,
+    pub role: String,
+    pub token: String,
+    pub org_key: Vec<u8>,
+    pub invited_by: i32,
+    pub created_date: NaiveDateTime,
+}
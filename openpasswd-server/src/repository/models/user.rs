@@ -3,4 +3,6 @@ pub struct NewUser {
     pub email: String,
     pub password: String,
     pub master_key: Option<String>,
+    pub public_key: Option<String>,
+    pub private_key: Option<Vec<u8>>,
 }
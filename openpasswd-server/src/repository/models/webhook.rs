@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+
+pub struct NewWebhook {
+    pub user_id: Option<i32>,
+    pub organization_id: Option<i32>,
+    pub url: String,
+    pub secret: String,
+    pub event_types: String,
+    pub created_date: NaiveDateTime,
+}
+
+pub struct NewWebhookDelivery {
+    pub webhook_id: i32,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_date: NaiveDateTime,
+}
+
+/// Outcome of a delivery attempt.
+pub struct WebhookDeliveryAttempt {
+    pub status: String,
+    pub attempts: i16,
+    pub next_attempt_at: NaiveDateTime,
+    pub response_status: Option<i16>,
+    pub error: Option<String>,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+pub struct WebhookChanges {
+    pub url: Option<String>,
+    pub event_types: Option<String>,
+    pub enabled: Option<bool>,
+}
@@ -1,36 +1,53 @@
-use crate::repository::models::account::{NewAccount, NewAccountGroup, NewAccountPassword};
+use crate::repository::models::account::{
+    AccountChanges, AccountFilter, AccountSort, NewAccount, NewAccountGroup, NewAccountPassword,
+    PageRequest,
+};
 use crate::repository::Repository;
 use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Func, OnConflict, Query};
 use sea_orm::ActiveValue::Set;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, TransactionTrait,
+};
 
 #[async_trait]
 pub trait AccountsRepository {
-    async fn accounts_groups_list(&self, user_id: i32) -> Vec<entity::account_groups::Model>;
-    async fn accounts_groups_find_by_id(
-        &self,
-        id: i32,
-        user_id: i32,
-    ) -> Option<entity::account_groups::Model>;
+    async fn accounts_groups_find_by_id(&self, id: i32) -> Option<entity::account_groups::Model>;
     async fn accounts_groups_insert(
         &self,
         account_group: NewAccountGroup,
     ) -> Result<entity::account_groups::Model, ()>;
     async fn accounts_insert(&self, account: NewAccount) -> Result<entity::accounts::Model, ()>;
-    async fn accounts_list(&self, user_id: i32) -> Vec<entity::accounts::Model>;
-    async fn accounts_list_by_group_id(
+    async fn accounts_find_by_id(&self, account_id: i32) -> Option<entity::accounts::Model>;
+    async fn accounts_groups_update(
         &self,
-        user_id: i32,
         group_id: i32,
-    ) -> Vec<entity::accounts::Model>;
-    async fn accounts_get_with_passwords_by_account_id(
+        revision: Option<i32>,
+        name: String,
+        rotation_days: Option<Option<i32>>,
+    ) -> bool;
+    /// Applies the changes and bumps the revision, unless `revision` is set
+    /// and no longer the current one.
+    async fn accounts_update(
         &self,
         account_id: i32,
-        user_id: i32,
-    ) -> Option<(
-        entity::accounts::Model,
-        Vec<entity::account_passwords::Model>,
-    )>;
+        revision: Option<i32>,
+        changes: AccountChanges,
+    ) -> bool;
+    async fn accounts_set_item_key(
+        &self,
+        account_id: i32,
+        item_key: Vec<u8>,
+        passwords: Vec<(i32, Vec<u8>)>,
+    );
+    async fn accounts_list(
+        &self,
+        group_ids: Vec<i32>,
+        filter: AccountFilter,
+        sort: AccountSort,
+        page: PageRequest,
+    ) -> (Vec<entity::accounts::Model>, u64);
 
     async fn account_passwords_insert(
         &self,
@@ -40,28 +57,25 @@ pub trait AccountsRepository {
         &self,
         account_id: i32,
     ) -> Vec<entity::account_passwords::Model>;
+
+    async fn tags_list(&self, user_id: i32) -> Vec<entity::tags::Model>;
+    async fn tags_find_or_insert(
+        &self,
+        user_id: i32,
+        names: Vec<String>,
+    ) -> Vec<entity::tags::Model>;
+    async fn account_tags_insert(&self, account_id: i32, tag_ids: Vec<i32>);
+    async fn account_tags_list_by_account_ids(&self, account_ids: Vec<i32>) -> Vec<(i32, String)>;
 }
 
 #[async_trait]
 impl AccountsRepository for Repository {
-    async fn accounts_groups_list(&self, user_id: i32) -> Vec<entity::account_groups::Model> {
-        entity::account_groups::Entity::find()
-            .filter(entity::account_groups::Column::UserId.eq(user_id))
-            .all(&self.db)
-            .await
-            .unwrap()
-    }
-
-    async fn accounts_groups_find_by_id(
-        &self,
-        id: i32,
-        user_id: i32,
-    ) -> Option<entity::account_groups::Model> {
+    async fn accounts_groups_find_by_id(&self, id: i32) -> Option<entity::account_groups::Model> {
         entity::account_groups::Entity::find()
             .filter(
                 entity::account_groups::Column::Id
                     .eq(id)
-                    .and(entity::account_groups::Column::UserId.eq(user_id)),
+                    .and(entity::account_groups::Column::DeletedAt.is_null()),
             )
             .one(&self.db)
             .await
@@ -75,6 +89,8 @@ impl AccountsRepository for Repository {
         let account_group = entity::account_groups::ActiveModel {
             user_id: Set(account_group.user_id),
             name: Set(account_group.name),
+            organization_id: Set(account_group.organization_id),
+            rotation_days: Set(account_group.rotation_days),
             ..Default::default()
         };
         let result = account_group.insert(&self.db).await.unwrap();
@@ -92,6 +108,13 @@ impl AccountsRepository for Repository {
             name: Set(account.name),
             user_id: Set(account.user_id),
             account_groups_id: Set(account.account_groups_id),
+            level: Set(account.level),
+            url: Set(account.url),
+            favorite: Set(account.favorite),
+            two_factor: Set(account.two_factor),
+            rotation_days: Set(account.rotation_days),
+            generator_profile: Set(account.generator_profile),
+            item_key: Set(account.item_key),
             ..Default::default()
         };
         let result = account.insert(&self.db).await.unwrap();
@@ -103,101 +126,207 @@ impl AccountsRepository for Repository {
         Ok(result)
     }
 
-    async fn accounts_list(&self, user_id: i32) -> Vec<entity::accounts::Model> {
+    async fn accounts_find_by_id(&self, account_id: i32) -> Option<entity::accounts::Model> {
         entity::accounts::Entity::find()
-            .filter(entity::accounts::Column::UserId.eq(user_id))
-            .all(&self.db)
+            .filter(
+                entity::accounts::Column::Id
+                    .eq(account_id)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .one(&self.db)
             .await
             .unwrap()
     }
 
-    async fn accounts_list_by_group_id(
+    async fn accounts_groups_update(
         &self,
-        user_id: i32,
         group_id: i32,
-    ) -> Vec<entity::accounts::Model> {
-        entity::accounts::Entity::find()
-            .filter(
-                entity::accounts::Column::UserId
-                    .eq(user_id)
-                    .and(entity::accounts::Column::AccountGroupsId.eq(group_id)),
-            )
-            .all(&self.db)
-            .await
-            .unwrap()
+        revision: Option<i32>,
+        name: String,
+        rotation_days: Option<Option<i32>>,
+    ) -> bool {
+        let mut condition = Condition::all().add(entity::account_groups::Column::Id.eq(group_id));
+        if let Some(revision) = revision {
+            condition = condition.add(entity::account_groups::Column::Revision.eq(revision));
+        }
+
+        let mut update = entity::account_groups::Entity::update_many()
+            .col_expr(entity::account_groups::Column::Name, Expr::value(name))
+            .col_expr(
+                entity::account_groups::Column::Revision,
+                Expr::col(entity::account_groups::Column::Revision).add(1),
+            );
+        if let Some(rotation_days) = rotation_days {
+            update = update.col_expr(
+                entity::account_groups::Column::RotationDays,
+                Expr::value(rotation_days),
+            );
+        }
+        let result = update.filter(condition).exec(&self.db).await.unwrap();
+        result.rows_affected > 0
     }
 
-    async fn accounts_get_with_passwords_by_account_id(
+    async fn accounts_update(
         &self,
         account_id: i32,
-        user_id: i32,
-    ) -> Option<(
-        entity::accounts::Model,
-        Vec<entity::account_passwords::Model>,
-    )> {
-        let mut result = match entity::accounts::Entity::find()
-            .find_with_related(entity::account_passwords::Entity)
-            .filter(
-                entity::accounts::Column::Id
-                    .eq(account_id)
-                    .and(entity::accounts::Column::UserId.eq(user_id)),
-            )
-            .all(&self.db)
-            .await
-        {
-            Ok(result) => result,
-            Err(e) => {
-                log::error!("{}", e);
-                return None;
-            }
-        };
+        revision: Option<i32>,
+        changes: AccountChanges,
+    ) -> bool {
+        // The revision check and bump happen in the same statement, two
+        // concurrent updates based on the same revision can't both succeed.
+        let mut condition = Condition::all().add(entity::accounts::Column::Id.eq(account_id));
+        if let Some(revision) = revision {
+            condition = condition.add(entity::accounts::Column::Revision.eq(revision));
+        }
+
+        let mut update = entity::accounts::Entity::update_many().col_expr(
+            entity::accounts::Column::Revision,
+            Expr::col(entity::accounts::Column::Revision).add(1),
+        );
+        if let Some(name) = changes.name {
+            update = update.col_expr(entity::accounts::Column::Name, Expr::value(name));
+        }
+        if let Some(url) = changes.url {
+            update = update.col_expr(entity::accounts::Column::Url, Expr::value(url));
+        }
+        if let Some(two_factor) = changes.two_factor {
+            update = update.col_expr(entity::accounts::Column::TwoFactor, Expr::value(two_factor));
+        }
+        if let Some(rotation_days) = changes.rotation_days {
+            update = update.col_expr(
+                entity::accounts::Column::RotationDays,
+                Expr::value(rotation_days),
+            );
+        }
+        if let Some(generator_profile) = changes.generator_profile {
+            update = update.col_expr(
+                entity::accounts::Column::GeneratorProfile,
+                Expr::value(generator_profile),
+            );
+        }
+
+        let result = update.filter(condition).exec(&self.db).await.unwrap();
+        result.rows_affected > 0
+    }
+
+    async fn accounts_set_item_key(
+        &self,
+        account_id: i32,
+        item_key: Vec<u8>,
+        passwords: Vec<(i32, Vec<u8>)>,
+    ) {
+        let txn = self.db.begin().await.unwrap();
 
-        let account = if result.len() > 0 {
-            result.remove(0)
-        } else {
-            return None;
+        let account = entity::accounts::ActiveModel {
+            id: Set(account_id),
+            item_key: Set(Some(item_key)),
+            ..Default::default()
         };
+        entity::accounts::Entity::update(account)
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        for (id, password) in passwords {
+            let account_password = entity::account_passwords::ActiveModel {
+                id: Set(id),
+                password: Set(password),
+                ..Default::default()
+            };
+            entity::account_passwords::Entity::update(account_password)
+                .exec(&txn)
+                .await
+                .unwrap();
+        }
 
-        Some(account)
-        // let connection = &self.db.get().unwrap();
-        // // TODO: Join it better =D, I want to use sqlx
-
-        // let mut result = match accounts_dsl::accounts
-        //     .filter(
-        //         accounts_dsl::id
-        //             .eq(&account_id)
-        //             .and(accounts_dsl::user_id.eq(user_id)),
-        //     )
-        //     .load::<Account>(connection)
-        // {
-        //     Ok(result) => result,
-        //     Err(e) => panic!("{e}"),
-        // };
-
-        // let account = if result.len() > 0 {
-        //     result.remove(0)
-        // } else {
-        //     return None;
-        // };
-
-        // let passwords = match account_passwords_dsl::account_passwords
-        //     .filter(account_passwords_dsl::account_id.eq(account_id))
-        //     .load::<AccountPassword>(connection)
-        // {
-        //     Ok(result) => result,
-        //     Err(e) => panic!("{e}"),
-        // };
-
-        // Some(AccountWithPassword {
-        //     id: account.id,
-        //     user_id: account.user_id,
-        //     account_groups_id: account.account_groups_id,
-        //     level: account.level,
-        //     name: account.name,
-        //     passwords,
-        // })
+        txn.commit().await.unwrap();
     }
 
+    async fn accounts_list(
+        &self,
+        group_ids: Vec<i32>,
+        filter: AccountFilter,
+        sort: AccountSort,
+        page: PageRequest,
+    ) -> (Vec<entity::accounts::Model>, u64) {
+        let mut condition = Condition::all()
+            .add(entity::accounts::Column::AccountGroupsId.is_in(group_ids))
+            .add(entity::accounts::Column::DeletedAt.is_null());
+
+        if let Some(group_id) = filter.group_id {
+            condition = condition.add(entity::accounts::Column::AccountGroupsId.eq(group_id));
+        }
+
+        if let Some(favorite) = filter.favorite {
+            condition = condition.add(entity::accounts::Column::Favorite.eq(favorite));
+        }
+
+        if let Some(tag) = filter.tag {
+            condition = condition.add(
+                entity::accounts::Column::Id.in_subquery(
+                    Query::select()
+                        .column(entity::account_tags::Column::AccountId)
+                        .from(entity::account_tags::Entity)
+                        .inner_join(
+                            entity::tags::Entity,
+                            Expr::tbl(entity::tags::Entity, entity::tags::Column::Id).equals(
+                                entity::account_tags::Entity,
+                                entity::account_tags::Column::TagId,
+                            ),
+                        )
+                        .and_where(entity::tags::Column::Name.eq(tag))
+                        .to_owned(),
+                ),
+            );
+        }
+
+        if let Some(search) = filter.search {
+            let pattern = format!("%{}%", escape_like(&search.to_lowercase()));
+            condition = condition.add(
+                Condition::any()
+                    .add(
+                        Expr::expr(Func::lower(Expr::col(entity::accounts::Column::Name)))
+                            .like(&pattern),
+                    )
+                    .add(
+                        Expr::expr(Func::lower(Expr::col(entity::accounts::Column::Url)))
+                            .like(&pattern),
+                    )
+                    .add(
+                        entity::accounts::Column::Id.in_subquery(
+                            Query::select()
+                                .column(entity::account_passwords::Column::AccountId)
+                                .from(entity::account_passwords::Entity)
+                                .and_where(
+                                    Expr::expr(Func::lower(Expr::col(
+                                        entity::account_passwords::Column::Username,
+                                    )))
+                                    .like(&pattern),
+                                )
+                                .to_owned(),
+                        ),
+                    ),
+            );
+        }
+
+        let query = entity::accounts::Entity::find().filter(condition);
+        let query = match sort {
+            AccountSort::NameAsc => query.order_by_asc(entity::accounts::Column::Name),
+            AccountSort::NameDesc => query.order_by_desc(entity::accounts::Column::Name),
+            AccountSort::CreatedAsc => query.order_by_asc(entity::accounts::Column::Id),
+            AccountSort::CreatedDesc => query.order_by_desc(entity::accounts::Column::Id),
+        }
+        .order_by_asc(entity::accounts::Column::Id);
+
+        let paginator = query.paginate(&self.db, page.limit as usize);
+        let total = paginator.num_items().await.unwrap() as u64;
+        let items = paginator
+            .fetch_page(page.page.saturating_sub(1) as usize)
+            .await
+            .unwrap();
+
+        (items, total)
+    }
     async fn account_passwords_insert(
         &self,
         account_password: NewAccountPassword,
@@ -226,4 +355,97 @@ impl AccountsRepository for Repository {
             .await
             .unwrap()
     }
+
+    async fn tags_list(&self, user_id: i32) -> Vec<entity::tags::Model> {
+        entity::tags::Entity::find()
+            .filter(entity::tags::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn tags_find_or_insert(
+        &self,
+        user_id: i32,
+        names: Vec<String>,
+    ) -> Vec<entity::tags::Model> {
+        if names.is_empty() {
+            return Vec::new();
+        }
+
+        let mut insert = Query::insert()
+            .into_table(entity::tags::Entity)
+            .columns([entity::tags::Column::UserId, entity::tags::Column::Name])
+            .on_conflict(
+                OnConflict::columns([entity::tags::Column::UserId, entity::tags::Column::Name])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .to_owned();
+        for name in names.iter() {
+            insert.values_panic([user_id.into(), name.as_str().into()]);
+        }
+        let backend = self.db.get_database_backend();
+        self.db.execute(backend.build(&insert)).await.unwrap();
+
+        entity::tags::Entity::find()
+            .filter(
+                entity::tags::Column::UserId
+                    .eq(user_id)
+                    .and(entity::tags::Column::Name.is_in(names)),
+            )
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn account_tags_insert(&self, account_id: i32, tag_ids: Vec<i32>) {
+        if tag_ids.is_empty() {
+            return;
+        }
+
+        let mut insert = Query::insert()
+            .into_table(entity::account_tags::Entity)
+            .columns([
+                entity::account_tags::Column::AccountId,
+                entity::account_tags::Column::TagId,
+            ])
+            .on_conflict(
+                OnConflict::columns([
+                    entity::account_tags::Column::AccountId,
+                    entity::account_tags::Column::TagId,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .to_owned();
+        for tag_id in tag_ids {
+            insert.values_panic([account_id.into(), tag_id.into()]);
+        }
+        let backend = self.db.get_database_backend();
+        self.db.execute(backend.build(&insert)).await.unwrap();
+    }
+
+    async fn account_tags_list_by_account_ids(&self, account_ids: Vec<i32>) -> Vec<(i32, String)> {
+        if account_ids.is_empty() {
+            return Vec::new();
+        }
+
+        entity::account_tags::Entity::find()
+            .find_also_related(entity::tags::Entity)
+            .filter(entity::account_tags::Column::AccountId.is_in(account_ids))
+            .all(&self.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|(account_tag, tag)| tag.map(|tag| (account_tag.account_id, tag.name)))
+            .collect()
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
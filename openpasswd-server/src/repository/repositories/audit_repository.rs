@@ -0,0 +1,69 @@
+use crate::repository::models::account::PageRequest;
+use crate::repository::models::audit::{AuditFilter, NewAuditEvent};
+use crate::repository::Repository;
+use async_trait::async_trait;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+
+/// Audit events are append-only, there is deliberately no way to update or
+/// delete them other than removing the user.
+#[async_trait]
+pub trait AuditRepository {
+    async fn audit_events_insert(&self, event: NewAuditEvent);
+    async fn audit_events_list(
+        &self,
+        user_id: i32,
+        filter: AuditFilter,
+        page: PageRequest,
+    ) -> (Vec<entity::audit_events::Model>, u64);
+}
+
+#[async_trait]
+impl AuditRepository for Repository {
+    async fn audit_events_insert(&self, event: NewAuditEvent) {
+        let event = entity::audit_events::ActiveModel {
+            user_id: Set(event.user_id),
+            event_type: Set(event.event_type),
+            target: Set(event.target),
+            device: Set(event.device),
+            ip_address: Set(event.ip_address),
+            created_date: Set(event.created_date),
+            ..Default::default()
+        };
+        event.insert(&self.db).await.unwrap();
+    }
+
+    async fn audit_events_list(
+        &self,
+        user_id: i32,
+        filter: AuditFilter,
+        page: PageRequest,
+    ) -> (Vec<entity::audit_events::Model>, u64) {
+        let mut condition = Condition::all().add(entity::audit_events::Column::UserId.eq(user_id));
+
+        if let Some(event_type) = filter.event_type {
+            condition = condition.add(entity::audit_events::Column::EventType.eq(event_type));
+        }
+        if let Some(since) = filter.since {
+            condition = condition.add(entity::audit_events::Column::CreatedDate.gte(since));
+        }
+        if let Some(until) = filter.until {
+            condition = condition.add(entity::audit_events::Column::CreatedDate.lt(until));
+        }
+
+        let paginator = entity::audit_events::Entity::find()
+            .filter(condition)
+            .order_by_desc(entity::audit_events::Column::CreatedDate)
+            .order_by_desc(entity::audit_events::Column::Id)
+            .paginate(&self.db, page.limit as usize);
+        let total = paginator.num_items().await.unwrap() as u64;
+        let items = paginator
+            .fetch_page(page.page.saturating_sub(1) as usize)
+            .await
+            .unwrap();
+
+        (items, total)
+    }
+}
@@ -0,0 +1,160 @@
+use crate::repository::Repository;
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+#[async_trait]
+pub trait AuthorizationRepository {
+    async fn authorization_personal_groups(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::account_groups::Model>;
+    async fn authorization_trashed_personal_groups(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::account_groups::Model>;
+    async fn authorization_memberships(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::organization_members::Model>;
+    async fn authorization_organization_groups(
+        &self,
+        organization_ids: Vec<i32>,
+    ) -> Vec<entity::account_groups::Model>;
+    async fn authorization_trashed_organization_groups(
+        &self,
+        organization_ids: Vec<i32>,
+    ) -> Vec<entity::account_groups::Model>;
+    async fn authorization_collection_members(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::collection_members::Model>;
+    async fn authorization_organization_members(
+        &self,
+        organization_id: i32,
+    ) -> Vec<entity::organization_members::Model>;
+    async fn authorization_group_assignments(
+        &self,
+        group_id: i32,
+    ) -> Vec<entity::collection_members::Model>;
+}
+
+#[async_trait]
+impl AuthorizationRepository for Repository {
+    async fn authorization_personal_groups(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::account_groups::Model> {
+        entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::UserId
+                    .eq(user_id)
+                    .and(entity::account_groups::Column::OrganizationId.is_null())
+                    .and(entity::account_groups::Column::DeletedAt.is_null()),
+            )
+            .order_by_asc(entity::account_groups::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn authorization_trashed_personal_groups(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::account_groups::Model> {
+        entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::UserId
+                    .eq(user_id)
+                    .and(entity::account_groups::Column::OrganizationId.is_null())
+                    .and(entity::account_groups::Column::DeletedAt.is_not_null()),
+            )
+            .order_by_asc(entity::account_groups::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn authorization_memberships(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::organization_members::Model> {
+        entity::organization_members::Entity::find()
+            .filter(entity::organization_members::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn authorization_organization_groups(
+        &self,
+        organization_ids: Vec<i32>,
+    ) -> Vec<entity::account_groups::Model> {
+        if organization_ids.is_empty() {
+            return Vec::new();
+        }
+
+        entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::OrganizationId
+                    .is_in(organization_ids)
+                    .and(entity::account_groups::Column::DeletedAt.is_null()),
+            )
+            .order_by_asc(entity::account_groups::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn authorization_trashed_organization_groups(
+        &self,
+        organization_ids: Vec<i32>,
+    ) -> Vec<entity::account_groups::Model> {
+        if organization_ids.is_empty() {
+            return Vec::new();
+        }
+
+        entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::OrganizationId
+                    .is_in(organization_ids)
+                    .and(entity::account_groups::Column::DeletedAt.is_not_null()),
+            )
+            .order_by_asc(entity::account_groups::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn authorization_collection_members(
+        &self,
+        user_id: i32,
+    ) -> Vec<entity::collection_members::Model> {
+        entity::collection_members::Entity::find()
+            .filter(entity::collection_members::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn authorization_organization_members(
+        &self,
+        organization_id: i32,
+    ) -> Vec<entity::organization_members::Model> {
+        entity::organization_members::Entity::find()
+            .filter(entity::organization_members::Column::OrganizationId.eq(organization_id))
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn authorization_group_assignments(
+        &self,
+        group_id: i32,
+    ) -> Vec<entity::collection_members::Model> {
+        entity::collection_members::Entity::find()
+            .filter(entity::collection_members::Column::AccountGroupsId.eq(group_id))
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+}
@@ -0,0 +1,217 @@
+use crate::repository::models::emergency_access::NewEmergencyAccess;
+use crate::repository::Repository;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+#[async_trait]
+pub trait EmergencyAccessRepository {
+    async fn emergency_access_insert(
+        &self,
+        emergency_access: NewEmergencyAccess,
+    ) -> entity::emergency_access::Model;
+    async fn emergency_access_find_by_id(&self, id: i32)
+        -> Option<entity::emergency_access::Model>;
+    async fn emergency_access_list_by_grantor_id(
+        &self,
+        grantor_id: i32,
+    ) -> Vec<entity::emergency_access::Model>;
+    async fn emergency_access_list_by_grantee(
+        &self,
+        grantee_id: i32,
+        email: &str,
+    ) -> Vec<(entity::emergency_access::Model, entity::users::Model)>;
+    async fn emergency_access_list_by_status(
+        &self,
+        status: &str,
+    ) -> Vec<entity::emergency_access::Model>;
+    async fn emergency_access_accept(&self, id: i32, grantee_id: i32, master_key: Vec<u8>);
+    async fn emergency_access_update_status(
+        &self,
+        id: i32,
+        status: &str,
+        recovery_requested_at: Option<NaiveDateTime>,
+    );
+    async fn emergency_access_delete(&self, id: i32);
+    async fn emergency_access_vault(
+        &self,
+        grantor_id: i32,
+    ) -> Vec<(
+        entity::accounts::Model,
+        Vec<entity::account_passwords::Model>,
+    )>;
+}
+
+#[async_trait]
+impl EmergencyAccessRepository for Repository {
+    async fn emergency_access_insert(
+        &self,
+        emergency_access: NewEmergencyAccess,
+    ) -> entity::emergency_access::Model {
+        let emergency_access = entity::emergency_access::ActiveModel {
+            grantor_id: Set(emergency_access.grantor_id),
+            email: Set(emergency_access.email),
+            access_type: Set(emergency_access.access_type),
+            status: Set(emergency_access.status),
+            wait_days: Set(emergency_access.wait_days),
+            created_date: Set(emergency_access.created_date),
+            ..Default::default()
+        };
+        emergency_access.insert(&self.db).await.unwrap()
+    }
+
+    async fn emergency_access_find_by_id(
+        &self,
+        id: i32,
+    ) -> Option<entity::emergency_access::Model> {
+        entity::emergency_access::Entity::find_by_id(id)
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn emergency_access_list_by_grantor_id(
+        &self,
+        grantor_id: i32,
+    ) -> Vec<entity::emergency_access::Model> {
+        entity::emergency_access::Entity::find()
+            .filter(entity::emergency_access::Column::GrantorId.eq(grantor_id))
+            .order_by_asc(entity::emergency_access::Column::Email)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn emergency_access_list_by_grantee(
+        &self,
+        grantee_id: i32,
+        email: &str,
+    ) -> Vec<(entity::emergency_access::Model, entity::users::Model)> {
+        // Invitations are addressed by email until the contact accepts them.
+        let emergency_accesses = entity::emergency_access::Entity::find()
+            .filter(
+                entity::emergency_access::Column::GranteeId
+                    .eq(grantee_id)
+                    .or(entity::emergency_access::Column::GranteeId
+                        .is_null()
+                        .and(entity::emergency_access::Column::Email.eq(email))),
+            )
+            .order_by_asc(entity::emergency_access::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap();
+
+        let grantor_ids: Vec<i32> = emergency_accesses.iter().map(|e| e.grantor_id).collect();
+        let grantors = if grantor_ids.is_empty() {
+            Vec::new()
+        } else {
+            entity::users::Entity::find()
+                .filter(entity::users::Column::Id.is_in(grantor_ids))
+                .all(&self.db)
+                .await
+                .unwrap()
+        };
+
+        emergency_accesses
+            .into_iter()
+            .filter_map(|emergency_access| {
+                let grantor = grantors
+                    .iter()
+                    .find(|grantor| grantor.id == emergency_access.grantor_id)?;
+                Some((emergency_access, grantor.to_owned()))
+            })
+            .collect()
+    }
+
+    async fn emergency_access_list_by_status(
+        &self,
+        status: &str,
+    ) -> Vec<entity::emergency_access::Model> {
+        entity::emergency_access::Entity::find()
+            .filter(entity::emergency_access::Column::Status.eq(status))
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn emergency_access_accept(&self, id: i32, grantee_id: i32, master_key: Vec<u8>) {
+        let emergency_access = entity::emergency_access::ActiveModel {
+            id: Set(id),
+            grantee_id: Set(Some(grantee_id)),
+            status: Set(String::from("accepted")),
+            master_key: Set(Some(master_key)),
+            ..Default::default()
+        };
+
+        entity::emergency_access::Entity::update(emergency_access)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn emergency_access_update_status(
+        &self,
+        id: i32,
+        status: &str,
+        recovery_requested_at: Option<NaiveDateTime>,
+    ) {
+        let emergency_access = entity::emergency_access::ActiveModel {
+            id: Set(id),
+            status: Set(status.to_owned()),
+            recovery_requested_at: Set(recovery_requested_at),
+            ..Default::default()
+        };
+
+        entity::emergency_access::Entity::update(emergency_access)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn emergency_access_delete(&self, id: i32) {
+        entity::emergency_access::Entity::delete_by_id(id)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn emergency_access_vault(
+        &self,
+        grantor_id: i32,
+    ) -> Vec<(
+        entity::accounts::Model,
+        Vec<entity::account_passwords::Model>,
+    )> {
+        // Only personal groups, organization collections stay with the
+        // organization.
+        let group_ids: Vec<i32> = entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::UserId
+                    .eq(grantor_id)
+                    .and(entity::account_groups::Column::OrganizationId.is_null())
+                    .and(entity::account_groups::Column::DeletedAt.is_null()),
+            )
+            .all(&self.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|group| group.id)
+            .collect();
+        if group_ids.is_empty() {
+            return Vec::new();
+        }
+
+        entity::accounts::Entity::find()
+            .find_with_related(entity::account_passwords::Entity)
+            .filter(
+                entity::accounts::Column::AccountGroupsId
+                    .is_in(group_ids)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .order_by_asc(entity::accounts::Column::Name)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+}
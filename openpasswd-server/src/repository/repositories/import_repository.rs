@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use crate::repository::models::import::NewImportedAccount;
+use crate::repository::Repository;
+use async_trait::async_trait;
+use sea_orm::sea_query::{OnConflict, Query};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, TransactionTrait,
+};
+
+#[async_trait]
+pub trait ImportRepository {
+    /// Group, name and latest username of the accounts in the groups.
+    async fn import_logins(&self, group_ids: Vec<i32>) -> Vec<(i32, String, String)>;
+    /// Creates the groups and accounts all at once, `group_ids` maps the
+    /// names of the existing groups. Returns the created groups and accounts.
+    async fn import_vault(
+        &self,
+        user_id: i32,
+        group_ids: HashMap<String, i32>,
+        new_groups: Vec<String>,
+        accounts: Vec<NewImportedAccount>,
+    ) -> (
+        Vec<entity::account_groups::Model>,
+        Vec<entity::accounts::Model>,
+    );
+}
+
+#[async_trait]
+impl ImportRepository for Repository {
+    async fn import_logins(&self, group_ids: Vec<i32>) -> Vec<(i32, String, String)> {
+        if group_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let accounts = entity::accounts::Entity::find()
+            .filter(
+                entity::accounts::Column::AccountGroupsId
+                    .is_in(group_ids)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .all(&self.db)
+            .await
+            .unwrap();
+        let passwords = entity::account_passwords::Entity::find()
+            .filter(
+                entity::account_passwords::Column::AccountId.is_in(
+                    accounts
+                        .iter()
+                        .map(|account| account.id)
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .all(&self.db)
+            .await
+            .unwrap();
+
+        accounts
+            .into_iter()
+            .map(|account| {
+                let username = passwords
+                    .iter()
+                    .filter(|password| password.account_id == account.id)
+                    .max_by_key(|password| password.id)
+                    .map(|password| password.username.to_owned())
+                    .unwrap_or_default();
+                (account.account_groups_id, account.name, username)
+            })
+            .collect()
+    }
+
+    async fn import_vault(
+        &self,
+        user_id: i32,
+        mut group_ids: HashMap<String, i32>,
+        new_groups: Vec<String>,
+        accounts: Vec<NewImportedAccount>,
+    ) -> (
+        Vec<entity::account_groups::Model>,
+        Vec<entity::accounts::Model>,
+    ) {
+        let txn = self.db.begin().await.unwrap();
+        let backend = txn.get_database_backend();
+
+        let mut groups = Vec::new();
+        for name in new_groups {
+            let group = entity::account_groups::ActiveModel {
+                user_id: Set(user_id),
+                name: Set(name),
+                organization_id: Set(None),
+                ..Default::default()
+            };
+            let group = group.insert(&txn).await.unwrap();
+            group_ids.insert(group.name.to_owned(), group.id);
+            groups.push(group);
+        }
+
+        let mut tag_names: Vec<String> = accounts
+            .iter()
+            .flat_map(|account| account.tags.iter().cloned())
+            .collect();
+        tag_names.sort();
+        tag_names.dedup();
+        let tags = if tag_names.is_empty() {
+            Vec::new()
+        } else {
+            let mut insert = Query::insert()
+                .into_table(entity::tags::Entity)
+                .columns([entity::tags::Column::UserId, entity::tags::Column::Name])
+                .on_conflict(
+                    OnConflict::columns([entity::tags::Column::UserId, entity::tags::Column::Name])
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .to_owned();
+            for name in tag_names.iter() {
+                insert.values_panic([user_id.into(), name.as_str().into()]);
+            }
+            txn.execute(backend.build(&insert)).await.unwrap();
+
+            entity::tags::Entity::find()
+                .filter(
+                    entity::tags::Column::UserId
+                        .eq(user_id)
+                        .and(entity::tags::Column::Name.is_in(tag_names)),
+                )
+                .all(&txn)
+                .await
+                .unwrap()
+        };
+
+        let mut result = Vec::new();
+        for account in accounts {
+            let db_account = entity::accounts::ActiveModel {
+                user_id: Set(user_id),
+                account_groups_id: Set(group_ids[&account.group]),
+                name: Set(account.name),
+                level: Set(account.level),
+                url: Set(account.url),
+                favorite: Set(account.favorite),
+                two_factor: Set(account.two_factor),
+                item_key: Set(Some(account.item_key)),
+                ..Default::default()
+            };
+            let db_account = db_account.insert(&txn).await.unwrap();
+
+            // Inserted in order, the latest credentials get the highest id.
+            for password in account.passwords {
+                let password = entity::account_passwords::ActiveModel {
+                    account_id: Set(db_account.id),
+                    username: Set(password.username),
+                    password: Set(password.password),
+                    created_date: Set(password.created_date),
+                    ..Default::default()
+                };
+                password.insert(&txn).await.unwrap();
+            }
+
+            let tag_ids: Vec<i32> = tags
+                .iter()
+                .filter(|tag| account.tags.contains(&tag.name))
+                .map(|tag| tag.id)
+                .collect();
+            if !tag_ids.is_empty() {
+                let mut insert = Query::insert()
+                    .into_table(entity::account_tags::Entity)
+                    .columns([
+                        entity::account_tags::Column::AccountId,
+                        entity::account_tags::Column::TagId,
+                    ])
+                    .to_owned();
+                for tag_id in tag_ids {
+                    insert.values_panic([db_account.id.into(), tag_id.into()]);
+                }
+                txn.execute(backend.build(&insert)).await.unwrap();
+            }
+
+            result.push(db_account);
+        }
+
+        txn.commit().await.unwrap();
+        (groups, result)
+    }
+}
@@ -1,3 +1,14 @@
 pub mod accounts_repository;
+pub mod audit_repository;
+pub mod authorization_repository;
 pub mod devices_repository;
+pub mod emergency_access_repository;
+pub mod import_repository;
+pub mod organizations_repository;
+pub mod reports_repository;
+pub mod rotation_repository;
+pub mod shares_repository;
+pub mod sync_repository;
+pub mod trash_repository;
 pub mod users_repository;
+pub mod webhooks_repository;
@@ -0,0 +1,327 @@
+use crate::repository::models::organization::{
+    NewOrganization, NewOrganizationInvitation, NewOrganizationMember,
+};
+use crate::repository::Repository;
+use async_trait::async_trait;
+use model::organizations::OrganizationRole;
+use sea_orm::sea_query::{OnConflict, Query};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+
+#[async_trait]
+pub trait OrganizationsRepository {
+    async fn organizations_insert(
+        &self,
+        organization: NewOrganization,
+        owner_id: i32,
+        owner_org_key: Vec<u8>,
+    ) -> entity::organizations::Model;
+    async fn organizations_list_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Vec<(
+        entity::organization_members::Model,
+        entity::organizations::Model,
+    )>;
+    async fn organizations_find_by_id(&self, id: i32) -> Option<entity::organizations::Model>;
+
+    async fn organization_members_find(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+    ) -> Option<entity::organization_members::Model>;
+    async fn organization_members_list(
+        &self,
+        organization_id: i32,
+    ) -> Vec<(entity::organization_members::Model, entity::users::Model)>;
+    async fn organization_members_insert(&self, member: NewOrganizationMember);
+    async fn organization_members_update_role(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        role: String,
+    );
+    async fn organization_members_delete(&self, organization_id: i32, user_id: i32) -> bool;
+
+    async fn organization_invitations_insert(
+        &self,
+        invitation: NewOrganizationInvitation,
+    ) -> entity::organization_invitations::Model;
+    async fn organization_invitations_find_by_token(
+        &self,
+        token: &str,
+    ) -> Option<entity::organization_invitations::Model>;
+    async fn organization_invitations_delete(&self, id: i32);
+
+    async fn collections_list(&self, organization_id: i32) -> Vec<entity::account_groups::Model>;
+    async fn collections_find_by_id(
+        &self,
+        organization_id: i32,
+        group_id: i32,
+    ) -> Option<entity::account_groups::Model>;
+    async fn collection_members_upsert(&self, group_id: i32, user_id: i32, permission: String);
+    async fn collection_members_delete(&self, group_id: i32, user_id: i32) -> bool;
+}
+
+#[async_trait]
+impl OrganizationsRepository for Repository {
+    async fn organizations_insert(
+        &self,
+        organization: NewOrganization,
+        owner_id: i32,
+        owner_org_key: Vec<u8>,
+    ) -> entity::organizations::Model {
+        let txn = self.db.begin().await.unwrap();
+
+        let created_date = organization.created_date;
+        let organization = entity::organizations::ActiveModel {
+            name: Set(organization.name),
+            created_date: Set(created_date),
+            ..Default::default()
+        };
+        let organization = organization.insert(&txn).await.unwrap();
+
+        let owner = entity::organization_members::ActiveModel {
+            organization_id: Set(organization.id),
+            user_id: Set(owner_id),
+            role: Set(OrganizationRole::Owner.as_str().to_owned()),
+            org_key: Set(owner_org_key),
+            created_date: Set(created_date),
+        };
+        entity::organization_members::Entity::insert(owner)
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        txn.commit().await.unwrap();
+        organization
+    }
+
+    async fn organizations_list_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Vec<(
+        entity::organization_members::Model,
+        entity::organizations::Model,
+    )> {
+        entity::organization_members::Entity::find()
+            .find_also_related(entity::organizations::Entity)
+            .filter(entity::organization_members::Column::UserId.eq(user_id))
+            .order_by_asc(entity::organizations::Column::Name)
+            .all(&self.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|(member, organization)| {
+                organization.map(|organization| (member, organization))
+            })
+            .collect()
+    }
+
+    async fn organizations_find_by_id(&self, id: i32) -> Option<entity::organizations::Model> {
+        entity::organizations::Entity::find_by_id(id)
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn organization_members_find(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+    ) -> Option<entity::organization_members::Model> {
+        entity::organization_members::Entity::find_by_id((organization_id, user_id))
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn organization_members_list(
+        &self,
+        organization_id: i32,
+    ) -> Vec<(entity::organization_members::Model, entity::users::Model)> {
+        entity::organization_members::Entity::find()
+            .find_also_related(entity::users::Entity)
+            .filter(entity::organization_members::Column::OrganizationId.eq(organization_id))
+            .order_by_asc(entity::users::Column::Name)
+            .all(&self.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|(member, user)| user.map(|user| (member, user)))
+            .collect()
+    }
+
+    async fn organization_members_insert(&self, member: NewOrganizationMember) {
+        let member = entity::organization_members::ActiveModel {
+            organization_id: Set(member.organization_id),
+            user_id: Set(member.user_id),
+            role: Set(member.role),
+            org_key: Set(member.org_key),
+            created_date: Set(member.created_date),
+        };
+        entity::organization_members::Entity::insert(member)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn organization_members_update_role(
+        &self,
+        organization_id: i32,
+        user_id: i32,
+        role: String,
+    ) {
+        let member = entity::organization_members::ActiveModel {
+            organization_id: Set(organization_id),
+            user_id: Set(user_id),
+            role: Set(role),
+            ..Default::default()
+        };
+
+        entity::organization_members::Entity::update(member)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn organization_members_delete(&self, organization_id: i32, user_id: i32) -> bool {
+        let txn = self.db.begin().await.unwrap();
+
+        // Leaving the organization also removes access to its collections.
+        entity::collection_members::Entity::delete_many()
+            .filter(
+                entity::collection_members::Column::UserId.eq(user_id).and(
+                    entity::collection_members::Column::AccountGroupsId.in_subquery(
+                        Query::select()
+                            .column(entity::account_groups::Column::Id)
+                            .from(entity::account_groups::Entity)
+                            .and_where(
+                                entity::account_groups::Column::OrganizationId.eq(organization_id),
+                            )
+                            .to_owned(),
+                    ),
+                ),
+            )
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        let result = entity::organization_members::Entity::delete_many()
+            .filter(
+                entity::organization_members::Column::OrganizationId
+                    .eq(organization_id)
+                    .and(entity::organization_members::Column::UserId.eq(user_id)),
+            )
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        txn.commit().await.unwrap();
+        result.rows_affected > 0
+    }
+
+    async fn organization_invitations_insert(
+        &self,
+        invitation: NewOrganizationInvitation,
+    ) -> entity::organization_invitations::Model {
+        let invitation = entity::organization_invitations::ActiveModel {
+            organization_id: Set(invitation.organization_id),
+            email: Set(invitation.email),
+            role: Set(invitation.role),
+            token: Set(invitation.token),
+            org_key: Set(invitation.org_key),
+            invited_by: Set(invitation.invited_by),
+            created_date: Set(invitation.created_date),
+            ..Default::default()
+        };
+        invitation.insert(&self.db).await.unwrap()
+    }
+
+    async fn organization_invitations_find_by_token(
+        &self,
+        token: &str,
+    ) -> Option<entity::organization_invitations::Model> {
+        entity::organization_invitations::Entity::find()
+            .filter(entity::organization_invitations::Column::Token.eq(token))
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn organization_invitations_delete(&self, id: i32) {
+        entity::organization_invitations::Entity::delete_by_id(id)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn collections_list(&self, organization_id: i32) -> Vec<entity::account_groups::Model> {
+        entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::OrganizationId
+                    .eq(organization_id)
+                    .and(entity::account_groups::Column::DeletedAt.is_null()),
+            )
+            .order_by_asc(entity::account_groups::Column::Name)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn collections_find_by_id(
+        &self,
+        organization_id: i32,
+        group_id: i32,
+    ) -> Option<entity::account_groups::Model> {
+        entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::Id
+                    .eq(group_id)
+                    .and(entity::account_groups::Column::OrganizationId.eq(organization_id))
+                    .and(entity::account_groups::Column::DeletedAt.is_null()),
+            )
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn collection_members_upsert(&self, group_id: i32, user_id: i32, permission: String) {
+        let insert = Query::insert()
+            .into_table(entity::collection_members::Entity)
+            .columns([
+                entity::collection_members::Column::AccountGroupsId,
+                entity::collection_members::Column::UserId,
+                entity::collection_members::Column::Permission,
+            ])
+            .values_panic([group_id.into(), user_id.into(), permission.into()])
+            .on_conflict(
+                OnConflict::columns([
+                    entity::collection_members::Column::AccountGroupsId,
+                    entity::collection_members::Column::UserId,
+                ])
+                .update_column(entity::collection_members::Column::Permission)
+                .to_owned(),
+            )
+            .to_owned();
+        let backend = self.db.get_database_backend();
+        self.db.execute(backend.build(&insert)).await.unwrap();
+    }
+
+    async fn collection_members_delete(&self, group_id: i32, user_id: i32) -> bool {
+        let result = entity::collection_members::Entity::delete_many()
+            .filter(
+                entity::collection_members::Column::AccountGroupsId
+                    .eq(group_id)
+                    .and(entity::collection_members::Column::UserId.eq(user_id)),
+            )
+            .exec(&self.db)
+            .await
+            .unwrap();
+
+        result.rows_affected > 0
+    }
+}
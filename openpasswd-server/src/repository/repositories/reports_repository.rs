@@ -0,0 +1,44 @@
+use crate::repository::Repository;
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+#[async_trait]
+pub trait ReportsRepository {
+    /// Latest credentials of each account.
+    async fn reports_current_passwords(
+        &self,
+        account_ids: Vec<i32>,
+    ) -> Vec<entity::account_passwords::Model>;
+}
+
+#[async_trait]
+impl ReportsRepository for Repository {
+    async fn reports_current_passwords(
+        &self,
+        account_ids: Vec<i32>,
+    ) -> Vec<entity::account_passwords::Model> {
+        if account_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let passwords = entity::account_passwords::Entity::find()
+            .filter(entity::account_passwords::Column::AccountId.is_in(account_ids))
+            .order_by_asc(entity::account_passwords::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap();
+
+        // Ordered by id, the last row of an account is its current one.
+        let mut current: Vec<entity::account_passwords::Model> = Vec::new();
+        for password in passwords {
+            match current
+                .iter_mut()
+                .find(|latest| latest.account_id == password.account_id)
+            {
+                Some(latest) => *latest = password,
+                None => current.push(password),
+            }
+        }
+        current
+    }
+}
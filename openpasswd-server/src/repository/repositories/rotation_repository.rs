@@ -0,0 +1,66 @@
+use crate::repository::Repository;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder};
+
+#[async_trait]
+pub trait RotationRepository {
+    /// Accounts with a rotation interval, set on them or on their group,
+    /// along with their group. All groups when `group_ids` is `None`.
+    async fn rotation_accounts(
+        &self,
+        group_ids: Option<Vec<i32>>,
+    ) -> Vec<(entity::accounts::Model, entity::account_groups::Model)>;
+    async fn rotation_set_reminded(&self, account_ids: Vec<i32>, reminded_at: NaiveDateTime);
+}
+
+#[async_trait]
+impl RotationRepository for Repository {
+    async fn rotation_accounts(
+        &self,
+        group_ids: Option<Vec<i32>>,
+    ) -> Vec<(entity::accounts::Model, entity::account_groups::Model)> {
+        let mut condition = Condition::all()
+            .add(entity::accounts::Column::DeletedAt.is_null())
+            .add(entity::account_groups::Column::DeletedAt.is_null())
+            .add(
+                Condition::any()
+                    .add(entity::accounts::Column::RotationDays.is_not_null())
+                    .add(entity::account_groups::Column::RotationDays.is_not_null()),
+            );
+        if let Some(group_ids) = group_ids {
+            if group_ids.is_empty() {
+                return Vec::new();
+            }
+            condition = condition.add(entity::accounts::Column::AccountGroupsId.is_in(group_ids));
+        }
+
+        entity::accounts::Entity::find()
+            .find_also_related(entity::account_groups::Entity)
+            .filter(condition)
+            .order_by_asc(entity::accounts::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|(account, group)| group.map(|group| (account, group)))
+            .collect()
+    }
+
+    async fn rotation_set_reminded(&self, account_ids: Vec<i32>, reminded_at: NaiveDateTime) {
+        if account_ids.is_empty() {
+            return;
+        }
+
+        entity::accounts::Entity::update_many()
+            .col_expr(
+                entity::accounts::Column::RotationRemindedAt,
+                Expr::value(reminded_at),
+            )
+            .filter(entity::accounts::Column::Id.is_in(account_ids))
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+use crate::repository::models::account::NewAccountShare;
+use crate::repository::Repository;
+use async_trait::async_trait;
+use sea_orm::sea_query::{OnConflict, Query};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder};
+
+#[async_trait]
+pub trait SharesRepository {
+    async fn shares_upsert(&self, share: NewAccountShare) -> entity::account_shares::Model;
+    async fn shares_list_by_account_id(
+        &self,
+        account_id: i32,
+    ) -> Vec<(entity::account_shares::Model, entity::users::Model)>;
+    async fn shares_list_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Vec<(
+        entity::account_shares::Model,
+        entity::accounts::Model,
+        entity::users::Model,
+    )>;
+    async fn shares_find(
+        &self,
+        account_id: i32,
+        user_id: i32,
+    ) -> Option<entity::account_shares::Model>;
+    async fn shares_delete(&self, share_id: i32, account_id: i32) -> bool;
+}
+
+#[async_trait]
+impl SharesRepository for Repository {
+    async fn shares_upsert(&self, share: NewAccountShare) -> entity::account_shares::Model {
+        // Sharing again with the same user replaces the key and permission.
+        let insert = Query::insert()
+            .into_table(entity::account_shares::Entity)
+            .columns([
+                entity::account_shares::Column::AccountId,
+                entity::account_shares::Column::UserId,
+                entity::account_shares::Column::ItemKey,
+                entity::account_shares::Column::Permission,
+                entity::account_shares::Column::CreatedDate,
+            ])
+            .values_panic([
+                share.account_id.into(),
+                share.user_id.into(),
+                share.item_key.into(),
+                share.permission.into(),
+                share.created_date.into(),
+            ])
+            .on_conflict(
+                OnConflict::columns([
+                    entity::account_shares::Column::AccountId,
+                    entity::account_shares::Column::UserId,
+                ])
+                .update_columns([
+                    entity::account_shares::Column::ItemKey,
+                    entity::account_shares::Column::Permission,
+                ])
+                .to_owned(),
+            )
+            .to_owned();
+        let backend = self.db.get_database_backend();
+        self.db.execute(backend.build(&insert)).await.unwrap();
+
+        entity::account_shares::Entity::find()
+            .filter(
+                entity::account_shares::Column::AccountId
+                    .eq(share.account_id)
+                    .and(entity::account_shares::Column::UserId.eq(share.user_id)),
+            )
+            .one(&self.db)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn shares_list_by_account_id(
+        &self,
+        account_id: i32,
+    ) -> Vec<(entity::account_shares::Model, entity::users::Model)> {
+        entity::account_shares::Entity::find()
+            .find_also_related(entity::users::Entity)
+            .filter(entity::account_shares::Column::AccountId.eq(account_id))
+            .order_by_asc(entity::account_shares::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|(share, user)| user.map(|user| (share, user)))
+            .collect()
+    }
+
+    async fn shares_list_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Vec<(
+        entity::account_shares::Model,
+        entity::accounts::Model,
+        entity::users::Model,
+    )> {
+        let shares = entity::account_shares::Entity::find()
+            .find_also_related(entity::accounts::Entity)
+            .filter(
+                entity::account_shares::Column::UserId
+                    .eq(user_id)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .order_by_asc(entity::accounts::Column::Name)
+            .all(&self.db)
+            .await
+            .unwrap();
+
+        let owner_ids: Vec<i32> = shares
+            .iter()
+            .filter_map(|(_, account)| account.as_ref().map(|account| account.user_id))
+            .collect();
+        let owners = if owner_ids.is_empty() {
+            Vec::new()
+        } else {
+            entity::users::Entity::find()
+                .filter(entity::users::Column::Id.is_in(owner_ids))
+                .all(&self.db)
+                .await
+                .unwrap()
+        };
+
+        shares
+            .into_iter()
+            .filter_map(|(share, account)| {
+                let account = account?;
+                let owner = owners.iter().find(|owner| owner.id == account.user_id)?;
+                Some((share, account, owner.to_owned()))
+            })
+            .collect()
+    }
+
+    async fn shares_find(
+        &self,
+        account_id: i32,
+        user_id: i32,
+    ) -> Option<entity::account_shares::Model> {
+        entity::account_shares::Entity::find()
+            .filter(
+                entity::account_shares::Column::AccountId
+                    .eq(account_id)
+                    .and(entity::account_shares::Column::UserId.eq(user_id)),
+            )
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn shares_delete(&self, share_id: i32, account_id: i32) -> bool {
+        let result = entity::account_shares::Entity::delete_many()
+            .filter(
+                entity::account_shares::Column::Id
+                    .eq(share_id)
+                    .and(entity::account_shares::Column::AccountId.eq(account_id)),
+            )
+            .exec(&self.db)
+            .await
+            .unwrap();
+
+        result.rows_affected > 0
+    }
+}
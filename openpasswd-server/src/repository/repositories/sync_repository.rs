@@ -0,0 +1,133 @@
+use crate::repository::Repository;
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+
+#[async_trait]
+pub trait SyncRepository {
+    /// Bumps the revision of every user and logs the change under it.
+    async fn sync_record(&self, user_ids: &[i32], item_type: &str, item_id: i32, action: &str);
+    async fn sync_revision(&self, user_id: i32) -> i64;
+    async fn sync_changes(
+        &self,
+        user_id: i32,
+        since: i64,
+        until: i64,
+    ) -> Vec<entity::vault_changes::Model>;
+    async fn sync_accounts_by_group_ids(&self, group_ids: Vec<i32>)
+        -> Vec<entity::accounts::Model>;
+    async fn sync_accounts_by_ids(&self, account_ids: Vec<i32>) -> Vec<entity::accounts::Model>;
+}
+
+#[async_trait]
+impl SyncRepository for Repository {
+    async fn sync_record(&self, user_ids: &[i32], item_type: &str, item_id: i32, action: &str) {
+        if user_ids.is_empty() {
+            return;
+        }
+
+        let txn = self.db.begin().await.unwrap();
+        let created_date = chrono::Utc::now().naive_utc();
+        for &user_id in user_ids {
+            // The update locks the user row, concurrent changes get
+            // consecutive revisions.
+            entity::users::Entity::update_many()
+                .col_expr(
+                    entity::users::Column::Revision,
+                    Expr::col(entity::users::Column::Revision).add(1),
+                )
+                .filter(entity::users::Column::Id.eq(user_id))
+                .exec(&txn)
+                .await
+                .unwrap();
+            let revision = match entity::users::Entity::find_by_id(user_id)
+                .one(&txn)
+                .await
+                .unwrap()
+            {
+                Some(user) => user.revision,
+                None => continue,
+            };
+
+            let change = entity::vault_changes::ActiveModel {
+                user_id: Set(user_id),
+                revision: Set(revision),
+                item_type: Set(item_type.to_owned()),
+                item_id: Set(item_id),
+                action: Set(action.to_owned()),
+                created_date: Set(created_date),
+                ..Default::default()
+            };
+            change.insert(&txn).await.unwrap();
+        }
+        txn.commit().await.unwrap();
+    }
+
+    async fn sync_revision(&self, user_id: i32) -> i64 {
+        entity::users::Entity::find_by_id(user_id)
+            .one(&self.db)
+            .await
+            .unwrap()
+            .map(|user| user.revision)
+            .unwrap_or_default()
+    }
+
+    async fn sync_changes(
+        &self,
+        user_id: i32,
+        since: i64,
+        until: i64,
+    ) -> Vec<entity::vault_changes::Model> {
+        entity::vault_changes::Entity::find()
+            .filter(
+                entity::vault_changes::Column::UserId
+                    .eq(user_id)
+                    .and(entity::vault_changes::Column::Revision.gt(since))
+                    .and(entity::vault_changes::Column::Revision.lte(until)),
+            )
+            .order_by_asc(entity::vault_changes::Column::Revision)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn sync_accounts_by_group_ids(
+        &self,
+        group_ids: Vec<i32>,
+    ) -> Vec<entity::accounts::Model> {
+        if group_ids.is_empty() {
+            return Vec::new();
+        }
+
+        entity::accounts::Entity::find()
+            .filter(
+                entity::accounts::Column::AccountGroupsId
+                    .is_in(group_ids)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .order_by_asc(entity::accounts::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn sync_accounts_by_ids(&self, account_ids: Vec<i32>) -> Vec<entity::accounts::Model> {
+        if account_ids.is_empty() {
+            return Vec::new();
+        }
+
+        entity::accounts::Entity::find()
+            .filter(
+                entity::accounts::Column::Id
+                    .is_in(account_ids)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .order_by_asc(entity::accounts::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+}
@@ -0,0 +1,328 @@
+use crate::repository::Repository;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter, TransactionTrait,
+};
+
+#[async_trait]
+pub trait TrashRepository {
+    async fn trash_accounts_move(&self, account_id: i32) -> bool;
+    async fn trash_groups_move(&self, group_id: i32) -> bool;
+    async fn trash_accounts_list(&self, group_ids: Vec<i32>) -> Vec<entity::accounts::Model>;
+    async fn trash_accounts_find_by_id(
+        &self,
+        account_id: i32,
+        group_ids: Vec<i32>,
+    ) -> Option<entity::accounts::Model>;
+    async fn trash_accounts_restore(&self, account_id: i32, group_ids: Vec<i32>) -> bool;
+    async fn trash_groups_restore(&self, group_id: i32, group_ids: Vec<i32>) -> bool;
+    async fn trash_accounts_purge(&self, account_id: i32, group_ids: Vec<i32>) -> bool;
+    async fn trash_groups_purge(&self, group_id: i32, group_ids: Vec<i32>) -> bool;
+    async fn trash_purge_deleted_before(&self, deleted_before: NaiveDateTime) -> u64;
+}
+
+async fn purge_accounts(txn: &DatabaseTransaction, account_ids: Vec<i32>) -> Result<u64, DbErr> {
+    if account_ids.is_empty() {
+        return Ok(0);
+    }
+
+    entity::account_passwords::Entity::delete_many()
+        .filter(entity::account_passwords::Column::AccountId.is_in(account_ids.clone()))
+        .exec(txn)
+        .await?;
+    entity::account_tags::Entity::delete_many()
+        .filter(entity::account_tags::Column::AccountId.is_in(account_ids.clone()))
+        .exec(txn)
+        .await?;
+    entity::account_shares::Entity::delete_many()
+        .filter(entity::account_shares::Column::AccountId.is_in(account_ids.clone()))
+        .exec(txn)
+        .await?;
+    let result = entity::accounts::Entity::delete_many()
+        .filter(entity::accounts::Column::Id.is_in(account_ids))
+        .exec(txn)
+        .await?;
+
+    Ok(result.rows_affected)
+}
+
+async fn purge_groups(txn: &DatabaseTransaction, group_ids: Vec<i32>) -> Result<u64, DbErr> {
+    if group_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let account_ids: Vec<i32> = entity::accounts::Entity::find()
+        .filter(entity::accounts::Column::AccountGroupsId.is_in(group_ids.clone()))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|model| model.id)
+        .collect();
+    let mut purged = purge_accounts(txn, account_ids).await?;
+
+    let result = entity::account_groups::Entity::delete_many()
+        .filter(entity::account_groups::Column::Id.is_in(group_ids))
+        .exec(txn)
+        .await?;
+    purged += result.rows_affected;
+
+    Ok(purged)
+}
+
+#[async_trait]
+impl TrashRepository for Repository {
+    async fn trash_accounts_move(&self, account_id: i32) -> bool {
+        let result = entity::accounts::Entity::update_many()
+            .col_expr(
+                entity::accounts::Column::DeletedAt,
+                Expr::value(chrono::Utc::now().naive_utc()),
+            )
+            .filter(
+                entity::accounts::Column::Id
+                    .eq(account_id)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .exec(&self.db)
+            .await
+            .unwrap();
+
+        result.rows_affected > 0
+    }
+
+    async fn trash_groups_move(&self, group_id: i32) -> bool {
+        let deleted_at = chrono::Utc::now().naive_utc();
+        let txn = self.db.begin().await.unwrap();
+
+        let result = entity::account_groups::Entity::update_many()
+            .col_expr(
+                entity::account_groups::Column::DeletedAt,
+                Expr::value(deleted_at),
+            )
+            .filter(
+                entity::account_groups::Column::Id
+                    .eq(group_id)
+                    .and(entity::account_groups::Column::DeletedAt.is_null()),
+            )
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        if result.rows_affected == 0 {
+            txn.rollback().await.unwrap();
+            return false;
+        }
+
+        // Accounts trashed together with their group share its deleted_at, so
+        // restoring the group brings back exactly those accounts.
+        entity::accounts::Entity::update_many()
+            .col_expr(entity::accounts::Column::DeletedAt, Expr::value(deleted_at))
+            .filter(
+                entity::accounts::Column::AccountGroupsId
+                    .eq(group_id)
+                    .and(entity::accounts::Column::DeletedAt.is_null()),
+            )
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        txn.commit().await.unwrap();
+        true
+    }
+
+    async fn trash_accounts_list(&self, group_ids: Vec<i32>) -> Vec<entity::accounts::Model> {
+        if group_ids.is_empty() {
+            return Vec::new();
+        }
+
+        entity::accounts::Entity::find()
+            .filter(
+                entity::accounts::Column::AccountGroupsId
+                    .is_in(group_ids)
+                    .and(entity::accounts::Column::DeletedAt.is_not_null()),
+            )
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn trash_accounts_find_by_id(
+        &self,
+        account_id: i32,
+        group_ids: Vec<i32>,
+    ) -> Option<entity::accounts::Model> {
+        if group_ids.is_empty() {
+            return None;
+        }
+
+        entity::accounts::Entity::find()
+            .filter(
+                entity::accounts::Column::Id
+                    .eq(account_id)
+                    .and(entity::accounts::Column::AccountGroupsId.is_in(group_ids))
+                    .and(entity::accounts::Column::DeletedAt.is_not_null()),
+            )
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn trash_accounts_restore(&self, account_id: i32, group_ids: Vec<i32>) -> bool {
+        if group_ids.is_empty() {
+            return false;
+        }
+
+        let result = entity::accounts::Entity::update_many()
+            .col_expr(
+                entity::accounts::Column::DeletedAt,
+                Expr::value(Option::<NaiveDateTime>::None),
+            )
+            .filter(
+                entity::accounts::Column::Id
+                    .eq(account_id)
+                    .and(entity::accounts::Column::AccountGroupsId.is_in(group_ids))
+                    .and(entity::accounts::Column::DeletedAt.is_not_null()),
+            )
+            .exec(&self.db)
+            .await
+            .unwrap();
+
+        result.rows_affected > 0
+    }
+
+    async fn trash_groups_restore(&self, group_id: i32, group_ids: Vec<i32>) -> bool {
+        if group_ids.is_empty() {
+            return false;
+        }
+
+        let txn = self.db.begin().await.unwrap();
+
+        let group = entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::Id
+                    .eq(group_id)
+                    .and(entity::account_groups::Column::Id.is_in(group_ids))
+                    .and(entity::account_groups::Column::DeletedAt.is_not_null()),
+            )
+            .one(&txn)
+            .await
+            .unwrap();
+
+        let deleted_at = match group.and_then(|group| group.deleted_at) {
+            Some(deleted_at) => deleted_at,
+            None => {
+                txn.rollback().await.unwrap();
+                return false;
+            }
+        };
+
+        entity::account_groups::Entity::update_many()
+            .col_expr(
+                entity::account_groups::Column::DeletedAt,
+                Expr::value(Option::<NaiveDateTime>::None),
+            )
+            .filter(entity::account_groups::Column::Id.eq(group_id))
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        entity::accounts::Entity::update_many()
+            .col_expr(
+                entity::accounts::Column::DeletedAt,
+                Expr::value(Option::<NaiveDateTime>::None),
+            )
+            .filter(
+                entity::accounts::Column::AccountGroupsId
+                    .eq(group_id)
+                    .and(entity::accounts::Column::DeletedAt.eq(deleted_at)),
+            )
+            .exec(&txn)
+            .await
+            .unwrap();
+
+        txn.commit().await.unwrap();
+        true
+    }
+
+    async fn trash_accounts_purge(&self, account_id: i32, group_ids: Vec<i32>) -> bool {
+        if group_ids.is_empty() {
+            return false;
+        }
+
+        let txn = self.db.begin().await.unwrap();
+
+        let account_ids: Vec<i32> = entity::accounts::Entity::find()
+            .filter(
+                entity::accounts::Column::Id
+                    .eq(account_id)
+                    .and(entity::accounts::Column::AccountGroupsId.is_in(group_ids))
+                    .and(entity::accounts::Column::DeletedAt.is_not_null()),
+            )
+            .all(&txn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|model| model.id)
+            .collect();
+
+        let purged = purge_accounts(&txn, account_ids).await.unwrap();
+        txn.commit().await.unwrap();
+
+        purged > 0
+    }
+
+    async fn trash_groups_purge(&self, group_id: i32, group_ids: Vec<i32>) -> bool {
+        if group_ids.is_empty() {
+            return false;
+        }
+
+        let txn = self.db.begin().await.unwrap();
+
+        let group_ids: Vec<i32> = entity::account_groups::Entity::find()
+            .filter(
+                entity::account_groups::Column::Id
+                    .eq(group_id)
+                    .and(entity::account_groups::Column::Id.is_in(group_ids))
+                    .and(entity::account_groups::Column::DeletedAt.is_not_null()),
+            )
+            .all(&txn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|model| model.id)
+            .collect();
+
+        let purged = purge_groups(&txn, group_ids).await.unwrap();
+        txn.commit().await.unwrap();
+
+        purged > 0
+    }
+
+    async fn trash_purge_deleted_before(&self, deleted_before: NaiveDateTime) -> u64 {
+        let txn = self.db.begin().await.unwrap();
+
+        let group_ids: Vec<i32> = entity::account_groups::Entity::find()
+            .filter(entity::account_groups::Column::DeletedAt.lt(deleted_before))
+            .all(&txn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|model| model.id)
+            .collect();
+        let mut purged = purge_groups(&txn, group_ids).await.unwrap();
+
+        let account_ids: Vec<i32> = entity::accounts::Entity::find()
+            .filter(entity::accounts::Column::DeletedAt.lt(deleted_before))
+            .all(&txn)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|model| model.id)
+            .collect();
+        purged += purge_accounts(&txn, account_ids).await.unwrap();
+
+        txn.commit().await.unwrap();
+        purged
+    }
+}
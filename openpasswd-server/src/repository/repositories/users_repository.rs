@@ -3,7 +3,7 @@ use crate::repository::models::user_password_recovery::NewUserPasswordRecovery;
 use crate::repository::Repository;
 use async_trait::async_trait;
 use sea_orm::ActiveValue::Set;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter};
 
 #[async_trait]
 pub trait UsersRepository {
@@ -12,7 +12,8 @@ pub trait UsersRepository {
     async fn users_update_last_login(&self, user_id: i32);
     async fn users_update_fail_attempts(&self, user_id: i32, fail_attempts: i16);
     async fn users_update_password(&self, user_id: i32, password: String);
-    async fn users_insert(&self, user: NewUser);
+    async fn users_update_key_pair(&self, user_id: i32, public_key: String, private_key: Vec<u8>);
+    async fn users_insert(&self, user: NewUser) -> entity::users::Model;
     async fn users_password_recovery_insert(&self, password_recovery: NewUserPasswordRecovery);
     async fn users_password_recovery_find_by_token(
         &self,
@@ -78,18 +79,31 @@ impl UsersRepository for Repository {
             .unwrap();
     }
 
-    async fn users_insert(&self, new_user: NewUser) {
+    async fn users_update_key_pair(&self, user_id: i32, public_key: String, private_key: Vec<u8>) {
+        let user = entity::users::ActiveModel {
+            id: Set(user_id),
+            public_key: Set(Some(public_key)),
+            private_key: Set(Some(private_key)),
+            ..Default::default()
+        };
+
+        entity::users::Entity::update(user)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn users_insert(&self, new_user: NewUser) -> entity::users::Model {
         let user = entity::users::ActiveModel {
             name: Set(new_user.name),
             email: Set(new_user.email),
             password: Set(new_user.password),
             master_key: Set(new_user.master_key),
+            public_key: Set(new_user.public_key),
+            private_key: Set(new_user.private_key),
             ..Default::default()
         };
-        entity::users::Entity::insert(user)
-            .exec(&self.db)
-            .await
-            .unwrap();
+        user.insert(&self.db).await.unwrap()
     }
 
     async fn users_password_recovery_insert(&self, password_recovery: NewUserPasswordRecovery) {
@@ -98,7 +112,6 @@ impl UsersRepository for Repository {
             token: Set(password_recovery.token),
             issued_at: Set(password_recovery.issued_at),
             valid: Set(password_recovery.valid),
-            ..Default::default()
         };
         entity::user_password_recovery::Entity::insert(user)
             .exec(&self.db)
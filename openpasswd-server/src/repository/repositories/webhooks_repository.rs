@@ -0,0 +1,224 @@
+use crate::repository::models::webhook::{
+    NewWebhook, NewWebhookDelivery, WebhookChanges, WebhookDeliveryAttempt,
+};
+use crate::repository::Repository;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::Query;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+
+#[async_trait]
+pub trait WebhooksRepository {
+    async fn webhooks_insert(&self, webhook: NewWebhook) -> entity::webhooks::Model;
+    async fn webhooks_find_by_id(&self, id: i32) -> Option<entity::webhooks::Model>;
+    async fn webhooks_list(
+        &self,
+        user_id: i32,
+        organization_ids: Vec<i32>,
+    ) -> Vec<entity::webhooks::Model>;
+    /// Enabled webhooks that may be interested in the events of a user: their
+    /// own and the ones of every organization they belong to.
+    async fn webhooks_list_subscribed(&self, user_id: i32) -> Vec<entity::webhooks::Model>;
+    /// Organization of the collection holding the account, `None` for
+    /// personal groups.
+    async fn webhooks_account_organization(&self, account_id: i32) -> Option<i32>;
+    async fn webhooks_update(&self, id: i32, changes: WebhookChanges) -> entity::webhooks::Model;
+    async fn webhooks_delete(&self, id: i32);
+    async fn webhook_deliveries_insert(
+        &self,
+        delivery: NewWebhookDelivery,
+    ) -> entity::webhook_deliveries::Model;
+    async fn webhook_deliveries_list_due(
+        &self,
+        now: NaiveDateTime,
+        limit: u64,
+    ) -> Vec<(entity::webhook_deliveries::Model, entity::webhooks::Model)>;
+    async fn webhook_deliveries_list_by_webhook_id(
+        &self,
+        webhook_id: i32,
+        limit: u64,
+    ) -> Vec<entity::webhook_deliveries::Model>;
+    async fn webhook_deliveries_update(
+        &self,
+        id: i32,
+        attempt: WebhookDeliveryAttempt,
+    ) -> entity::webhook_deliveries::Model;
+}
+
+#[async_trait]
+impl WebhooksRepository for Repository {
+    async fn webhooks_insert(&self, webhook: NewWebhook) -> entity::webhooks::Model {
+        let webhook = entity::webhooks::ActiveModel {
+            user_id: Set(webhook.user_id),
+            organization_id: Set(webhook.organization_id),
+            url: Set(webhook.url),
+            secret: Set(webhook.secret),
+            event_types: Set(webhook.event_types),
+            enabled: Set(true),
+            created_date: Set(webhook.created_date),
+            ..Default::default()
+        };
+        webhook.insert(&self.db).await.unwrap()
+    }
+
+    async fn webhooks_find_by_id(&self, id: i32) -> Option<entity::webhooks::Model> {
+        entity::webhooks::Entity::find_by_id(id)
+            .one(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn webhooks_list(
+        &self,
+        user_id: i32,
+        organization_ids: Vec<i32>,
+    ) -> Vec<entity::webhooks::Model> {
+        entity::webhooks::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(entity::webhooks::Column::UserId.eq(user_id))
+                    .add(entity::webhooks::Column::OrganizationId.is_in(organization_ids)),
+            )
+            .order_by_asc(entity::webhooks::Column::Id)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn webhooks_list_subscribed(&self, user_id: i32) -> Vec<entity::webhooks::Model> {
+        entity::webhooks::Entity::find()
+            .filter(entity::webhooks::Column::Enabled.eq(true))
+            .filter(
+                Condition::any()
+                    .add(entity::webhooks::Column::UserId.eq(user_id))
+                    .add(
+                        entity::webhooks::Column::OrganizationId.in_subquery(
+                            Query::select()
+                                .column(entity::organization_members::Column::OrganizationId)
+                                .from(entity::organization_members::Entity)
+                                .and_where(entity::organization_members::Column::UserId.eq(user_id))
+                                .to_owned(),
+                        ),
+                    ),
+            )
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn webhooks_account_organization(&self, account_id: i32) -> Option<i32> {
+        entity::accounts::Entity::find_by_id(account_id)
+            .find_also_related(entity::account_groups::Entity)
+            .one(&self.db)
+            .await
+            .unwrap()
+            .and_then(|(_, group)| group)
+            .and_then(|group| group.organization_id)
+    }
+
+    async fn webhooks_update(&self, id: i32, changes: WebhookChanges) -> entity::webhooks::Model {
+        let mut webhook = entity::webhooks::ActiveModel {
+            id: Set(id),
+            ..Default::default()
+        };
+        if let Some(url) = changes.url {
+            webhook.url = Set(url);
+        }
+        if let Some(event_types) = changes.event_types {
+            webhook.event_types = Set(event_types);
+        }
+        if let Some(enabled) = changes.enabled {
+            webhook.enabled = Set(enabled);
+        }
+
+        entity::webhooks::Entity::update(webhook)
+            .exec(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn webhooks_delete(&self, id: i32) {
+        entity::webhooks::Entity::delete_by_id(id)
+            .exec(&self.db)
+            .await
+            .unwrap();
+    }
+
+    async fn webhook_deliveries_insert(
+        &self,
+        delivery: NewWebhookDelivery,
+    ) -> entity::webhook_deliveries::Model {
+        let delivery = entity::webhook_deliveries::ActiveModel {
+            webhook_id: Set(delivery.webhook_id),
+            event_type: Set(delivery.event_type),
+            payload: Set(delivery.payload),
+            status: Set(delivery.status),
+            attempts: Set(0),
+            next_attempt_at: Set(delivery.next_attempt_at),
+            created_date: Set(delivery.created_date),
+            ..Default::default()
+        };
+        delivery.insert(&self.db).await.unwrap()
+    }
+
+    async fn webhook_deliveries_list_due(
+        &self,
+        now: NaiveDateTime,
+        limit: u64,
+    ) -> Vec<(entity::webhook_deliveries::Model, entity::webhooks::Model)> {
+        entity::webhook_deliveries::Entity::find()
+            .find_also_related(entity::webhooks::Entity)
+            .filter(entity::webhook_deliveries::Column::Status.eq("pending"))
+            .filter(entity::webhook_deliveries::Column::NextAttemptAt.lte(now))
+            // Pending deliveries of a disabled webhook wait until it is
+            // enabled again.
+            .filter(entity::webhooks::Column::Enabled.eq(true))
+            .order_by_asc(entity::webhook_deliveries::Column::NextAttemptAt)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|(delivery, webhook)| webhook.map(|webhook| (delivery, webhook)))
+            .collect()
+    }
+
+    async fn webhook_deliveries_list_by_webhook_id(
+        &self,
+        webhook_id: i32,
+        limit: u64,
+    ) -> Vec<entity::webhook_deliveries::Model> {
+        entity::webhook_deliveries::Entity::find()
+            .filter(entity::webhook_deliveries::Column::WebhookId.eq(webhook_id))
+            .order_by_desc(entity::webhook_deliveries::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .unwrap()
+    }
+
+    async fn webhook_deliveries_update(
+        &self,
+        id: i32,
+        attempt: WebhookDeliveryAttempt,
+    ) -> entity::webhook_deliveries::Model {
+        let delivery = entity::webhook_deliveries::ActiveModel {
+            id: Set(id),
+            status: Set(attempt.status),
+            attempts: Set(attempt.attempts),
+            next_attempt_at: Set(attempt.next_attempt_at),
+            response_status: Set(attempt.response_status),
+            error: Set(attempt.error),
+            delivered_at: Set(attempt.delivered_at),
+            ..Default::default()
+        };
+
+        entity::webhook_deliveries::Entity::update(delivery)
+            .exec(&self.db)
+            .await
+            .unwrap()
+    }
+}
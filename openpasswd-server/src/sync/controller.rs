@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use super::{
+    dto::sync_error::{SyncError, SyncResult},
+    service::SyncService,
+};
+use crate::{
+    auth::dto::claims::Claims, core::authorization::Authorizer, core::etag::json_with_etag,
+    repository::Repository,
+};
+use axum::{extract::Query, http::HeaderMap, response::IntoResponse, Extension};
+
+pub async fn sync(
+    claims: Claims,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+    Extension(repository): Extension<Repository>,
+) -> SyncResult<impl IntoResponse> {
+    let since = match params.get("since") {
+        Some(since) => since
+            .parse::<i64>()
+            .ok()
+            .filter(|since| *since >= 0)
+            .ok_or(SyncError::InvalidRevision)?,
+        None => 0,
+    };
+
+    let groups = Authorizer::new(repository.clone()).groups(claims.sub).await;
+    let sync_service = SyncService::new(repository);
+    let result = sync_service.sync(claims.sub, groups, since).await?;
+    Ok(json_with_etag(&headers, &result))
+}
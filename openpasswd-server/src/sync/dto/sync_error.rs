@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+pub type SyncResult<T = ()> = Result<T, SyncError>;
+
+#[derive(Debug)]
+pub enum SyncError {
+    InvalidRevision,
+}
+
+impl IntoResponse for SyncError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            SyncError::InvalidRevision => (
+                StatusCode::BAD_REQUEST,
+                String::from("Unknown revision, sync again from scratch"),
+            ),
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
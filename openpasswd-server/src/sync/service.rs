@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use super::dto::sync_error::{SyncError, SyncResult};
+use crate::accounts::stored_generator_profile;
+use crate::core::authorization::GroupAccess;
+use crate::repository::repositories::accounts_repository::AccountsRepository;
+use crate::repository::repositories::shares_repository::SharesRepository;
+use crate::repository::repositories::sync_repository::SyncRepository;
+use model::accounts::{AccountGroupView, AccountView};
+use model::sync::{SyncAction, SyncChanges, SyncItemType, SyncView};
+
+pub struct SyncService<T>
+where
+    T: AccountsRepository + SharesRepository + SyncRepository,
+{
+    repository: T,
+}
+
+impl<T> SyncService<T>
+where
+    T: AccountsRepository + SharesRepository + SyncRepository,
+{
+    pub fn new(repository: T) -> SyncService<T> {
+        SyncService { repository }
+    }
+
+    /// Everything that changed in the vault of the user after revision
+    /// `since`, or the whole vault when `since` is 0. Accounts of a deleted
+    /// group are not listed, clients drop them along with the group.
+    pub async fn sync(
+        self,
+        user_id: i32,
+        groups: Vec<GroupAccess>,
+        since: i64,
+    ) -> SyncResult<SyncView> {
+        // Read first: whatever changes while the vault is collected is sent
+        // again on the next sync, never lost.
+        let revision = self.repository.sync_revision(user_id).await;
+        if since > revision {
+            return Err(SyncError::InvalidRevision);
+        }
+
+        let shared_ids: Vec<i32> = self
+            .repository
+            .shares_list_by_user_id(user_id)
+            .await
+            .into_iter()
+            .map(|(_, account, _)| account.id)
+            .collect();
+
+        if since == 0 {
+            let group_ids: Vec<i32> = groups.iter().map(|access| access.group.id).collect();
+            let mut accounts = self.repository.sync_accounts_by_group_ids(group_ids).await;
+            for account in self.repository.sync_accounts_by_ids(shared_ids).await {
+                if !accounts.iter().any(|a| a.id == account.id) {
+                    accounts.push(account);
+                }
+            }
+
+            return Ok(SyncView {
+                revision,
+                groups: SyncChanges {
+                    created: groups.iter().map(group_view).collect(),
+                    ..Default::default()
+                },
+                accounts: SyncChanges {
+                    created: self.account_views(accounts).await,
+                    ..Default::default()
+                },
+            });
+        }
+
+        let changes = self.repository.sync_changes(user_id, since, revision).await;
+        let group_changes = collapse(&changes, SyncItemType::Group);
+        let mut account_changes = collapse(&changes, SyncItemType::Account);
+
+        let mut result = SyncView {
+            revision,
+            groups: SyncChanges::default(),
+            accounts: SyncChanges::default(),
+        };
+
+        let mut created_group_ids = Vec::new();
+        for (group_id, action) in group_changes {
+            match groups.iter().find(|access| access.group.id == group_id) {
+                Some(access) if action != SyncAction::Deleted => {
+                    if action == SyncAction::Created {
+                        created_group_ids.push(group_id);
+                        result.groups.created.push(group_view(access));
+                    } else {
+                        result.groups.updated.push(group_view(access));
+                    }
+                }
+                _ => result.groups.deleted.push(group_id),
+            }
+        }
+
+        // A group showing up brings every account it holds.
+        for account in self
+            .repository
+            .sync_accounts_by_group_ids(created_group_ids)
+            .await
+        {
+            account_changes.insert(account.id, SyncAction::Created);
+        }
+
+        let accounts = self
+            .repository
+            .sync_accounts_by_ids(account_changes.keys().copied().collect())
+            .await;
+        let visible: Vec<_> = accounts
+            .into_iter()
+            .filter(|account| {
+                shared_ids.contains(&account.id)
+                    || groups
+                        .iter()
+                        .any(|access| access.group.id == account.account_groups_id)
+            })
+            .collect();
+
+        let mut created = Vec::new();
+        let mut updated = Vec::new();
+        for (account_id, action) in account_changes {
+            match visible.iter().find(|account| account.id == account_id) {
+                Some(account) if action == SyncAction::Created => created.push(account.clone()),
+                Some(account) if action == SyncAction::Updated => updated.push(account.clone()),
+                _ => result.accounts.deleted.push(account_id),
+            }
+        }
+        result.accounts.created = self.account_views(created).await;
+        result.accounts.updated = self.account_views(updated).await;
+
+        // Keep the answer stable for its ETag.
+        result.groups.created.sort_by_key(|group| group.id);
+        result.groups.updated.sort_by_key(|group| group.id);
+        result.groups.deleted.sort_unstable();
+        result.accounts.deleted.sort_unstable();
+        Ok(result)
+    }
+
+    async fn account_views(&self, mut accounts: Vec<entity::accounts::Model>) -> Vec<AccountView> {
+        accounts.sort_by_key(|account| account.id);
+        let account_tags = self
+            .repository
+            .account_tags_list_by_account_ids(accounts.iter().map(|a| a.id).collect())
+            .await;
+
+        accounts
+            .into_iter()
+            .map(|account| AccountView {
+                tags: account_tags
+                    .iter()
+                    .filter(|(account_id, _)| *account_id == account.id)
+                    .map(|(_, name)| name.to_owned())
+                    .collect(),
+                id: account.id,
+                name: account.name,
+                group_id: account.account_groups_id,
+                level: account.level,
+                url: account.url,
+                favorite: account.favorite,
+                rotation_days: account.rotation_days,
+                generator_profile: stored_generator_profile(account.generator_profile.as_deref()),
+                two_factor: account.two_factor,
+                revision: account.revision,
+            })
+            .collect()
+    }
+}
+
+/// Net effect of the changes to each item: created when the window starts
+/// with its creation, deleted when it ends with its deletion, updated
+/// otherwise.
+fn collapse(
+    changes: &[entity::vault_changes::Model],
+    item_type: SyncItemType,
+) -> HashMap<i32, SyncAction> {
+    let mut result: HashMap<i32, (SyncAction, SyncAction)> = HashMap::new();
+    for change in changes {
+        if change.item_type.parse::<SyncItemType>() != Ok(item_type) {
+            continue;
+        }
+        let action = change.action.parse().unwrap_or(SyncAction::Updated);
+        result
+            .entry(change.item_id)
+            .and_modify(|(_, last)| *last = action)
+            .or_insert((action, action));
+    }
+
+    result
+        .into_iter()
+        .map(|(item_id, (first, last))| {
+            let action = match (first, last) {
+                (_, SyncAction::Deleted) => SyncAction::Deleted,
+                (SyncAction::Created, _) => SyncAction::Created,
+                _ => SyncAction::Updated,
+            };
+            (item_id, action)
+        })
+        .collect()
+}
+
+fn group_view(access: &GroupAccess) -> AccountGroupView {
+    AccountGroupView {
+        id: access.group.id,
+        name: access.group.name.to_owned(),
+        organization_id: access.group.organization_id,
+        revision: access.group.revision,
+        rotation_days: access.group.rotation_days,
+    }
+}
@@ -0,0 +1,75 @@
+use super::service::TrashService;
+use crate::{
+    accounts::dto::accounts_error::AccountResult,
+    auth::dto::claims::Claims,
+    core::{authorization::Authorizer, cache::Cache},
+    repository::Repository,
+};
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+
+pub async fn list_trash(
+    claims: Claims,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let authorizer = Authorizer::new(repository.clone());
+    let groups = authorizer.groups(claims.sub).await;
+    let trashed_groups = authorizer.trashed_groups(claims.sub).await;
+    let trash_service = TrashService::new(repository, cache);
+    let result = trash_service.list(groups, trashed_groups).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn restore_account(
+    claims: Claims,
+    Path(account_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let groups = Authorizer::new(repository.clone()).groups(claims.sub).await;
+    let trash_service = TrashService::new(repository, cache);
+    trash_service.restore_account(groups, account_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn purge_account(
+    claims: Claims,
+    Path(account_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let groups = Authorizer::new(repository.clone()).groups(claims.sub).await;
+    let trash_service = TrashService::new(repository, cache);
+    trash_service.purge_account(groups, account_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn restore_group(
+    claims: Claims,
+    Path(group_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let trashed_groups = Authorizer::new(repository.clone())
+        .trashed_groups(claims.sub)
+        .await;
+    let trash_service = TrashService::new(repository, cache);
+    trash_service
+        .restore_group(trashed_groups, group_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn purge_group(
+    claims: Claims,
+    Path(group_id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+    Extension(cache): Extension<Cache>,
+) -> AccountResult<impl IntoResponse> {
+    let trashed_groups = Authorizer::new(repository.clone())
+        .trashed_groups(claims.sub)
+        .await;
+    let trash_service = TrashService::new(repository, cache);
+    trash_service.purge_group(trashed_groups, group_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,36 @@
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::{core::cache::Cache, repository::Repository};
+
+pub mod controller;
+mod service;
+
+pub fn route() -> Router {
+    Router::new()
+        .route("/api/trash", get(self::controller::list_trash))
+        .route(
+            "/api/trash/accounts/:id",
+            delete(self::controller::purge_account),
+        )
+        .route(
+            "/api/trash/accounts/:id/restore",
+            post(self::controller::restore_account),
+        )
+        .route(
+            "/api/trash/groups/:id",
+            delete(self::controller::purge_group),
+        )
+        .route(
+            "/api/trash/groups/:id/restore",
+            post(self::controller::restore_group),
+        )
+}
+
+pub async fn purge_task(repository: Repository, cache: Cache) {
+    service::TrashService::new(repository, cache)
+        .purge_task()
+        .await
+}
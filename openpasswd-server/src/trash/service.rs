@@ -0,0 +1,278 @@
+use crate::accounts::dto::accounts_error::{AccountError, AccountResult};
+use crate::core::authorization::GroupAccess;
+use crate::core::cache::Cache;
+use crate::core::notifications::{group_recipients, notify_users};
+use crate::repository::repositories::accounts_repository::AccountsRepository;
+use crate::repository::repositories::authorization_repository::AuthorizationRepository;
+use crate::repository::repositories::shares_repository::SharesRepository;
+use crate::repository::repositories::sync_repository::SyncRepository;
+use crate::repository::repositories::trash_repository::TrashRepository;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use model::accounts::{TrashView, TrashedAccountView, TrashedGroupView};
+use model::notifications::NotificationEvent;
+use model::organizations::CollectionPermission;
+use model::sync::{SyncAction, SyncItemType};
+
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+const MAX_RETENTION_DAYS: i64 = 3650;
+const PURGE_INTERVAL_SECONDS: u64 = 60 * 60;
+
+fn retention() -> chrono::Duration {
+    chrono::Duration::days(retention_days(
+        std::env::var("TRASH_RETENTION_DAYS").ok().as_deref(),
+    ))
+}
+
+/// Days kept in the trash, between 1 and 10 years, the default otherwise.
+fn retention_days(value: Option<&str>) -> i64 {
+    match value.map(str::parse::<i64>) {
+        Some(Ok(days)) if (1..=MAX_RETENTION_DAYS).contains(&days) => days,
+        Some(Ok(days)) => {
+            log::warn!("TRASH_RETENTION_DAYS: {days} is not between 1 and {MAX_RETENTION_DAYS}");
+            DEFAULT_RETENTION_DAYS
+        }
+        Some(Err(e)) => {
+            log::warn!("TRASH_RETENTION_DAYS: {e}");
+            DEFAULT_RETENTION_DAYS
+        }
+        None => DEFAULT_RETENTION_DAYS,
+    }
+}
+
+fn to_rfc3339(datetime: NaiveDateTime) -> String {
+    Utc.from_utc_datetime(&datetime).to_rfc3339()
+}
+
+/// Whoever may move an account (`Edit`) or a group (`Manage`) to the trash
+/// may bring it back or purge it.
+fn allowed(groups: &[GroupAccess], required: CollectionPermission) -> Vec<i32> {
+    groups
+        .iter()
+        .filter(|access| access.permission >= required)
+        .map(|access| access.group.id)
+        .collect()
+}
+
+pub struct TrashService<T>
+where
+    T: AccountsRepository
+        + AuthorizationRepository
+        + SharesRepository
+        + SyncRepository
+        + TrashRepository,
+{
+    repository: T,
+    cache: Cache,
+}
+
+impl<T> TrashService<T>
+where
+    T: AccountsRepository
+        + AuthorizationRepository
+        + SharesRepository
+        + SyncRepository
+        + TrashRepository,
+{
+    pub fn new(repository: T, cache: Cache) -> TrashService<T> {
+        TrashService { repository, cache }
+    }
+
+    pub async fn list(
+        self,
+        groups: Vec<GroupAccess>,
+        trashed_groups: Vec<GroupAccess>,
+    ) -> AccountResult<TrashView> {
+        let retention = retention();
+        let accounts = self
+            .repository
+            .trash_accounts_list(allowed(&groups, CollectionPermission::Edit))
+            .await;
+
+        Ok(TrashView {
+            accounts: accounts
+                .into_iter()
+                .filter_map(|account| {
+                    let deleted_at = account.deleted_at?;
+                    let purge_at = deleted_at.checked_add_signed(retention)?;
+                    Some(TrashedAccountView {
+                        id: account.id,
+                        name: account.name,
+                        group_id: account.account_groups_id,
+                        deleted_at: to_rfc3339(deleted_at),
+                        purge_at: to_rfc3339(purge_at),
+                    })
+                })
+                .collect(),
+            groups: trashed_groups
+                .into_iter()
+                .filter(|access| access.permission >= CollectionPermission::Manage)
+                .filter_map(|GroupAccess { group, .. }| {
+                    let deleted_at = group.deleted_at?;
+                    let purge_at = deleted_at.checked_add_signed(retention)?;
+                    Some(TrashedGroupView {
+                        id: group.id,
+                        name: group.name,
+                        deleted_at: to_rfc3339(deleted_at),
+                        purge_at: to_rfc3339(purge_at),
+                    })
+                })
+                .collect(),
+        })
+    }
+
+    pub async fn restore_account(self, groups: Vec<GroupAccess>, account_id: i32) -> AccountResult {
+        let group_ids = allowed(&groups, CollectionPermission::Edit);
+        let account = match self
+            .repository
+            .trash_accounts_find_by_id(account_id, group_ids.clone())
+            .await
+        {
+            Some(account) => account,
+            None => return Err(AccountError::NotFound),
+        };
+
+        let group = match groups
+            .into_iter()
+            .find(|access| access.group.id == account.account_groups_id)
+        {
+            Some(access) => access.group,
+            None => return Err(AccountError::NotFound),
+        };
+
+        if self
+            .repository
+            .trash_accounts_restore(account_id, group_ids)
+            .await
+        {
+            let user_ids = group_recipients(&self.repository, &group, Some(account_id)).await;
+            self.repository
+                .sync_record(
+                    &user_ids,
+                    SyncItemType::Account.as_str(),
+                    account_id,
+                    SyncAction::Created.as_str(),
+                )
+                .await;
+            notify_users(
+                &self.cache,
+                &user_ids,
+                NotificationEvent::ItemCreated,
+                account_id,
+            )
+            .await;
+            Ok(())
+        } else {
+            Err(AccountError::NotFound)
+        }
+    }
+
+    pub async fn restore_group(
+        self,
+        trashed_groups: Vec<GroupAccess>,
+        group_id: i32,
+    ) -> AccountResult {
+        if self
+            .repository
+            .trash_groups_restore(
+                group_id,
+                allowed(&trashed_groups, CollectionPermission::Manage),
+            )
+            .await
+        {
+            if let Some(group) = self.repository.accounts_groups_find_by_id(group_id).await {
+                let user_ids = group_recipients(&self.repository, &group, None).await;
+                self.repository
+                    .sync_record(
+                        &user_ids,
+                        SyncItemType::Group.as_str(),
+                        group.id,
+                        SyncAction::Created.as_str(),
+                    )
+                    .await;
+                notify_users(
+                    &self.cache,
+                    &user_ids,
+                    NotificationEvent::GroupCreated,
+                    group.id,
+                )
+                .await;
+            }
+            Ok(())
+        } else {
+            Err(AccountError::NotFound)
+        }
+    }
+
+    pub async fn purge_account(self, groups: Vec<GroupAccess>, account_id: i32) -> AccountResult {
+        if self
+            .repository
+            .trash_accounts_purge(account_id, allowed(&groups, CollectionPermission::Edit))
+            .await
+        {
+            Ok(())
+        } else {
+            Err(AccountError::NotFound)
+        }
+    }
+
+    pub async fn purge_group(
+        self,
+        trashed_groups: Vec<GroupAccess>,
+        group_id: i32,
+    ) -> AccountResult {
+        if self
+            .repository
+            .trash_groups_purge(
+                group_id,
+                allowed(&trashed_groups, CollectionPermission::Manage),
+            )
+            .await
+        {
+            Ok(())
+        } else {
+            Err(AccountError::NotFound)
+        }
+    }
+
+    /// Permanently removes everything that has been in the trash for longer
+    /// than `TRASH_RETENTION_DAYS`, checking once an hour.
+    pub async fn purge_task(self) {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(PURGE_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+
+            let deleted_before = match Utc::now().naive_utc().checked_sub_signed(retention()) {
+                Some(deleted_before) => deleted_before,
+                None => continue,
+            };
+            let purged = self
+                .repository
+                .trash_purge_deleted_before(deleted_before)
+                .await;
+            if purged > 0 {
+                log::info!("Purged {purged} items from the trash");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retention_days, DEFAULT_RETENTION_DAYS};
+
+    #[test]
+    fn retention_days_are_bounded() {
+        assert_eq!(DEFAULT_RETENTION_DAYS, retention_days(None));
+        assert_eq!(90, retention_days(Some("90")));
+        assert_eq!(3650, retention_days(Some("3650")));
+        assert_eq!(DEFAULT_RETENTION_DAYS, retention_days(Some("0")));
+        assert_eq!(DEFAULT_RETENTION_DAYS, retention_days(Some("-1")));
+        assert_eq!(DEFAULT_RETENTION_DAYS, retention_days(Some("3651")));
+        assert_eq!(
+            DEFAULT_RETENTION_DAYS,
+            retention_days(Some("9223372036854775807"))
+        );
+        assert_eq!(DEFAULT_RETENTION_DAYS, retention_days(Some("a month")));
+    }
+}
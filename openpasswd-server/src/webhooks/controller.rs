@@ -0,0 +1,64 @@
+use super::{dto::webhook_error::WebhookResult, service::WebhookService};
+use crate::{auth::dto::claims::Claims, core::validator::ValidatedJson, repository::Repository};
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use model::webhooks::{WebhookRegister, WebhookUpdate};
+
+pub async fn register(
+    claims: Claims,
+    ValidatedJson(register): ValidatedJson<WebhookRegister>,
+    Extension(repository): Extension<Repository>,
+) -> WebhookResult<impl IntoResponse> {
+    let webhook_service = WebhookService::new(repository);
+    let result = webhook_service.register(claims.sub, register).await?;
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+pub async fn list(
+    claims: Claims,
+    Extension(repository): Extension<Repository>,
+) -> WebhookResult<impl IntoResponse> {
+    let webhook_service = WebhookService::new(repository);
+    let result = webhook_service.list(claims.sub).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn update(
+    claims: Claims,
+    Path(id): Path<i32>,
+    ValidatedJson(changes): ValidatedJson<WebhookUpdate>,
+    Extension(repository): Extension<Repository>,
+) -> WebhookResult<impl IntoResponse> {
+    let webhook_service = WebhookService::new(repository);
+    let result = webhook_service.update(claims.sub, id, changes).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn delete(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> WebhookResult<impl IntoResponse> {
+    let webhook_service = WebhookService::new(repository);
+    webhook_service.delete(claims.sub, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_deliveries(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> WebhookResult<impl IntoResponse> {
+    let webhook_service = WebhookService::new(repository);
+    let result = webhook_service.list_deliveries(claims.sub, id).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
+
+pub async fn test(
+    claims: Claims,
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Repository>,
+) -> WebhookResult<impl IntoResponse> {
+    let webhook_service = WebhookService::new(repository);
+    let result = webhook_service.test(claims.sub, id).await?;
+    Ok((StatusCode::OK, Json(result)))
+}
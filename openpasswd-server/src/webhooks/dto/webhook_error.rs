@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use model::error::ErrorResponse;
+
+pub type WebhookResult<T = ()> = Result<T, WebhookError>;
+
+#[derive(Debug)]
+pub enum WebhookError {
+    NotFound,
+    Forbidden,
+}
+
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            WebhookError::NotFound => (StatusCode::NOT_FOUND, String::from("Invalid Path")),
+            WebhookError::Forbidden => (
+                StatusCode::FORBIDDEN,
+                String::from("Insufficient permissions"),
+            ),
+        };
+        let body = Json(ErrorResponse {
+            error: HashMap::from([(String::from("message"), error_message)]),
+        });
+        (status, body).into_response()
+    }
+}
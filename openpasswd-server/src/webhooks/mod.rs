@@ -0,0 +1,34 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+
+use crate::repository::Repository;
+
+pub mod controller;
+pub mod dto;
+mod service;
+
+pub fn route() -> Router {
+    Router::new()
+        .route(
+            "/api/webhooks",
+            post(self::controller::register).get(self::controller::list),
+        )
+        .route(
+            "/api/webhooks/:id",
+            put(self::controller::update).delete(self::controller::delete),
+        )
+        .route(
+            "/api/webhooks/:id/deliveries",
+            get(self::controller::list_deliveries),
+        )
+        .route("/api/webhooks/:id/test", post(self::controller::test))
+}
+
+/// Sends queued deliveries and retries the failed ones with back-off.
+pub async fn delivery_task(repository: Repository) {
+    service::WebhookService::new(repository)
+        .delivery_task()
+        .await
+}
@@ -0,0 +1,264 @@
+use super::dto::webhook_error::{WebhookError, WebhookResult};
+use crate::core::authorization::can_administer;
+use crate::core::webhooks::{deliver, join_event_types, split_event_types};
+use crate::repository::models::webhook::{NewWebhook, NewWebhookDelivery, WebhookChanges};
+use crate::repository::repositories::organizations_repository::OrganizationsRepository;
+use crate::repository::repositories::webhooks_repository::WebhooksRepository;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use model::organizations::OrganizationRole;
+use model::webhooks::{
+    WebhookDeliveryStatus, WebhookDeliveryView, WebhookEvent, WebhookRegister, WebhookUpdate,
+    WebhookView, WEBHOOK_PING_EVENT,
+};
+use model::List;
+use rand::RngCore;
+
+const DELIVERY_INTERVAL_SECONDS: u64 = 10;
+const DELIVERY_BATCH_SIZE: u64 = 50;
+const DELIVERY_LOG_LIMIT: u64 = 100;
+
+fn to_rfc3339(datetime: NaiveDateTime) -> String {
+    Utc.from_utc_datetime(&datetime).to_rfc3339()
+}
+
+fn webhook_view(webhook: entity::webhooks::Model, secret: Option<String>) -> WebhookView {
+    WebhookView {
+        id: webhook.id,
+        url: webhook.url,
+        event_types: split_event_types(&webhook.event_types),
+        organization_id: webhook.organization_id,
+        enabled: webhook.enabled,
+        created_date: to_rfc3339(webhook.created_date),
+        secret,
+    }
+}
+
+fn delivery_view(delivery: entity::webhook_deliveries::Model) -> WebhookDeliveryView {
+    let status = delivery
+        .status
+        .parse()
+        .unwrap_or(WebhookDeliveryStatus::Failed);
+    WebhookDeliveryView {
+        id: delivery.id,
+        event_type: delivery.event_type,
+        status,
+        attempts: delivery.attempts,
+        response_status: delivery.response_status,
+        error: delivery.error,
+        created_date: to_rfc3339(delivery.created_date),
+        next_attempt_at: (status == WebhookDeliveryStatus::Pending)
+            .then(|| to_rfc3339(delivery.next_attempt_at)),
+        delivered_at: delivery.delivered_at.map(to_rfc3339),
+    }
+}
+
+pub struct WebhookService<T>
+where
+    T: WebhooksRepository + OrganizationsRepository,
+{
+    repository: T,
+}
+
+impl<T> WebhookService<T>
+where
+    T: WebhooksRepository + OrganizationsRepository,
+{
+    pub fn new(repository: T) -> WebhookService<T> {
+        WebhookService { repository }
+    }
+
+    pub async fn register(
+        self,
+        user_id: i32,
+        register: WebhookRegister,
+    ) -> WebhookResult<WebhookView> {
+        if let Some(organization_id) = register.organization_id {
+            self.require_admin(organization_id, user_id).await?;
+        }
+
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let secret = hex::encode(secret);
+
+        let new_webhook = NewWebhook {
+            // Organization webhooks outlive the admin who created them.
+            user_id: register.organization_id.is_none().then_some(user_id),
+            organization_id: register.organization_id,
+            url: register.url,
+            secret: secret.to_owned(),
+            event_types: join_event_types(&register.event_types),
+            created_date: Utc::now().naive_utc(),
+        };
+        let webhook = self.repository.webhooks_insert(new_webhook).await;
+
+        Ok(webhook_view(webhook, Some(secret)))
+    }
+
+    pub async fn list(self, user_id: i32) -> WebhookResult<List<WebhookView>> {
+        let organization_ids = self
+            .repository
+            .organizations_list_by_user_id(user_id)
+            .await
+            .into_iter()
+            .filter(|(member, _)| can_administer(Self::role(&member.role)))
+            .map(|(member, _)| member.organization_id)
+            .collect();
+        let webhooks = self
+            .repository
+            .webhooks_list(user_id, organization_ids)
+            .await;
+
+        Ok(List {
+            total: webhooks.len() as u32,
+            items: webhooks
+                .into_iter()
+                .map(|webhook| webhook_view(webhook, None))
+                .collect(),
+            pagination: None,
+        })
+    }
+
+    pub async fn update(
+        self,
+        user_id: i32,
+        id: i32,
+        changes: WebhookUpdate,
+    ) -> WebhookResult<WebhookView> {
+        let webhook = self.find_managed(user_id, id).await?;
+        let changes = WebhookChanges {
+            url: changes.url,
+            event_types: changes
+                .event_types
+                .map(|event_types| join_event_types(&event_types)),
+            enabled: changes.enabled,
+        };
+        let webhook = self.repository.webhooks_update(webhook.id, changes).await;
+        Ok(webhook_view(webhook, None))
+    }
+
+    pub async fn delete(self, user_id: i32, id: i32) -> WebhookResult {
+        let webhook = self.find_managed(user_id, id).await?;
+        self.repository.webhooks_delete(webhook.id).await;
+        Ok(())
+    }
+
+    pub async fn list_deliveries(
+        self,
+        user_id: i32,
+        id: i32,
+    ) -> WebhookResult<List<WebhookDeliveryView>> {
+        let webhook = self.find_managed(user_id, id).await?;
+        let deliveries = self
+            .repository
+            .webhook_deliveries_list_by_webhook_id(webhook.id, DELIVERY_LOG_LIMIT)
+            .await;
+
+        Ok(List {
+            total: deliveries.len() as u32,
+            items: deliveries.into_iter().map(delivery_view).collect(),
+            pagination: None,
+        })
+    }
+
+    /// Sends a ping right away and reports the outcome, test deliveries are
+    /// never retried.
+    pub async fn test(self, user_id: i32, id: i32) -> WebhookResult<WebhookDeliveryView> {
+        let webhook = self.find_managed(user_id, id).await?;
+
+        let now = Utc::now().naive_utc();
+        let event = WebhookEvent {
+            event_type: WEBHOOK_PING_EVENT.to_owned(),
+            user_id,
+            target: Some(format!("webhook:{}", webhook.id)),
+            device: None,
+            ip_address: None,
+            created_date: to_rfc3339(now),
+        };
+        let delivery = self
+            .repository
+            .webhook_deliveries_insert(NewWebhookDelivery {
+                webhook_id: webhook.id,
+                event_type: WEBHOOK_PING_EVENT.to_owned(),
+                payload: serde_json::to_string(&event).unwrap(),
+                status: WebhookDeliveryStatus::Pending.as_str().to_owned(),
+                next_attempt_at: now,
+                created_date: now,
+            })
+            .await;
+
+        let mut attempt = deliver(&webhook, &delivery).await;
+        if attempt.status == WebhookDeliveryStatus::Pending.as_str() {
+            attempt.status = WebhookDeliveryStatus::Failed.as_str().to_owned();
+        }
+        let delivery = self
+            .repository
+            .webhook_deliveries_update(delivery.id, attempt)
+            .await;
+
+        Ok(delivery_view(delivery))
+    }
+
+    pub async fn delivery_task(self) {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(DELIVERY_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+
+            let now = Utc::now().naive_utc();
+            for (delivery, webhook) in self
+                .repository
+                .webhook_deliveries_list_due(now, DELIVERY_BATCH_SIZE)
+                .await
+            {
+                let attempt = deliver(&webhook, &delivery).await;
+                if attempt.status == WebhookDeliveryStatus::Failed.as_str() {
+                    log::warn!(
+                        "Webhook delivery {} failed after {} attempts",
+                        delivery.id,
+                        attempt.attempts
+                    );
+                }
+                self.repository
+                    .webhook_deliveries_update(delivery.id, attempt)
+                    .await;
+            }
+        }
+    }
+
+    /// Personal webhooks are managed by their owner, organization webhooks by
+    /// the organization admins.
+    async fn find_managed(&self, user_id: i32, id: i32) -> WebhookResult<entity::webhooks::Model> {
+        let webhook = self
+            .repository
+            .webhooks_find_by_id(id)
+            .await
+            .ok_or(WebhookError::NotFound)?;
+
+        match (webhook.user_id, webhook.organization_id) {
+            (Some(owner_id), _) if owner_id == user_id => Ok(webhook),
+            (_, Some(organization_id)) => {
+                self.require_admin(organization_id, user_id).await?;
+                Ok(webhook)
+            }
+            _ => Err(WebhookError::NotFound),
+        }
+    }
+
+    async fn require_admin(&self, organization_id: i32, user_id: i32) -> WebhookResult {
+        let member = self
+            .repository
+            .organization_members_find(organization_id, user_id)
+            .await
+            .ok_or(WebhookError::NotFound)?;
+
+        if can_administer(Self::role(&member.role)) {
+            Ok(())
+        } else {
+            Err(WebhookError::Forbidden)
+        }
+    }
+
+    fn role(role: &str) -> OrganizationRole {
+        role.parse().unwrap_or(OrganizationRole::Member)
+    }
+}